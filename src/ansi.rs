@@ -0,0 +1,525 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::{Attribute, Color, Style};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{borrow::Cow, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// A tiny state machine to find escape sequences.
+///
+/// Recognizes CSI sequences, including SGR sequences like `\x1B[1;31m`, OSC
+/// sequences, including hyperlinks like `\x1B]8;;url\x1B\\`, and any other
+/// two-character `ESC` sequence whose second character is ASCII. A truncated
+/// sequence at the end of the input is considered part of the sequence.
+#[derive(Default)]
+pub(crate) struct Escapes {
+    state: State,
+}
+
+#[derive(Default)]
+enum State {
+    #[default]
+    Text,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape,
+}
+
+impl Escapes {
+    /// Feeds `c` to the state machine. Returns `true` if `c` is part of an
+    /// escape sequence.
+    pub fn is_escape(&mut self, c: char) -> bool {
+        self.state = match (&self.state, c) {
+            (State::Text, '\x1B') => State::Escape,
+            (State::Text, _) => return false,
+            (State::Escape, '[') => State::Csi,
+            (State::Escape, ']') => State::Osc,
+            // Consuming a non-ASCII char, or just the lead byte of one when fed
+            // bytes, could split it. It's text, not part of the sequence.
+            (State::Escape | State::OscEscape, c) if !c.is_ascii() => {
+                self.state = State::Text;
+                return false;
+            }
+            (State::Escape, _) => State::Text,
+            (State::Csi, '\x40'..='\x7E') => State::Text,
+            (State::Csi, _) => State::Csi,
+            (State::Osc, '\x1B') => State::OscEscape,
+            (State::Osc, '\x07') => State::Text,
+            (State::Osc, _) => State::Osc,
+            (State::OscEscape, _) => State::Text,
+        };
+
+        true
+    }
+
+    /// Returns `true` if the state machine is within an escape sequence.
+    #[cfg(feature = "alloc")]
+    pub fn in_sequence(&self) -> bool {
+        !matches!(self.state, State::Text)
+    }
+}
+
+/// A writer that measures, without storing, what's written to it.
+///
+/// Counts all `char`s and the `char`s outside of escape sequences, as
+/// recognized by [`Escapes`], as well as the `pad` characters in all, at the
+/// start, and at the end of what's written.
+#[derive(Default)]
+pub(crate) struct Measure {
+    escapes: Escapes,
+    pub pad: char,
+    pub chars: usize,
+    pub width: usize,
+    pub pads: usize,
+    pub leading: usize,
+    pub trailing: usize,
+}
+
+impl Measure {
+    pub fn new(pad: char) -> Self {
+        Measure { pad, ..Measure::default() }
+    }
+}
+
+impl fmt::Write for Measure {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c == self.pad {
+                true => {
+                    self.leading += usize::from(self.leading == self.chars);
+                    self.trailing += 1;
+                    self.pads += 1;
+                }
+                false => self.trailing = 0,
+            }
+
+            self.chars += 1;
+            if self.escapes.is_escape(c) {
+                continue;
+            }
+
+            self.width += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// A writer that measures what's written to it, as [`Measure`] does, and stores
+/// it if it fits in `N` bytes.
+pub(crate) struct Buffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+    overflowed: bool,
+    pub measure: Measure,
+}
+
+impl<const N: usize> Buffer<N> {
+    pub fn new(pad: char) -> Self {
+        Buffer { bytes: [0; N], len: 0, overflowed: false, measure: Measure::new(pad) }
+    }
+
+    /// Returns what was written or `None` if it didn't fit.
+    pub fn as_str(&self) -> Option<&str> {
+        match self.overflowed {
+            true => None,
+            false => core::str::from_utf8(&self.bytes[..self.len]).ok(),
+        }
+    }
+}
+
+impl<const N: usize> fmt::Write for Buffer<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.measure.write_str(s)?;
+        match self.bytes.get_mut(self.len..self.len + s.len()) {
+            Some(bytes) if !self.overflowed => {
+                bytes.copy_from_slice(s.as_bytes());
+                self.len += s.len();
+            }
+            _ => self.overflowed = true,
+        }
+
+        Ok(())
+    }
+}
+
+/// A writer that forwards to `out` everything outside of escape sequences, as
+/// recognized by [`Escapes`].
+pub(crate) struct Stripper<'a> {
+    out: &'a mut dyn fmt::Write,
+    escapes: Escapes,
+}
+
+impl<'a> Stripper<'a> {
+    pub fn new(out: &'a mut dyn fmt::Write) -> Self {
+        Stripper { out, escapes: Escapes::default() }
+    }
+}
+
+impl fmt::Write for Stripper<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            if self.escapes.is_escape(c) {
+                if start < i {
+                    self.out.write_str(&s[start..i])?;
+                }
+
+                start = i + c.len_utf8();
+            }
+        }
+
+        self.out.write_str(&s[start..])
+    }
+}
+
+/// Returns `string` with all ANSI escape sequences removed.
+///
+/// Removes styling (SGR) sequences like those emitted by [`Painted`] as well
+/// as hyperlink (OSC) sequences like those emitted by the `hyperlink` module.
+/// Any other `ESC`-initiated sequence is removed as well. A truncated escape
+/// sequence at the end of `string` is removed in its entirety.
+///
+/// If `string` contains no escape sequences, it is returned as-is without
+/// allocating.
+///
+/// [`Painted`]: crate::Painted
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use yansi::Paint;
+///
+/// let styled = format!("{} and {}", "Stop".red(), "Go".green().bold());
+/// assert_eq!(yansi::strip(&styled), "Stop and Go");
+///
+/// let plain = "Nothing to see here.";
+/// assert!(matches!(yansi::strip(plain), Cow::Borrowed(_)));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub fn strip(string: &str) -> Cow<'_, str> {
+    if !string.contains('\x1B') {
+        return Cow::Borrowed(string);
+    }
+
+    let mut escapes = Escapes::default();
+    Cow::Owned(string.replace(|c| escapes.is_escape(c), ""))
+}
+
+/// Splits `string` into runs of text, each paired with the [`Style`] in effect
+/// for the run, by interpreting the ANSI escape sequences in `string`.
+///
+/// This is the inverse of rendering: styling (SGR) sequences like those
+/// emitted by [`Painted`] update the style in effect, starting from
+/// [`Style::new()`], and are otherwise removed, as are all other escape
+/// sequences, exactly as by [`strip()`]. The following SGR codes are
+/// understood; all others are skipped:
+///
+///   * `0`, or no code at all, resets the style to `Style::new()`.
+///   * Every [`Attribute`] code, including `4:3`, as well as the codes that
+///     disable attributes: `22` through `29`, `54`, and `55`.
+///   * Foreground, background, and underline colors in all forms, including
+///     `38;5;n`, `38;2;r;g;b`, and their `:`-separated variants. `39`, `49`,
+///     and `59` set [`Color::Primary`]. As base colors are written as such, an
+///     underline color of `58;5;n` with `n < 16` sets a base color.
+///
+/// Empty runs are omitted. Adjacent runs are not merged, even if their styles
+/// are equal. Quirks, conditions, and hyperlink targets are never set.
+///
+/// [`Painted`]: crate::Painted
+/// [`strip()`]: crate::strip()
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Style, Color::*};
+///
+/// let styled = format!("{} and {}", "Stop".red(), "Go".green().bold());
+/// assert_eq!(yansi::parse_ansi(&styled), vec![
+///     (Red.foreground(), "Stop"),
+///     (Style::new(), " and "),
+///     (Green.bold(), "Go"),
+/// ]);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub fn parse_ansi(string: &str) -> Vec<(Style, &str)> {
+    let mut runs = Vec::new();
+    let (mut escapes, mut style, mut start) = (Escapes::default(), Style::new(), 0);
+    for (i, c) in string.char_indices() {
+        let in_text = !escapes.in_sequence();
+        if !escapes.is_escape(c) {
+            continue;
+        }
+
+        if in_text {
+            if start < i {
+                runs.push((style, &string[start..i]));
+            }
+
+            start = i;
+        }
+
+        if !escapes.in_sequence() {
+            let end = i + c.len_utf8();
+            let sgr = string[start..end].strip_prefix("\x1B[")
+                .and_then(|seq| seq.strip_suffix('m'))
+                .filter(|params| params.bytes().all(|b| matches!(b, b'0'..=b'9' | b';' | b':')));
+
+            if let Some(params) = sgr {
+                style = apply_sgr(style, params);
+            }
+
+            start = end;
+        }
+    }
+
+    if !escapes.in_sequence() && start < string.len() {
+        runs.push((style, &string[start..]));
+    }
+
+    runs
+}
+
+/// Applies the SGR parameters `params`, i.e., the `1;31` in `\x1B[1;31m`, to
+/// `style` as described in [`parse_ansi()`].
+#[cfg(feature = "alloc")]
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    const UNDERLINES: [Attribute; 3] = [
+        Attribute::Underline,
+        Attribute::DoubleUnderline,
+        Attribute::CurlyUnderline,
+    ];
+
+    fn without(style: Style, attrs: &[Attribute]) -> Style {
+        attrs.iter().fold(style, |style, &attr| style.without(attr))
+    }
+
+    let mut params = params.split(';');
+    while let Some(param) = params.next() {
+        let mut subparams = param.split(':');
+        let code = match subparams.next() {
+            Some("") | None => 0,
+            Some(code) => match code.parse::<u8>() {
+                Ok(code) => code,
+                Err(_) => continue,
+            },
+        };
+
+        let mut extended = |subparams: core::str::Split<'_, char>| match param.contains(':') {
+            true => extended_color(subparams, true),
+            false => extended_color(params.by_ref(), false),
+        };
+
+        style = match code {
+            0 => Style::new(),
+            1 => style.bold(),
+            2 => style.dim(),
+            3 => style.italic(),
+            4 => match subparams.next() {
+                Some("0") => without(style, &UNDERLINES),
+                Some("2") => style.double_underline(),
+                Some("3") => style.curly_underline(),
+                _ => style.underline(),
+            },
+            5 => style.blink(),
+            6 => style.rapid_blink(),
+            7 => style.invert(),
+            8 => style.conceal(),
+            9 => style.strike(),
+            21 => style.double_underline(),
+            51 => style.framed(),
+            52 => style.encircled(),
+            53 => style.overline(),
+            22 => without(style, &[Attribute::Bold, Attribute::Dim]),
+            23 => style.without(Attribute::Italic),
+            24 => without(style, &UNDERLINES),
+            25 => without(style, &[Attribute::Blink, Attribute::RapidBlink]),
+            27 => style.without(Attribute::Invert),
+            28 => style.without(Attribute::Conceal),
+            29 => style.without(Attribute::Strike),
+            54 => without(style, &[Attribute::Framed, Attribute::Encircled]),
+            55 => style.without(Attribute::Overline),
+            30..=37 => style.fg(Color::Fixed(code - 30).to_base()),
+            90..=97 => style.fg(Color::Fixed(code - 90 + 8).to_base()),
+            40..=47 => style.bg(Color::Fixed(code - 40).to_base()),
+            100..=107 => style.bg(Color::Fixed(code - 100 + 8).to_base()),
+            38 => match extended(subparams) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            48 => match extended(subparams) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            // Base underline colors are written as `58;5;n` with `n < 16`.
+            58 => match extended(subparams) {
+                Some(color @ Color::Fixed(0..=15)) => style.underline_color(color.to_base()),
+                Some(color) => style.underline_color(color),
+                None => style,
+            },
+            39 => style.fg(Color::Primary),
+            49 => style.bg(Color::Primary),
+            59 => style.underline_color(Color::Primary),
+            _ => style,
+        };
+    }
+
+    style
+}
+
+/// Parses the parameters of an extended color, i.e., the `5;n` or `2;r;g;b`
+/// following a `38`, `48`, or `58`, from `params`. If `colon` is `true`, the
+/// parameters are `:`-separated subparameters, and an RGB color may be preceded
+/// by a color space identifier, as in `38:2::r:g:b`.
+#[cfg(feature = "alloc")]
+fn extended_color<'a>(mut params: impl Iterator<Item = &'a str>, colon: bool) -> Option<Color> {
+    let mut next = || params.next();
+    let num = |param: Option<&str>| param?.parse::<u8>().ok();
+    match next()? {
+        "5" => Some(Color::Fixed(num(next())?)),
+        "2" => {
+            let (mut r, mut g, mut b) = (next(), next(), next());
+            if let Some(last) = colon.then(&mut next).flatten() {
+                (r, g, b) = (g, b, Some(last));
+            }
+
+            Some(Color::Rgb(num(r)?, num(g)?, num(b)?))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of `char`s in `string` outside of ANSI escape sequences.
+///
+/// Escape sequences are recognized exactly as they are by [`strip()`], but no
+/// allocation is performed. This is useful to align styled text, for instance,
+/// in table columns.
+///
+/// **Note:** _This function counts Unicode scalar values, i.e., `char`s. It
+/// does not account for grapheme clusters nor for characters that are
+/// displayed wider or narrower than a single column, such as East Asian wide
+/// characters or combining marks._
+///
+/// [`strip()`]: crate::strip()
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// assert_eq!(yansi::visible_width(&"hi".red().to_string()), 2);
+/// assert_eq!(yansi::visible_width("hello"), 5);
+/// assert_eq!(yansi::visible_width("héllo"), 5);
+/// ```
+pub fn visible_width(string: &str) -> usize {
+    let mut escapes = Escapes::default();
+    string.chars().filter(|&c| !escapes.is_escape(c)).count()
+}
+
+/// An [`io::Write`](std::io::Write) adapter that strips ANSI escape sequences
+/// when styling is disabled.
+///
+/// When styling is [globally enabled](crate::is_enabled()), bytes are passed
+/// through to the inner writer unchanged. Otherwise, escape sequences are
+/// removed, exactly as by [`strip()`], before bytes reach the inner writer.
+/// Whether styling is enabled is checked on every write.
+///
+/// Escape sequences may be split across writes: the position within a sequence
+/// is tracked from one write to the next, so a sequence is stripped in its
+/// entirety no matter how it is split. No bytes are buffered.
+///
+/// [`strip()`]: crate::strip()
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+/// use yansi::{AutoStrip, Paint};
+///
+/// let mut log = AutoStrip::new(Vec::new());
+///
+/// yansi::disable();
+/// write!(log, "{} started", "server".green()).unwrap();
+/// assert_eq!(log.get_ref(), b"server started");
+/// # yansi::enable();
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "std")))]
+pub struct AutoStrip<W> {
+    inner: W,
+    escapes: Escapes,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> AutoStrip<W> {
+    /// Wraps `inner` in a new `AutoStrip`.
+    pub fn new(inner: W) -> Self {
+        AutoStrip { inner, escapes: Escapes::default() }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Writing to the inner writer directly may interfere with the tracking of
+    /// escape sequences split across writes.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes `self`, returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for AutoStrip<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Escape sequences are ASCII: other bytes never start or end one.
+        if crate::is_enabled() {
+            let n = self.inner.write(buf)?;
+            buf[..n].iter().for_each(|&b| { self.escapes.is_escape(b as char); });
+            return Ok(n);
+        }
+
+        let mut start = 0;
+        for (i, &b) in buf.iter().enumerate() {
+            if self.escapes.is_escape(b as char) {
+                if start < i {
+                    self.inner.write_all(&buf[start..i])?;
+                }
+
+                start = i + 1;
+            }
+        }
+
+        if start < buf.len() {
+            self.inner.write_all(&buf[start..])?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: fmt::Debug> fmt::Debug for AutoStrip<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutoStrip").field("inner", &self.inner).finish()
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Style;
+use crate::set::Set;
 
 /// Enum representing text attributes, largely for text formatting.
 ///
@@ -16,10 +17,14 @@ use crate::Style;
 /// in a terminal depends on the terminal's support for the attribute as well as
 /// the terminal's configuration. Common attributes, such as `bold`, `dim`,
 /// `italic`, `underline`, and `strike` typically have good support and are
-/// largely reliable. Less commonly supported attributes like `conceal` and
-/// `invert` will _usually_ be supported by "modern" terminals. Rarely supported
-/// attributes, such as  `blink` and `rapid blink`, will typically have no
-/// effect when applied.
+/// largely reliable. Less commonly supported attributes like `conceal`,
+/// `invert`, `double underline`, and `overline` will _usually_ be supported by
+/// "modern" terminals. Rarely supported attributes, such as  `blink`, `rapid
+/// blink`, `framed`, and `encircled`, will typically have no effect when
+/// applied.
+///
+/// Attributes can also be combined with each other, a [`Color`], or a
+/// [`Style`] using `|`, producing a `Style`. A `Color` sets the foreground.
 ///
 /// # Example
 ///
@@ -30,6 +35,19 @@ use crate::Style;
 /// static MAD: Style = Red.bold().dim().italic().underline().strike();
 /// ```
 ///
+/// Using `|`:
+///
+/// ```rust
+/// use yansi::{Style, Color::Red, Attribute::*};
+///
+/// let style: Style = Red | Bold | Underline;
+/// assert_eq!(style, Red.bold().underline());
+///
+/// let mut style = Bold | Italic;
+/// style |= Strike;
+/// assert_eq!(style, Style::new().bold().italic().strike());
+/// ```
+///
 /// [`Style`]: crate::Style
 /// [`Painted`]: crate::Painted
 /// [`Color`]: crate::Painted
@@ -83,6 +101,43 @@ pub enum Attribute {
     /// Typically used via the [`strike()`](crate::Style::strike()) builder
     /// method.
     Strike,
+    /// <u style="text-decoration-style: double">Double underline</u> text.
+    ///
+    /// Typically used via the
+    /// [`double_underline()`](crate::Style::double_underline()) builder
+    /// method.
+    DoubleUnderline,
+    /// Display text with a <span style="text-decoration: overline">line
+    /// over</span> it.
+    ///
+    /// Typically used via the [`overline()`](crate::Style::overline())
+    /// builder method.
+    Overline,
+    /// <u style="text-decoration-style: wavy">Curly underline</u> text.
+    ///
+    /// Often paired with an underline color set via, e.g.,
+    /// [`underline_rgb()`](crate::Style::underline_rgb()), to produce
+    /// diagnostic-style squiggles. Terminals without support for curly
+    /// underlines will typically display a straight underline or none at all.
+    ///
+    /// Typically used via the
+    /// [`curly_underline()`](crate::Style::curly_underline()) builder method.
+    CurlyUnderline,
+    /// <span style="border: 1px solid">Frame</span> text.
+    ///
+    /// Very few terminals support framing; most display the text as-is.
+    ///
+    /// Typically used via the [`framed()`](crate::Style::framed()) builder
+    /// method.
+    Framed,
+    /// <span style="border: 1px solid; border-radius: 50%">Encircle</span>
+    /// text.
+    ///
+    /// Very few terminals support encircling; most display the text as-is.
+    ///
+    /// Typically used via the [`encircled()`](crate::Style::encircled())
+    /// builder method.
+    Encircled,
 }
 
 /// Enum representing a `yansi` quirk.
@@ -140,19 +195,157 @@ pub enum Quirk {
     ///
     /// See the [crate level docs](crate#brightening) for details.
     OnBright,
+    /// Use the regular variant of the foreground color if it is bright.
+    ///
+    /// Overrides the [`Bright`](Quirk::Bright) quirk if present.
+    ///
+    /// Typically applied via the [`dull()`](crate::Painted::dull()) builder
+    /// method.
+    ///
+    /// See the [crate level docs](crate#brightening) for details.
+    Dull,
+    /// Use the regular variant of the background color if it is bright.
+    ///
+    /// Overrides the [`OnBright`](Quirk::OnBright) quirk if present.
+    ///
+    /// Typically applied via the [`on_dull()`](crate::Painted::on_dull())
+    /// builder method.
+    ///
+    /// See the [crate level docs](crate#brightening) for details.
+    OnDull,
+    /// Reset all styling before the style is applied.
+    ///
+    /// Clears any dangling styling, such as from a lingering style or from
+    /// upstream output, so the style is applied to a clean slate.
+    ///
+    /// Typically applied via the [`reset_before()`](crate::Painted::reset_before())
+    /// builder method.
+    ///
+    /// See the [crate level docs](crate#lingering) for details.
+    ResetBefore,
+    /// Style each line separately: end the style before and apply it anew
+    /// after every newline in the value.
+    ///
+    /// Typically applied via the [`per_line()`](crate::Painted::per_line())
+    /// builder method.
+    ///
+    /// See the [crate level docs](crate#styling-per-line) for details.
+    PerLine,
+    /// Force styling: apply the style even when styling is globally
+    /// disabled. A per-style condition still applies.
+    ///
+    /// Typically applied via the [`force()`](crate::Painted::force()) builder
+    /// method.
+    ///
+    /// See the [crate level docs](crate#forcing) for details.
+    Force,
+}
+
+/// When, if ever, styling is reset after a styled value.
+///
+/// A single setting for what the [`Linger`](Quirk::Linger) and
+/// [`Resetting`](Quirk::Resetting) quirks each control in part. It is applied
+/// via the [`reset_mode()`](crate::Painted::reset_mode()) builder method,
+/// which replaces either quirk if present. Conversely, the `linger()` and
+/// `resetting()` builder methods are equivalent to setting
+/// [`ResetMode::Never`] and [`ResetMode::Always`], respectively, except that
+/// they don't replace one another.
+///
+/// See the [crate level docs](crate#lingering) for details.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, ResetMode};
+///
+/// # yansi::enable();
+/// assert_eq!("a".red().reset_mode(ResetMode::Auto).to_string(), "\x1b[31ma\x1b[0m");
+/// assert_eq!("a".red().reset_mode(ResetMode::Never).to_string(), "\x1b[31ma");
+/// assert_eq!("a".reset_mode(ResetMode::Always).to_string(), "a\x1b[0m");
+///
+/// // The last setting wins.
+/// let painted = "a".red().linger().reset_mode(ResetMode::Auto);
+/// assert_eq!(painted.to_string(), "\x1b[31ma\x1b[0m");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum ResetMode {
+    /// Reset styling afterwards only if styling was applied. The default.
+    #[default]
+    Auto,
+    /// Always reset styling afterwards, even if no styling was applied. As
+    /// with [`Quirk::Resetting`].
+    Always,
+    /// Never reset styling afterwards: the style lingers. As with
+    /// [`Quirk::Linger`].
+    Never,
+}
+
+impl ResetMode {
+    /// Returns `quirks` with the quirks corresponding to `self`, and only
+    /// those, among the quirks that control resetting.
+    pub(crate) const fn apply(self, quirks: Set<Quirk>) -> Set<Quirk> {
+        let quirks = quirks.remove(Quirk::Linger).remove(Quirk::Resetting).remove(Quirk::Clear);
+        match self {
+            ResetMode::Auto => quirks,
+            ResetMode::Always => quirks.insert(Quirk::Resetting),
+            ResetMode::Never => quirks.insert(Quirk::Linger),
+        }
+    }
 }
 
 set_enum! {
-    Attribute { Bold, Dim, Italic, Underline, Blink, RapidBlink, Invert, Conceal, Strike }
+    Attribute {
+        Bold, Dim, Italic, Underline, Blink, RapidBlink, Invert, Conceal, Strike,
+        DoubleUnderline, Overline, CurlyUnderline, Framed, Encircled,
+    }
 }
 
 set_enum! {
-    Quirk { Mask, Wrap, Linger, Clear, Resetting, Bright, OnBright }
+    Quirk {
+        Mask, Wrap, Linger, Clear, Resetting, Bright, OnBright, Dull, OnDull,
+        ResetBefore, PerLine, Force,
+    }
 }
 
 impl Attribute {
     pub(crate) fn fmt(&self, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
-        write!(f, "{}", match self {
+        f.write_str(match self {
+            Attribute::Bold => "1",
+            Attribute::Dim => "2",
+            Attribute::Italic => "3",
+            Attribute::Underline => "4",
+            Attribute::Blink => "5",
+            Attribute::RapidBlink => "6",
+            Attribute::Invert => "7",
+            Attribute::Conceal => "8",
+            Attribute::Strike => "9",
+            Attribute::DoubleUnderline => "21",
+            Attribute::Framed => "51",
+            Attribute::Encircled => "52",
+            Attribute::Overline => "53",
+            Attribute::CurlyUnderline => "4:3",
+        })
+    }
+
+    /// Returns the SGR code that enables the attribute `self`.
+    ///
+    /// [`Attribute::CurlyUnderline`] is the exception to the rule that an
+    /// attribute is enabled by its code alone: its code is that of
+    /// [`Attribute::Underline`], `4`, and it is enabled by the code followed
+    /// by the subparameter `3`, separated by a `:`, as in `\x1B[4:3m`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Attribute;
+    ///
+    /// assert_eq!(Attribute::Bold.code(), 1);
+    /// assert_eq!(Attribute::Strike.code(), 9);
+    /// assert_eq!(Attribute::Overline.code(), 53);
+    /// assert_eq!(Attribute::CurlyUnderline.code(), 4);
+    /// ```
+    pub const fn code(self) -> u8 {
+        match self {
             Attribute::Bold => 1,
             Attribute::Dim => 2,
             Attribute::Italic => 3,
@@ -162,7 +355,12 @@ impl Attribute {
             Attribute::Invert => 7,
             Attribute::Conceal => 8,
             Attribute::Strike => 9,
-        })
+            Attribute::DoubleUnderline => 21,
+            Attribute::Framed => 51,
+            Attribute::Encircled => 52,
+            Attribute::Overline => 53,
+            Attribute::CurlyUnderline => 4,
+        }
     }
 
     /// Returns a `Style` with the attribute `self` enabled.
@@ -205,3 +403,110 @@ impl From<Quirk> for crate::Style {
         quirk.style()
     }
 }
+
+/// A set of attributes supported by a terminal.
+///
+/// Used with [`Style::sanitize()`] to remove attributes that a terminal is
+/// known not to support, or supports unreliably, before rendering. See the
+/// [terminal support](Attribute#terminal-support) notes for details.
+///
+/// [`Style::sanitize()`]: crate::Style::sanitize()
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Capabilities, Attribute::*, Color::Red};
+///
+/// static CAPS: Capabilities = Capabilities::COMMON.with(Invert).without(Dim);
+///
+/// assert!(CAPS.contains(Bold) && CAPS.contains(Invert));
+/// assert!(!CAPS.contains(Dim) && !CAPS.contains(Blink));
+///
+/// let style = Red.bold().dim().invert().blink().sanitize(CAPS);
+/// assert_eq!(style, Red.bold().invert());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Capabilities(pub(crate) Set<Attribute>);
+
+impl Capabilities {
+    /// No attributes are supported.
+    pub const NONE: Capabilities = Capabilities(Set::EMPTY);
+
+    /// The attributes with good support across terminals: [`Bold`],
+    /// [`Dim`], [`Italic`], [`Underline`], and [`Strike`].
+    ///
+    /// [`Bold`]: Attribute::Bold
+    /// [`Dim`]: Attribute::Dim
+    /// [`Italic`]: Attribute::Italic
+    /// [`Underline`]: Attribute::Underline
+    /// [`Strike`]: Attribute::Strike
+    pub const COMMON: Capabilities = Capabilities::NONE
+        .with(Attribute::Bold)
+        .with(Attribute::Dim)
+        .with(Attribute::Italic)
+        .with(Attribute::Underline)
+        .with(Attribute::Strike);
+
+    /// Every attribute is supported.
+    pub const ALL: Capabilities = Capabilities::COMMON
+        .with(Attribute::Blink)
+        .with(Attribute::RapidBlink)
+        .with(Attribute::Invert)
+        .with(Attribute::Conceal)
+        .with(Attribute::DoubleUnderline)
+        .with(Attribute::Overline)
+        .with(Attribute::CurlyUnderline)
+        .with(Attribute::Framed)
+        .with(Attribute::Encircled);
+
+    /// Returns `self` with `attr` supported.
+    #[must_use]
+    pub const fn with(self, attr: Attribute) -> Capabilities {
+        Capabilities(self.0.insert(attr))
+    }
+
+    /// Returns `self` with `attr` unsupported.
+    #[must_use]
+    pub const fn without(self, attr: Attribute) -> Capabilities {
+        Capabilities(self.0.remove(attr))
+    }
+
+    /// Returns `true` if `attr` is supported.
+    pub fn contains(self, attr: Attribute) -> bool {
+        self.0.contains(attr)
+    }
+}
+
+/// The names of the attributes, matching their builder methods.
+pub(crate) const ATTRIBUTE_NAMES: &[(&str, Attribute)] = &[
+    ("bold", Attribute::Bold),
+    ("dim", Attribute::Dim),
+    ("italic", Attribute::Italic),
+    ("underline", Attribute::Underline),
+    ("blink", Attribute::Blink),
+    ("rapid_blink", Attribute::RapidBlink),
+    ("invert", Attribute::Invert),
+    ("conceal", Attribute::Conceal),
+    ("strike", Attribute::Strike),
+    ("double_underline", Attribute::DoubleUnderline),
+    ("overline", Attribute::Overline),
+    ("curly_underline", Attribute::CurlyUnderline),
+    ("framed", Attribute::Framed),
+    ("encircled", Attribute::Encircled),
+];
+
+/// The names of the quirks, matching their builder methods.
+pub(crate) const QUIRK_NAMES: &[(&str, Quirk)] = &[
+    ("mask", Quirk::Mask),
+    ("wrap", Quirk::Wrap),
+    ("linger", Quirk::Linger),
+    ("clear", Quirk::Clear),
+    ("resetting", Quirk::Resetting),
+    ("bright", Quirk::Bright),
+    ("on_bright", Quirk::OnBright),
+    ("dull", Quirk::Dull),
+    ("on_dull", Quirk::OnDull),
+    ("reset_before", Quirk::ResetBefore),
+    ("per_line", Quirk::PerLine),
+    ("force", Quirk::Force),
+];
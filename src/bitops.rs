@@ -1,67 +1,88 @@
-use std::ops::{BitOr, BitOrAssign};
+use core::ops::{Add, AddAssign, BitOr, BitOrAssign};
 
-use crate::Style;
+use crate::{Attribute, Color, Style};
 
-impl<T: Into<Set<T>>> BitOr<T> for Set<T> {
-    type Output = Self;
+impl BitOr for Attribute {
+    type Output = Style;
 
-    fn bitor(self, rhs: T) -> Self::Output {
-        Set(PhantomData, self.1 | rhs.into().1)
+    fn bitor(self, rhs: Self) -> Style {
+        self.style().attr(rhs)
     }
 }
 
-impl<T: Into<Set<T>>> BitOr<Set<T>> for Set<T> {
-    type Output = Self;
+impl BitOr<Style> for Attribute {
+    type Output = Style;
 
-    fn bitor(self, rhs: Set<T>) -> Self::Output {
-        Set(PhantomData, self.1 | rhs.1)
+    fn bitor(self, rhs: Style) -> Style {
+        rhs.attr(self)
     }
 }
 
-impl<T: Into<Set<T>>> BitOrAssign<T> for Set<T> {
-    fn bitor_assign(&mut self, rhs: T) {
-        self.1 |= rhs.into().1;
+impl BitOr<Attribute> for Style {
+    type Output = Style;
+
+    fn bitor(self, rhs: Attribute) -> Style {
+        self.attr(rhs)
     }
 }
 
-impl BitOr for Emphasis {
-    type Output = Style;
-
-    fn bitor(self, rhs: Self) -> Style {
-        let attribute = Set::from(self) | Set::from(rhs);
-        Style { attribute, ..Default::default() }
+impl BitOrAssign<Attribute> for Style {
+    fn bitor_assign(&mut self, rhs: Attribute) {
+        *self = self.attr(rhs);
     }
 }
 
-impl BitOr<Style> for Emphasis {
+impl BitOr<Attribute> for Color {
     type Output = Style;
 
-    fn bitor(self, mut rhs: Style) -> Style {
-        rhs.attribute |= self;
-        rhs
+    fn bitor(self, rhs: Attribute) -> Style {
+        self.foreground().attr(rhs)
     }
 }
 
-impl BitOr<Emphasis> for Style {
+impl BitOr<Color> for Attribute {
     type Output = Style;
 
-    fn bitor(self, rhs: Emphasis) -> Style {
+    fn bitor(self, rhs: Color) -> Style {
         rhs | self
     }
 }
 
-impl BitOr<Emphasis> for Color {
+impl BitOr<Color> for Style {
     type Output = Style;
 
-    fn bitor(self, rhs: Emphasis) -> Self::Output {
-        Style::from(self) | rhs
+    fn bitor(self, rhs: Color) -> Style {
+        self.fg(rhs)
     }
 }
 
-impl BitOr<Color> for Emphasis {
+/// Applies `rhs` over `self`. Equivalent to [`Style::combine()`].
+///
+/// Colors, the condition, and, with the `hyperlink` feature, the link set in
+/// `rhs` override those in `self`. Attributes and quirks are the union of both.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Style, Color::*};
+///
+/// static BASE: Style = White.on_black();
+/// static ACCENT: Style = Yellow.bold();
+///
+/// assert_eq!(BASE + ACCENT, Yellow.on_black().bold());
+/// assert_eq!(ACCENT + BASE, White.on_black().bold());
+/// ```
+impl Add for Style {
     type Output = Style;
 
-    fn bitor(self, rhs: Color) -> Self::Output {
-        Style::from(rhs) | self
+    fn add(self, rhs: Style) -> Style {
+        self.combine(rhs)
+    }
+}
+
+/// Applies `rhs` over `self` in place. Equivalent to [`Style::combine()`].
+impl AddAssign for Style {
+    fn add_assign(&mut self, rhs: Style) {
+        *self = self.combine(rhs);
     }
 }
@@ -1,11 +1,16 @@
-use crate::{Style, Attribute, Quirk, Condition};
+use core::fmt;
+
+use crate::{Style, Attribute, Quirk, Condition, ResetMode};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
 
 /// Enum representing a terminal color.
 ///
 /// **Note:** The color examples below are purely demonstrative. The actual
 /// color rendered depends entirely on the terminal and its configuration, the
 /// latter of which is entirely arbitrary.
-#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
+#[derive(Default, Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
 pub enum Color {
     /// Terminal primary color #9. (foreground code `39`, background code `49`).
     ///
@@ -14,6 +19,7 @@ pub enum Color {
     /// foreground looks "good" against the terminal's default background color,
     /// and this color is a "good" background color for the terminal's default
     /// foreground color.
+    #[default]
     Primary,
 
     /// A color from 0 to 255, for use in 256-color terminals.
@@ -91,10 +97,238 @@ pub enum Color {
     BrightWhite,
 }
 
-pub(crate) enum Variant { Fg, Bg, }
+/// The level of color support available in a terminal.
+///
+/// Levels are ordered from least to most capable, so `ColorLevel::Ansi16 <
+/// ColorLevel::TrueColor`. A level can be used to
+/// [`downgrade()`](crate::Style::downgrade()) a [`Style`] so that it only uses
+/// colors the terminal supports.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Style, Color, ColorLevel};
+///
+/// static ACCENT: Style = Color::Rgb(30, 144, 255).bold();
+///
+/// let level = ColorLevel::Ansi256;
+/// println!("{}", "Hello!".paint(ACCENT.downgrade(level)));
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No color support. Only attributes can be displayed.
+    None,
+    /// Support for the 16 base colors: [`Color::Black`] through
+    /// [`Color::BrightWhite`], as well as [`Color::Primary`].
+    Ansi16,
+    /// Support for the 256-color palette via [`Color::Fixed`].
+    Ansi256,
+    /// Support for 24-bit "true color" via [`Color::Rgb`].
+    TrueColor,
+}
+
+impl ColorLevel {
+    /// Returns the color level indicated by the environment, irrespective of
+    /// whether output is to a terminal.
+    ///
+    /// The level is determined by the following rules, in order:
+    ///
+    ///   * If `NO_COLOR` is set to a value other than `0`, or `TERM` is
+    ///     `dumb`, returns [`ColorLevel::None`].
+    ///   * If `COLORTERM` is `truecolor` or `24bit`, returns
+    ///     [`ColorLevel::TrueColor`].
+    ///   * On Windows, if `WT_SESSION` is set, as it is by Windows Terminal,
+    ///     or `ConEmuANSI` is `ON`, as it is by ConEmu and Cmder, returns
+    ///     [`ColorLevel::TrueColor`].
+    ///   * If `TERM` contains `256color`, returns [`ColorLevel::Ansi256`].
+    ///   * Otherwise, returns [`ColorLevel::Ansi16`].
+    ///
+    /// The environment is read on every call. To additionally check that
+    /// output is to a terminal, use [`detect_color_level()`].
+    ///
+    /// [`detect_color_level()`]: crate::detect_color_level()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, ColorLevel, Color::*};
+    ///
+    /// static ACCENT: Style = Rgb(30, 144, 255).bold();
+    ///
+    /// let accent = ACCENT.downgrade(ColorLevel::from_env());
+    /// ```
+    #[cfg(feature = "detect-env")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "detect-env")))]
+    pub fn from_env() -> ColorLevel {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        let (term, colorterm) = (var("TERM"), var("COLORTERM"));
+        let truecolor = ["truecolor", "24bit"].iter().any(|v| colorterm.eq_ignore_ascii_case(v))
+            || (cfg!(windows) && Self::windows_truecolor());
+
+        if !Condition::no_color_live() || term == "dumb" {
+            ColorLevel::None
+        } else if truecolor {
+            ColorLevel::TrueColor
+        } else if term.contains("256color") {
+            ColorLevel::Ansi256
+        } else {
+            ColorLevel::Ansi16
+        }
+    }
+
+    /// Whether the environment indicates a Windows terminal emulator with
+    /// true color support, beyond the legacy console.
+    #[cfg(feature = "detect-env")]
+    fn windows_truecolor() -> bool {
+        std::env::var_os("WT_SESSION").is_some()
+            || std::env::var("ConEmuANSI").map_or(false, |v| v.eq_ignore_ascii_case("ON"))
+    }
+}
+
+/// Returns the color level supported by the terminal connected to `stdout`.
+///
+/// Returns [`ColorLevel::None`] if `stdout` is not a terminal. Otherwise,
+/// returns the level indicated by the environment as determined by
+/// [`ColorLevel::from_env()`]. See that method for details.
+///
+/// This function is intended to be called once at startup, and its result
+/// used to [`downgrade()`](Style::downgrade()) styles accordingly.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Style, Color::*};
+///
+/// static ACCENT: Style = Rgb(30, 144, 255).bold();
+///
+/// let accent = ACCENT.downgrade(yansi::detect_color_level());
+/// println!("{}", "Hello!".paint(accent));
+/// ```
+#[cfg(all(feature = "detect-env", feature = "detect-tty"))]
+#[cfg_attr(feature = "_nightly", doc(cfg(all(feature = "detect-env", feature = "detect-tty"))))]
+pub fn detect_color_level() -> ColorLevel {
+    match Condition::stdout_is_tty_live() {
+        true => ColorLevel::from_env(),
+        false => ColorLevel::None,
+    }
+}
+
+/// The part of the text a [`Color`] is applied to.
+///
+/// Used to select the SGR code for a color via [`Color::code()`].
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Variant {
+    /// The foreground, i.e., the text itself.
+    Fg,
+    /// The background.
+    Bg,
+    /// The underline, if any.
+    Underline,
+}
+
+/// The 16 base colors in palette order along with their default xterm RGB.
+const BASE_RGB: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::White, (229, 229, 229)),
+    (Color::BrightBlack, (127, 127, 127)),
+    (Color::BrightRed, (255, 0, 0)),
+    (Color::BrightGreen, (0, 255, 0)),
+    (Color::BrightYellow, (255, 255, 0)),
+    (Color::BrightBlue, (92, 92, 255)),
+    (Color::BrightMagenta, (255, 0, 255)),
+    (Color::BrightCyan, (0, 255, 255)),
+    (Color::BrightWhite, (255, 255, 255)),
+];
+
+/// The decimal digits of every `u8`, zero-padded to three.
+const DECIMALS: [[u8; 3]; 256] = {
+    let mut table = [[0; 3]; 256];
+    let mut n = 0;
+    while n < 256 {
+        table[n] = [b'0' + (n / 100) as u8, b'0' + (n / 10 % 10) as u8, b'0' + (n % 10) as u8];
+        n += 1;
+    }
+
+    table
+};
+
+/// Returns the decimal representation of `n` as a precomputed string.
+fn decimal(n: u8) -> &'static str {
+    let digits = &DECIMALS[n as usize];
+    let skip = match n {
+        0..=9 => 2,
+        10..=99 => 1,
+        _ => 0,
+    };
+
+    core::str::from_utf8(&digits[skip..]).unwrap_or_default()
+}
+
+/// The value of the 6×6×6 color cube component with index `i`.
+const fn cube_value(i: u8) -> u8 {
+    match i {
+        0 => 0,
+        _ => 55 + i * 40,
+    }
+}
+
+/// The squared euclidean distance between two RGB triples.
+const fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0.abs_diff(b.0) as u32;
+    let dg = a.1.abs_diff(b.1) as u32;
+    let db = a.2.abs_diff(b.2) as u32;
+    dr * dr + dg * dg + db * db
+}
 
 impl Color {
-    fn fg_base(&self) -> u8 {
+    /// The eight base colors, [`Color::Black`] through [`Color::White`], in
+    /// palette order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// let palette: String = Color::BASE_COLORS.iter()
+    ///     .chain(Color::BRIGHT_COLORS.iter())
+    ///     .map(|&color| "  ".bg(color).to_string())
+    ///     .collect();
+    ///
+    /// println!("{}", palette);
+    /// ```
+    pub const BASE_COLORS: [Color; 8] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+
+    /// The eight bright base colors, [`Color::BrightBlack`] through
+    /// [`Color::BrightWhite`], in palette order.
+    ///
+    /// See [`Color::BASE_COLORS`] for an example.
+    pub const BRIGHT_COLORS: [Color; 8] = [
+        Color::BrightBlack,
+        Color::BrightRed,
+        Color::BrightGreen,
+        Color::BrightYellow,
+        Color::BrightBlue,
+        Color::BrightMagenta,
+        Color::BrightCyan,
+        Color::BrightWhite,
+    ];
+
+    const fn fg_base(&self) -> u8 {
         match self {
             Color::Black => 30,
             Color::Red => 31,
@@ -141,17 +375,668 @@ impl Color {
         }
     }
 
-    pub(crate) fn fmt(&self, f: &mut dyn core::fmt::Write, variant: Variant) -> core::fmt::Result {
+    pub(crate) const fn to_normal(self) -> Self {
+        match self {
+            Color::BrightBlack => Color::Black,
+            Color::BrightRed => Color::Red,
+            Color::BrightGreen => Color::Green,
+            Color::BrightYellow => Color::Yellow,
+            Color::BrightBlue => Color::Blue,
+            Color::BrightMagenta => Color::Magenta,
+            Color::BrightCyan => Color::Cyan,
+            Color::BrightWhite => Color::White,
+            Color::Fixed(_)
+                | Color::Primary
+                | Color::Rgb(_, _, _)
+                | Color::Black
+                | Color::Red
+                | Color::Green
+                | Color::Yellow
+                | Color::Blue
+                | Color::Magenta
+                | Color::Cyan
+                | Color::White => self
+        }
+    }
+
+    /// Returns the color at `level` in the 256-color palette's grayscale ramp.
+    ///
+    /// The ramp comprises 24 shades of gray, from the darkest at level `0`,
+    /// [`Color::Fixed(232)`](Color::Fixed), to the lightest at level `23`,
+    /// `Color::Fixed(255)`. Levels above `23` are clamped to `23`.
+    ///
+    /// The corresponding `gray()`, `on_gray()`, and `underline_gray()` builder
+    /// methods set the foreground, background, and underline color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// assert_eq!(Color::gray(0), Color::Fixed(232));
+    /// assert_eq!(Color::gray(23), Color::Fixed(255));
+    /// assert_eq!(Color::gray(100), Color::Fixed(255));
+    ///
+    /// println!("{}", "shadow".on_gray(20).gray(5));
+    /// ```
+    pub const fn gray(level: u8) -> Color {
+        match level {
+            0..=23 => Color::Fixed(232 + level),
+            _ => Color::Fixed(255),
+        }
+    }
+
+    /// Returns the color at coordinates `(r, g, b)` in the 256-color palette's
+    /// 6×6×6 color cube.
+    ///
+    /// Each component ranges from `0` to `5`: `(0, 0, 0)` is black,
+    /// [`Color::Fixed(16)`](Color::Fixed), and `(5, 5, 5)` is white,
+    /// `Color::Fixed(231)`. Components above `5` are clamped to `5`.
+    ///
+    /// The corresponding `cube()`, `on_cube()`, and `underline_cube()` builder
+    /// methods set the foreground, background, and underline color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// assert_eq!(Color::cube(0, 0, 0), Color::Fixed(16));
+    /// assert_eq!(Color::cube(5, 0, 0), Color::Fixed(196));
+    /// assert_eq!(Color::cube(9, 9, 9), Color::Fixed(231));
+    ///
+    /// println!("{}", "x".paint(Color::cube(5, 0, 0).foreground()));
+    /// println!("{}", "x".on_cube(5, 5, 0).cube(0, 2, 5));
+    /// ```
+    pub const fn cube(r: u8, g: u8, b: u8) -> Color {
+        const fn clamp(v: u8) -> u8 {
+            if v > 5 { 5 } else { v }
+        }
+
+        Color::Fixed(16 + 36 * clamp(r) + 6 * clamp(g) + clamp(b))
+    }
+
+    /// Returns the [`Color::Rgb`] with normalized components `r`, `g`, and
+    /// `b`.
+    ///
+    /// Each component is clamped to `[0.0, 1.0]` and then scaled, rounding to
+    /// the nearest integer, to `0..=255`. A `NaN` component is treated as
+    /// `0.0`. This is convenient when colors are computed, for instance, by
+    /// interpolation, and may fall outside the gamut.
+    ///
+    /// The corresponding `rgb_f32()` and `on_rgb_f32()` builder methods of
+    /// [`Style`] and [`Painted`](crate::Painted) set the foreground and
+    /// background color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// assert_eq!(Color::rgb_f32(1.0, 0.5, 0.0), Color::Rgb(255, 128, 0));
+    /// assert_eq!(Color::rgb_f32(1.5, -0.5, f32::NAN), Color::Rgb(255, 0, 0));
+    ///
+    /// println!("{}", "sunset".new().rgb_f32(1.0, 0.4, 0.2).on_rgb_f32(0.1, 0.0, 0.2));
+    /// ```
+    pub fn rgb_f32(r: f32, g: f32, b: f32) -> Color {
+        let scale = |v: f32| match v.is_nan() {
+            true => 0,
+            false => (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
+        };
+
+        Color::Rgb(scale(r), scale(g), scale(b))
+    }
+
+    /// Returns the [`Color::Fixed`] closest to `self` if `self` is a
+    /// [`Color::Rgb`]. Otherwise returns `self` unchanged.
+    ///
+    /// The closest color is selected from the standard xterm 256-color
+    /// palette: the 6×6×6 color cube (indices `16..=231`) and the grayscale
+    /// ramp (indices `232..=255`), choosing whichever is nearest in RGB space.
+    /// This is useful to degrade true color styling for terminals that only
+    /// support 256 colors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::Rgb(0, 0, 0).to_fixed(), Color::Fixed(16));
+    /// assert_eq!(Color::Rgb(255, 255, 255).to_fixed(), Color::Fixed(231));
+    /// assert_eq!(Color::Rgb(95, 135, 175).to_fixed(), Color::Fixed(67));
+    /// assert_eq!(Color::Rgb(128, 128, 128).to_fixed(), Color::Fixed(244));
+    ///
+    /// assert_eq!(Color::Red.to_fixed(), Color::Red);
+    /// assert_eq!(Color::Fixed(3).to_fixed(), Color::Fixed(3));
+    /// ```
+    pub const fn to_fixed(self) -> Color {
+        const fn cube_index(v: u8) -> u8 {
+            match v {
+                0..=47 => 0,
+                48..=114 => 1,
+                _ => (v - 35) / 40,
+            }
+        }
+
+        let (r, g, b) = match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            _ => return self,
+        };
+
+        let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+        let cube = (cube_value(ri), cube_value(gi), cube_value(bi));
+
+        let avg = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+        let gray_index = match avg {
+            0..=2 => 0,
+            239..=255 => 23,
+            _ => (avg - 3) / 10,
+        };
+
+        let gray_value = 8 + gray_index * 10;
+        let gray = (gray_value, gray_value, gray_value);
+        if distance((r, g, b), gray) < distance((r, g, b), cube) {
+            Color::Fixed(232 + gray_index)
+        } else {
+            Color::Fixed(16 + 36 * ri + 6 * gi + bi)
+        }
+    }
+
+    /// Returns the RGB components of `self` or `None` if `self` is
+    /// [`Color::Primary`].
+    ///
+    /// The components of base and fixed colors are those in xterm's default
+    /// palette; terminals may display these colors differently.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::Rgb(30, 144, 255).rgb_components(), Some((30, 144, 255)));
+    /// assert_eq!(Color::Red.rgb_components(), Some((205, 0, 0)));
+    /// assert_eq!(Color::Fixed(196).rgb_components(), Some((255, 0, 0)));
+    /// assert_eq!(Color::Fixed(232).rgb_components(), Some((8, 8, 8)));
+    /// assert_eq!(Color::Primary.rgb_components(), None);
+    ///
+    /// let (r, g, b) = Color::from([30, 144, 255]).rgb_components().unwrap();
+    /// assert_eq!([r, g, b], [30, 144, 255]);
+    /// ```
+    pub const fn rgb_components(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Primary => None,
+            Color::Rgb(r, g, b) => Some((r, g, b)),
+            Color::Fixed(n @ 0..=15) => Some(BASE_RGB[n as usize].1),
+            Color::Fixed(n @ 16..=231) => {
+                let n = n - 16;
+                Some((cube_value(n / 36), cube_value((n / 6) % 6), cube_value(n % 6)))
+            }
+            Color::Fixed(n) => {
+                let v = 8 + (n - 232) * 10;
+                Some((v, v, v))
+            }
+            _ => match self.fg_base() {
+                base @ 90.. => Some(BASE_RGB[(base - 90 + 8) as usize].1),
+                base => Some(BASE_RGB[(base - 30) as usize].1),
+            }
+        }
+    }
+
+    /// Returns the base color nearest to `self`. [`Color::Primary`] and the
+    /// base colors themselves are returned unchanged.
+    pub(crate) const fn to_base(self) -> Color {
+        let rgb = match self {
+            Color::Fixed(n @ 0..=15) => return BASE_RGB[n as usize].0,
+            Color::Fixed(_) | Color::Rgb(..) => match self.rgb_components() {
+                Some(rgb) => rgb,
+                None => return self,
+            },
+            _ => return self,
+        };
+
+        let (mut i, mut nearest) = (1, 0);
+        while i < BASE_RGB.len() {
+            if distance(rgb, BASE_RGB[i].1) < distance(rgb, BASE_RGB[nearest].1) {
+                nearest = i;
+            }
+
+            i += 1;
+        }
+
+        BASE_RGB[nearest].0
+    }
+
+    /// Returns `self` if it can be displayed at color level `level` or the
+    /// nearest color that can be otherwise. Returns `None` if no color can be
+    /// displayed at `level`.
+    pub(crate) const fn downgrade(self, level: ColorLevel) -> Option<Color> {
+        match (level, self) {
+            (ColorLevel::None, _) => None,
+            (ColorLevel::Ansi16, _) => Some(self.to_base()),
+            (ColorLevel::Ansi256, Color::Rgb(..)) => Some(self.to_fixed()),
+            _ => Some(self),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn fmt_css(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match *self {
+            Color::Primary => f.write_str("inherit"),
+            Color::Black => f.write_str("black"),
+            Color::Red => f.write_str("red"),
+            Color::Green => f.write_str("green"),
+            Color::Yellow => f.write_str("gold"),
+            Color::Blue => f.write_str("blue"),
+            Color::Magenta => f.write_str("darkmagenta"),
+            Color::Cyan => f.write_str("deepskyblue"),
+            Color::White => f.write_str("#eeeeee"),
+            Color::BrightBlack => f.write_str("gray"),
+            Color::BrightRed => f.write_str("hotpink"),
+            Color::BrightGreen => f.write_str("greenyellow"),
+            Color::BrightYellow => f.write_str("yellow"),
+            Color::BrightBlue => f.write_str("dodgerblue"),
+            Color::BrightMagenta => f.write_str("magenta"),
+            Color::BrightCyan => f.write_str("cyan"),
+            Color::BrightWhite => f.write_str("white"),
+            Color::Fixed(_) | Color::Rgb(..) => match self.rgb_components() {
+                Some((r, g, b)) => write!(f, "rgb({}, {}, {})", r, g, b),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Returns a CSS color value for `self`.
+    ///
+    /// Base colors are mapped to the CSS colors used throughout this crate's
+    /// documentation, for instance, [`Color::BrightRed`] is `hotpink`.
+    /// [`Color::Rgb`] and [`Color::Fixed`] colors are mapped to an `rgb()`
+    /// value, the latter based on xterm's default palette. [`Color::Primary`]
+    /// is mapped to `inherit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::Rgb(30, 144, 255).to_css(), "rgb(30, 144, 255)");
+    /// assert_eq!(Color::Fixed(196).to_css(), "rgb(255, 0, 0)");
+    /// assert_eq!(Color::Red.to_css(), "red");
+    /// assert_eq!(Color::Primary.to_css(), "inherit");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn to_css(&self) -> String {
+        let mut css = String::new();
+        let _ = self.fmt_css(&mut css);
+        css
+    }
+
+    pub(crate) fn fmt(&self, f: &mut dyn fmt::Write, variant: Variant) -> fmt::Result {
+        for (i, code) in self.code(variant).enumerate() {
+            if i != 0 {
+                f.write_char(';')?;
+            }
+
+            f.write_str(decimal(code))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the SGR parameters that apply `self` to `variant`.
+    ///
+    /// Base colors and [`Color::Primary`] have a single code, such as `31` for
+    /// a [`Color::Red`] foreground. [`Color::Fixed`] and [`Color::Rgb`] colors
+    /// have multiple: `38;5;n` and `38;2;r;g;b` for the foreground, for
+    /// instance. Because there are no short codes for underline colors, base
+    /// colors are applied to underlines as their index into the 256-color
+    /// palette: `58;5;n`.
+    ///
+    /// Parameters are separated by `;` when written in a sequence.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Color, Variant};
+    ///
+    /// assert!(Color::Red.code(Variant::Fg).eq([31]));
+    /// assert!(Color::Red.code(Variant::Bg).eq([41]));
+    /// assert!(Color::Red.code(Variant::Underline).eq([58, 5, 1]));
+    /// assert!(Color::Fixed(208).code(Variant::Fg).eq([38, 5, 208]));
+    /// assert!(Color::Rgb(30, 144, 255).code(Variant::Bg).eq([48, 2, 30, 144, 255]));
+    ///
+    /// // Build a sequence by hand.
+    /// let codes: Vec<_> = Color::Blue.code(Variant::Fg).map(|c| c.to_string()).collect();
+    /// assert_eq!(format!("\x1B[{}m", codes.join(";")), "\x1B[34m");
+    /// ```
+    pub fn code(self, variant: Variant) -> impl ExactSizeIterator<Item = u8> + Clone {
+        let (codes, len) = self.codes(variant);
+        codes.into_iter().take(len)
+    }
+
+    fn codes(self, variant: Variant) -> ([u8; 5], usize) {
         let base = match variant {
             Variant::Fg => self.fg_base(),
             Variant::Bg => self.fg_base() + 10,
+            // There are no short codes for underline colors: base colors are
+            // written as their index into the 256-color palette instead.
+            Variant::Underline => match self {
+                Color::Primary => 59,
+                Color::Fixed(_) | Color::Rgb(..) => 58,
+                _ => return match self.fg_base() {
+                    n @ 30..=37 => Color::Fixed(n - 30).codes(variant),
+                    n => Color::Fixed(n - 90 + 8).codes(variant),
+                },
+            },
         };
 
-        match *self {
-            Color::Fixed(num) => write!(f, "{};5;{}", base, num),
-            Color::Rgb(r, g, b) => write!(f, "{};2;{};{};{}", base, r, g, b),
-            _ => write!(f, "{}", base)
+        match self {
+            Color::Fixed(num) => ([base, 5, num, 0, 0], 3),
+            Color::Rgb(r, g, b) => ([base, 2, r, g, b], 5),
+            _ => ([base, 0, 0, 0, 0], 1),
+        }
+    }
+
+    /// Parses a hex color string into a [`Color::Rgb`].
+    ///
+    /// Accepts the long `#rrggbb` and short `#rgb` forms, with or without the
+    /// leading `#`. Hex digits are case-insensitive. In the short form, each
+    /// digit is repeated, so `#f0a` is equivalent to `#ff00aa`. Returns `None`
+    /// if `hex` is not of one of these forms.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::from_hex("#1e90ff"), Some(Color::Rgb(30, 144, 255)));
+    /// assert_eq!(Color::from_hex("1E90FF"), Some(Color::Rgb(30, 144, 255)));
+    /// assert_eq!(Color::from_hex("#f0a"), Some(Color::Rgb(0xff, 0x00, 0xaa)));
+    ///
+    /// assert_eq!(Color::from_hex("#12345"), None);
+    /// assert_eq!(Color::from_hex("xyz"), None);
+    /// ```
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        fn digit(c: u8) -> Option<u8> {
+            match c {
+                b'0'..=b'9' => Some(c - b'0'),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        match *hex.as_bytes() {
+            [r, g, b] => {
+                let (r, g, b) = (digit(r)?, digit(g)?, digit(b)?);
+                Some(Color::Rgb(r << 4 | r, g << 4 | g, b << 4 | b))
+            }
+            [r1, r0, g1, g0, b1, b0] => Some(Color::Rgb(
+                digit(r1)? << 4 | digit(r0)?,
+                digit(g1)? << 4 | digit(g0)?,
+                digit(b1)? << 4 | digit(b0)?,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Color::Rgb`] corresponding to the CSS/SVG named color
+    /// `name`, ignoring ASCII case. Returns `None` if `name` is not a known
+    /// color name.
+    ///
+    /// All 148 named colors defined by [CSS Color Module Level 4] are
+    /// recognized, including both the `gray` and `grey` spellings.
+    ///
+    /// [CSS Color Module Level 4]: https://www.w3.org/TR/css-color-4/#named-colors
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::from_name("dodgerblue"), Some(Color::Rgb(30, 144, 255)));
+    /// assert_eq!(Color::from_name("RebeccaPurple"), Some(Color::Rgb(102, 51, 153)));
+    /// assert_eq!(Color::from_name("dodgerbleu"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Color> {
+        let lowercase = || name.bytes().map(|b| b.to_ascii_lowercase());
+        NAMED_COLORS.binary_search_by(|(known, _)| known.bytes().cmp(lowercase()))
+            .ok()
+            .map(|i| NAMED_COLORS[i].1)
+            .map(|(r, g, b)| Color::Rgb(r, g, b))
+    }
+
+    /// Linearly interpolates between `self` and `other` by `t`, returning a
+    /// [`Color::Rgb`].
+    ///
+    /// `t` is clamped to `[0, 1]`: `t = 0` yields `self` and `t = 1` yields
+    /// `other`, both as a `Color::Rgb`. Base and [`Color::Fixed`] colors are
+    /// first converted to their RGB values in xterm's default palette.
+    ///
+    /// [`Color::Primary`] has no RGB value as it depends entirely on the
+    /// terminal's configuration. If either `self` or `other` is
+    /// `Color::Primary`, `self` is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// let (red, blue) = (Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255));
+    /// assert_eq!(red.blend(blue, 0.5), Color::Rgb(127, 0, 127));
+    /// assert_eq!(red.blend(blue, 0.0), red);
+    /// assert_eq!(red.blend(blue, 1.0), blue);
+    ///
+    /// assert_eq!(Color::Primary.blend(blue, 0.5), Color::Primary);
+    /// assert_eq!(red.blend(Color::Primary, 0.5), red);
+    /// ```
+    pub fn blend(self, other: Color, t: f32) -> Color {
+        let ((r1, g1, b1), (r2, g2, b2)) = match (self.rgb_components(), other.rgb_components()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return self,
+        };
+
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+        Color::Rgb(lerp(r1, r2), lerp(g1, g2), lerp(b1, b2))
+    }
+
+    /// Rotates the hue of `self` by `degrees`, returning a [`Color::Rgb`].
+    ///
+    /// The rotation is performed in the HSL color space: saturation and
+    /// lightness are preserved. Positive `degrees` rotate from red towards
+    /// green and blue. Base and [`Color::Fixed`] colors are first converted to
+    /// their RGB values in xterm's default palette. Grays have no hue and are
+    /// returned as their RGB value unchanged, as is every color if `degrees`
+    /// isn't finite.
+    ///
+    /// [`Color::Primary`] has no RGB value as it depends entirely on the
+    /// terminal's configuration. It is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// let red = Color::Rgb(255, 0, 0);
+    /// assert_eq!(red.rotate_hue(120.0), Color::Rgb(0, 255, 0));
+    /// assert_eq!(red.rotate_hue(-120.0), Color::Rgb(0, 0, 255));
+    /// assert_eq!(red.rotate_hue(360.0), red);
+    ///
+    /// assert_eq!(Color::Red.rotate_hue(120.0), Color::Rgb(0, 205, 0));
+    /// assert_eq!(Color::Primary.rotate_hue(120.0), Color::Primary);
+    /// ```
+    pub fn rotate_hue(self, degrees: f32) -> Color {
+        fn channel(p: f32, q: f32, mut t: f32) -> u8 {
+            t = if t < 0.0 { t + 1.0 } else if t >= 1.0 { t - 1.0 } else { t };
+            let value = match t {
+                t if t < 1.0 / 6.0 => p + (q - p) * 6.0 * t,
+                t if t < 1.0 / 2.0 => q,
+                t if t < 2.0 / 3.0 => p + (q - p) * (2.0 / 3.0 - t) * 6.0,
+                _ => p,
+            };
+
+            (value * 255.0 + 0.5) as u8
+        }
+
+        let (r, g, b) = match self.rgb_components() {
+            Some(rgb) => rgb,
+            None => return self,
+        };
+
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        if max == min || !degrees.is_finite() {
+            return Color::Rgb(r, g, b);
         }
+
+        let [r, g, b, max, min] = [r, g, b, max, min].map(|c| c as f32 / 255.0);
+        let (l, d) = ((max + min) / 2.0, max - min);
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+
+        let h = ((h * 60.0 + degrees % 360.0) % 360.0 + 360.0) % 360.0 / 360.0;
+        let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+        let p = 2.0 * l - q;
+        Color::Rgb(channel(p, q, h + 1.0 / 3.0), channel(p, q, h), channel(p, q, h - 1.0 / 3.0))
+    }
+
+    /// Returns the complement of `self`: its hue rotated by 180°, as by
+    /// [`rotate_hue()`](Self::rotate_hue()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::Rgb(255, 0, 0).complement(), Color::Rgb(0, 255, 255));
+    /// assert_eq!(Color::Rgb(30, 144, 255).complement(), Color::Rgb(255, 141, 30));
+    /// assert_eq!(Color::Primary.complement(), Color::Primary);
+    /// ```
+    pub fn complement(self) -> Color {
+        self.rotate_hue(180.0)
+    }
+
+    /// Returns the [WCAG relative luminance] of `self`, from `0.0` for the
+    /// darkest black to `1.0` for the lightest white.
+    ///
+    /// Base and [`Color::Fixed`] colors are first converted to their RGB values
+    /// in xterm's default palette. [`Color::Primary`] has no RGB value as it
+    /// depends entirely on the terminal's configuration, so a neutral `0.5` is
+    /// returned.
+    ///
+    /// [WCAG relative luminance]: https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::Rgb(0, 0, 0).luminance(), 0.0);
+    /// assert_eq!(Color::Rgb(255, 255, 255).luminance(), 1.0);
+    /// assert!((Color::Rgb(255, 0, 0).luminance() - 0.2126).abs() < 0.001);
+    /// assert_eq!(Color::Primary.luminance(), 0.5);
+    /// ```
+    pub fn luminance(self) -> f32 {
+        /// Returns `x^2.4` for `x` in `[0, 1]`. `f32::powf()` requires `std`.
+        fn pow_2_4(x: f32) -> f32 {
+            // `x^2.4 = x^2 * (x^0.2)^2`, finding `x^0.2` via Newton's method.
+            let mut root = 1.0f32;
+            for _ in 0..10 {
+                let root4 = root * root * root * root;
+                root -= (root4 * root - x) / (5.0 * root4);
+            }
+
+            x * x * root * root
+        }
+
+        fn linear(c: u8) -> f32 {
+            match c as f32 / 255.0 {
+                c if c <= 0.04045 => c / 12.92,
+                c => pow_2_4((c + 0.055) / 1.055),
+            }
+        }
+
+        match self.rgb_components() {
+            Some((r, g, b)) => 0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b),
+            None => 0.5,
+        }
+    }
+
+    /// Returns the gray [`Color::Rgb`] with the same
+    /// [luminance](Self::luminance()) as `self`: `self`, desaturated.
+    ///
+    /// All three channels of the returned color are equal. Base and
+    /// [`Color::Fixed`] colors are first converted to their RGB values in
+    /// xterm's default palette. [`Color::Primary`] has no RGB value as it
+    /// depends entirely on the terminal's configuration. It is returned
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// assert_eq!(Color::Rgb(255, 0, 0).grayscale(), Color::Rgb(127, 127, 127));
+    /// assert_eq!(Color::Rgb(0, 0, 255).grayscale(), Color::Rgb(76, 76, 76));
+    /// assert_eq!(Color::Rgb(80, 80, 80).grayscale(), Color::Rgb(80, 80, 80));
+    /// assert_eq!(Color::Primary.grayscale(), Color::Primary);
+    ///
+    /// let (accent, disabled) = (Color::Rgb(30, 144, 255), true);
+    /// let color = if disabled { accent.grayscale() } else { accent };
+    /// println!("{}", "Submit".fg(color));
+    /// ```
+    pub fn grayscale(self) -> Color {
+        /// Returns `x^(1/2.4)` for `x` in `[0, 1]`. `f32::powf()` requires `std`.
+        fn pow_inv_2_4(x: f32) -> f32 {
+            // `x^(1/2.4) = (x^(1/12))^5`, finding `x^(1/12)` via Newton's method.
+            let mut root = 1.0f32;
+            for _ in 0..16 {
+                let root2 = root * root;
+                let root11 = root2 * root2 * root2 * root2 * root2 * root;
+                root -= (root11 * root - x) / (12.0 * root11);
+            }
+
+            let root2 = root * root;
+            root2 * root2 * root
+        }
+
+        if self.rgb_components().is_none() {
+            return self;
+        }
+
+        let gray = match self.luminance() {
+            l if l <= 0.0031308 => l * 12.92,
+            l => 1.055 * pow_inv_2_4(l) - 0.055,
+        };
+
+        let value = (gray.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+        Color::Rgb(value, value, value)
+    }
+
+    /// Returns `true` if `self` is dark, that is, if its
+    /// [`luminance()`](Self::luminance()) is less than `0.5`.
+    ///
+    /// As its luminance is `0.5`, [`Color::Primary`] is not considered dark.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert!(Color::Black.is_dark());
+    /// assert!(Color::Rgb(30, 30, 120).is_dark());
+    /// assert!(!Color::BrightWhite.is_dark());
+    /// assert!(!Color::Primary.is_dark());
+    /// ```
+    pub fn is_dark(self) -> bool {
+        self.luminance() < 0.5
     }
 
     #[inline(always)]
@@ -205,19 +1090,17 @@ impl Color {
         Style::new().bg(self)
     }
 
-    bg!([pub const] constructor(Self) -> Style);
+    bg!([#[must_use] pub const] constructor(Self) -> Style);
 
-    attr!([pub const] constructor(Self) -> Style);
+    underline_color!([#[must_use] pub const] constructor(Self) -> Style);
 
-    quirk!([pub const] constructor(Self) -> Style);
+    attr!([#[must_use] pub const] constructor(Self) -> Style);
 
-    whenever!([pub const] constructor(Self) -> Style);
-}
+    quirk!([#[must_use] pub const] constructor(Self) -> Style);
 
-impl Default for Color {
-    fn default() -> Self {
-        Color::Primary
-    }
+    whenever!([#[must_use] pub const] constructor(Self) -> Style);
+
+    reset_mode!([#[must_use] pub const] constructor(Self) -> Style);
 }
 
 impl From<Color> for Style {
@@ -225,3 +1108,325 @@ impl From<Color> for Style {
         color.foreground()
     }
 }
+
+/// Converts `[r, g, b]` into `Color::Rgb(r, g, b)`.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Color;
+///
+/// assert_eq!(Color::from([30, 144, 255]), Color::Rgb(30, 144, 255));
+/// ```
+impl From<[u8; 3]> for Color {
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        Color::Rgb(r, g, b)
+    }
+}
+
+/// Converts `(r, g, b)` into `Color::Rgb(r, g, b)`.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Color;
+///
+/// assert_eq!(Color::from((30, 144, 255)), Color::Rgb(30, 144, 255));
+/// ```
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::Rgb(r, g, b)
+    }
+}
+
+/// The error returned when parsing a [`Color`] from a string fails.
+///
+/// See [`Color`'s `FromStr` implementation](Color#impl-FromStr-for-Color) for
+/// the accepted forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseColorError {
+    kind: ParseColorErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseColorErrorKind {
+    Empty,
+    Fixed,
+    Rgb,
+    Hex,
+    Unknown,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            ParseColorErrorKind::Empty => "cannot parse color from empty string",
+            ParseColorErrorKind::Fixed => "invalid fixed color: expected an integer in 0..=255",
+            ParseColorErrorKind::Rgb => "invalid RGB color: \
+                expected `rgb(r, g, b)` with r, g, b in 0..=255",
+            ParseColorErrorKind::Hex => "invalid hex color: expected `#rgb` or `#rrggbb`",
+            ParseColorErrorKind::Unknown => "unknown color name",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColorError { }
+
+/// Parses a color from a string.
+///
+/// Surrounding whitespace is ignored. The following forms are accepted:
+///
+///   * The name of a base color or [`Color::Primary`], ignoring ASCII case,
+///     with an optional `bright_` prefix: `red`, `Bright_Blue`, `primary`.
+///   * A fixed color index, bare or as `fixed(n)`: `202`, `fixed(202)`.
+///   * An RGB triple as `rgb(r, g, b)`: `rgb(30, 144, 255)`.
+///   * A `#`-prefixed hex string, as accepted by [`Color::from_hex()`]:
+///     `#1e90ff`, `#f0a`.
+///   * A CSS/SVG color name, as accepted by [`Color::from_name()`]:
+///     `dodgerblue`. Base color names take precedence, so `red` is
+///     [`Color::Red`], not `Color::Rgb(255, 0, 0)`.
+///
+/// Bare strings of digits are always parsed as fixed color indices, so `123`
+/// is `Color::Fixed(123)`, not the hex color `#123`. Unlike `from_hex()`, the
+/// `#` is required for hex strings so that words like `bad` or `facade` are
+/// never mistaken for colors.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Color;
+///
+/// assert_eq!("red".parse(), Ok(Color::Red));
+/// assert_eq!("BRIGHT_RED".parse(), Ok(Color::BrightRed));
+/// assert_eq!("202".parse(), Ok(Color::Fixed(202)));
+/// assert_eq!("fixed(202)".parse(), Ok(Color::Fixed(202)));
+/// assert_eq!("rgb(30, 144, 255)".parse(), Ok(Color::Rgb(30, 144, 255)));
+/// assert_eq!("#1e90ff".parse(), Ok(Color::Rgb(30, 144, 255)));
+/// assert_eq!("dodgerblue".parse(), Ok(Color::Rgb(30, 144, 255)));
+///
+/// assert!("256".parse::<Color>().is_err());
+/// assert!("redd".parse::<Color>().is_err());
+/// assert!("bad".parse::<Color>().is_err());
+/// ```
+impl core::str::FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ParseColorErrorKind::*;
+
+        /// Returns the contents of `s` inside of `{name}(` and `)`, if any.
+        fn call<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+            let prefix = s.get(..name.len()).filter(|p| p.eq_ignore_ascii_case(name))?;
+            s[prefix.len()..].trim_start().strip_prefix('(')?.strip_suffix(')')
+        }
+
+        let s = s.trim();
+        let err = |kind| ParseColorError { kind };
+        let byte = |n: &str| n.trim().parse::<u8>().ok();
+        if s.is_empty() {
+            return Err(err(Empty));
+        }
+
+        if let Some(n) = call(s, "fixed") {
+            return byte(n).map(Color::Fixed).ok_or(err(Fixed));
+        }
+
+        if let Some(args) = call(s, "rgb") {
+            let mut components = args.split(',').map(byte);
+            return match (components.next(), components.next(), components.next()) {
+                (Some(Some(r)), Some(Some(g)), Some(Some(b))) if components.next().is_none() => {
+                    Ok(Color::Rgb(r, g, b))
+                }
+                _ => Err(err(Rgb)),
+            };
+        }
+
+        if s.bytes().all(|b| b.is_ascii_digit()) {
+            return byte(s).map(Color::Fixed).ok_or(err(Fixed));
+        }
+
+        if s.starts_with('#') {
+            return Color::from_hex(s).ok_or(err(Hex));
+        }
+
+        COLOR_NAMES.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+            .map(|(_, color)| *color)
+            .or_else(|| Color::from_name(s))
+            .ok_or(err(Unknown))
+    }
+}
+
+/// The names of [`Color::Primary`] and the base colors.
+pub(crate) const COLOR_NAMES: &[(&str, Color)] = &[
+    ("primary", Color::Primary),
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("white", Color::White),
+    ("bright_black", Color::BrightBlack),
+    ("bright_red", Color::BrightRed),
+    ("bright_green", Color::BrightGreen),
+    ("bright_yellow", Color::BrightYellow),
+    ("bright_blue", Color::BrightBlue),
+    ("bright_magenta", Color::BrightMagenta),
+    ("bright_cyan", Color::BrightCyan),
+    ("bright_white", Color::BrightWhite),
+];
+
+/// The CSS/SVG named colors, sorted by name.
+const NAMED_COLORS: [(&str, (u8, u8, u8)); 148] = [
+    ("aliceblue", (240, 248, 255)),
+    ("antiquewhite", (250, 235, 215)),
+    ("aqua", (0, 255, 255)),
+    ("aquamarine", (127, 255, 212)),
+    ("azure", (240, 255, 255)),
+    ("beige", (245, 245, 220)),
+    ("bisque", (255, 228, 196)),
+    ("black", (0, 0, 0)),
+    ("blanchedalmond", (255, 235, 205)),
+    ("blue", (0, 0, 255)),
+    ("blueviolet", (138, 43, 226)),
+    ("brown", (165, 42, 42)),
+    ("burlywood", (222, 184, 135)),
+    ("cadetblue", (95, 158, 160)),
+    ("chartreuse", (127, 255, 0)),
+    ("chocolate", (210, 105, 30)),
+    ("coral", (255, 127, 80)),
+    ("cornflowerblue", (100, 149, 237)),
+    ("cornsilk", (255, 248, 220)),
+    ("crimson", (220, 20, 60)),
+    ("cyan", (0, 255, 255)),
+    ("darkblue", (0, 0, 139)),
+    ("darkcyan", (0, 139, 139)),
+    ("darkgoldenrod", (184, 134, 11)),
+    ("darkgray", (169, 169, 169)),
+    ("darkgreen", (0, 100, 0)),
+    ("darkgrey", (169, 169, 169)),
+    ("darkkhaki", (189, 183, 107)),
+    ("darkmagenta", (139, 0, 139)),
+    ("darkolivegreen", (85, 107, 47)),
+    ("darkorange", (255, 140, 0)),
+    ("darkorchid", (153, 50, 204)),
+    ("darkred", (139, 0, 0)),
+    ("darksalmon", (233, 150, 122)),
+    ("darkseagreen", (143, 188, 143)),
+    ("darkslateblue", (72, 61, 139)),
+    ("darkslategray", (47, 79, 79)),
+    ("darkslategrey", (47, 79, 79)),
+    ("darkturquoise", (0, 206, 209)),
+    ("darkviolet", (148, 0, 211)),
+    ("deeppink", (255, 20, 147)),
+    ("deepskyblue", (0, 191, 255)),
+    ("dimgray", (105, 105, 105)),
+    ("dimgrey", (105, 105, 105)),
+    ("dodgerblue", (30, 144, 255)),
+    ("firebrick", (178, 34, 34)),
+    ("floralwhite", (255, 250, 240)),
+    ("forestgreen", (34, 139, 34)),
+    ("fuchsia", (255, 0, 255)),
+    ("gainsboro", (220, 220, 220)),
+    ("ghostwhite", (248, 248, 255)),
+    ("gold", (255, 215, 0)),
+    ("goldenrod", (218, 165, 32)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("greenyellow", (173, 255, 47)),
+    ("grey", (128, 128, 128)),
+    ("honeydew", (240, 255, 240)),
+    ("hotpink", (255, 105, 180)),
+    ("indianred", (205, 92, 92)),
+    ("indigo", (75, 0, 130)),
+    ("ivory", (255, 255, 240)),
+    ("khaki", (240, 230, 140)),
+    ("lavender", (230, 230, 250)),
+    ("lavenderblush", (255, 240, 245)),
+    ("lawngreen", (124, 252, 0)),
+    ("lemonchiffon", (255, 250, 205)),
+    ("lightblue", (173, 216, 230)),
+    ("lightcoral", (240, 128, 128)),
+    ("lightcyan", (224, 255, 255)),
+    ("lightgoldenrodyellow", (250, 250, 210)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgreen", (144, 238, 144)),
+    ("lightgrey", (211, 211, 211)),
+    ("lightpink", (255, 182, 193)),
+    ("lightsalmon", (255, 160, 122)),
+    ("lightseagreen", (32, 178, 170)),
+    ("lightskyblue", (135, 206, 250)),
+    ("lightslategray", (119, 136, 153)),
+    ("lightslategrey", (119, 136, 153)),
+    ("lightsteelblue", (176, 196, 222)),
+    ("lightyellow", (255, 255, 224)),
+    ("lime", (0, 255, 0)),
+    ("limegreen", (50, 205, 50)),
+    ("linen", (250, 240, 230)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("mediumaquamarine", (102, 205, 170)),
+    ("mediumblue", (0, 0, 205)),
+    ("mediumorchid", (186, 85, 211)),
+    ("mediumpurple", (147, 112, 219)),
+    ("mediumseagreen", (60, 179, 113)),
+    ("mediumslateblue", (123, 104, 238)),
+    ("mediumspringgreen", (0, 250, 154)),
+    ("mediumturquoise", (72, 209, 204)),
+    ("mediumvioletred", (199, 21, 133)),
+    ("midnightblue", (25, 25, 112)),
+    ("mintcream", (245, 255, 250)),
+    ("mistyrose", (255, 228, 225)),
+    ("moccasin", (255, 228, 181)),
+    ("navajowhite", (255, 222, 173)),
+    ("navy", (0, 0, 128)),
+    ("oldlace", (253, 245, 230)),
+    ("olive", (128, 128, 0)),
+    ("olivedrab", (107, 142, 35)),
+    ("orange", (255, 165, 0)),
+    ("orangered", (255, 69, 0)),
+    ("orchid", (218, 112, 214)),
+    ("palegoldenrod", (238, 232, 170)),
+    ("palegreen", (152, 251, 152)),
+    ("paleturquoise", (175, 238, 238)),
+    ("palevioletred", (219, 112, 147)),
+    ("papayawhip", (255, 239, 213)),
+    ("peachpuff", (255, 218, 185)),
+    ("peru", (205, 133, 63)),
+    ("pink", (255, 192, 203)),
+    ("plum", (221, 160, 221)),
+    ("powderblue", (176, 224, 230)),
+    ("purple", (128, 0, 128)),
+    ("rebeccapurple", (102, 51, 153)),
+    ("red", (255, 0, 0)),
+    ("rosybrown", (188, 143, 143)),
+    ("royalblue", (65, 105, 225)),
+    ("saddlebrown", (139, 69, 19)),
+    ("salmon", (250, 128, 114)),
+    ("sandybrown", (244, 164, 96)),
+    ("seagreen", (46, 139, 87)),
+    ("seashell", (255, 245, 238)),
+    ("sienna", (160, 82, 45)),
+    ("silver", (192, 192, 192)),
+    ("skyblue", (135, 206, 235)),
+    ("slateblue", (106, 90, 205)),
+    ("slategray", (112, 128, 144)),
+    ("slategrey", (112, 128, 144)),
+    ("snow", (255, 250, 250)),
+    ("springgreen", (0, 255, 127)),
+    ("steelblue", (70, 130, 180)),
+    ("tan", (210, 180, 140)),
+    ("teal", (0, 128, 128)),
+    ("thistle", (216, 191, 216)),
+    ("tomato", (255, 99, 71)),
+    ("turquoise", (64, 224, 208)),
+    ("violet", (238, 130, 238)),
+    ("wheat", (245, 222, 179)),
+    ("white", (255, 255, 255)),
+    ("whitesmoke", (245, 245, 245)),
+    ("yellow", (255, 255, 0)),
+    ("yellowgreen", (154, 205, 50)),
+];
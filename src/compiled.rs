@@ -0,0 +1,130 @@
+use core::fmt;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+
+use crate::{Painted, Quirk, Style};
+
+/// A [`Style`] with precomputed ANSI prefix and suffix sequences.
+///
+/// Returned by [`Style::compile()`]. Painting a value via
+/// [`CompiledStyle::paint()`] renders exactly as painting the value with the
+/// original style would, but without recomputing the style's escape sequences
+/// each time the value is rendered. This is useful when rendering many values
+/// with the same style in a hot loop.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Style, Color::*};
+///
+/// static ERROR: Style = Red.bold().underline();
+///
+/// let error = ERROR.compile();
+/// for i in 0..10 {
+///     println!("{}", error.paint(i));
+/// }
+///
+/// # yansi::enable();
+/// assert_eq!(error.paint(1).to_string(), 1.paint(ERROR).to_string());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub struct CompiledStyle {
+    style: Style,
+    prefix: String,
+    suffix: String,
+}
+
+/// A value painted with a [`CompiledStyle`].
+///
+/// Returned by [`CompiledStyle::paint()`]. Renders exactly as a [`Painted`]
+/// with the same value and style.
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub struct CompiledPainted<'a, T> {
+    value: T,
+    compiled: &'a CompiledStyle,
+}
+
+impl Style {
+    /// Compiles `self` into a [`CompiledStyle`] with the prefix and suffix
+    /// sequences precomputed.
+    ///
+    /// See [`CompiledStyle`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// let style = Green.on_black().italic();
+    /// let compiled = style.compile();
+    /// assert_eq!(compiled.prefix(), style.prefix());
+    /// assert_eq!(compiled.suffix(), style.suffix());
+    /// ```
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn compile(&self) -> CompiledStyle {
+        let (mut prefix, mut suffix) = (String::new(), String::new());
+        let _ = self.fmt_prefix(&mut prefix);
+        let _ = self.fmt_suffix(&mut suffix);
+        CompiledStyle { style: *self, prefix, suffix }
+    }
+}
+
+impl CompiledStyle {
+    /// Returns the style that was compiled.
+    #[inline(always)]
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Returns the precomputed prefix, as written by [`Style::fmt_prefix()`].
+    #[inline(always)]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the precomputed suffix, as written by [`Style::fmt_suffix()`].
+    #[inline(always)]
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Paints `value` with the compiled style.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color::*;
+    ///
+    /// let style = Blue.bold().compile();
+    /// println!("{} and {}", style.paint("this"), style.paint(&"that"));
+    /// ```
+    #[inline(always)]
+    pub fn paint<T>(&self, value: T) -> CompiledPainted<'_, T> {
+        CompiledPainted { value, compiled: self }
+    }
+}
+
+impl<T> CompiledPainted<'_, T> {
+    fn fmt_with(
+        &self,
+        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        // Only the common case is precomputed: defer to `Painted` otherwise.
+        let style = &self.compiled.style;
+        let rewrites = style.quirks.contains(Quirk::Wrap) || style.quirks.contains(Quirk::PerLine);
+        if f.width().is_some() || rewrites || !style.effective() {
+            let painted = Painted { value: &self.value, style: *style };
+            return painted.fmt_with(&|value, f| fmt(value, f), f);
+        }
+
+        f.write_str(&self.compiled.prefix)?;
+        fmt(&self.value, f)?;
+        f.write_str(&self.compiled.suffix)
+    }
+}
+
+impl_fmt_traits!(<T> CompiledPainted<'_, T> => self.value (T));
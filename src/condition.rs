@@ -1,7 +1,14 @@
 use core::fmt;
-use core::sync::atomic::AtomicU8;
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize};
 use core::sync::atomic::{AtomicPtr, Ordering};
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::boxed::Box;
+
+/// A condition function that may capture state, recorded by `boxed()`.
+#[cfg(feature = "alloc")]
+type BoxedFn = Box<dyn Fn() -> bool + Send + Sync>;
+
 /// A function that decides whether styling should be applied.
 ///
 /// A styling `Condition` can be specified globally via
@@ -72,19 +79,61 @@ use core::sync::atomic::{AtomicPtr, Ordering};
 /// static DEBUG: Style = Yellow.bold().on_primary().invert().whenever(STDERR_COLOR);
 /// # }
 /// ```
+///
+/// # Combining Conditions
+///
+/// Existing conditions can be combined at runtime with [`Condition::and()`],
+/// [`Condition::or()`], and negated with `!`, via [`Not`](core::ops::Not). The
+/// `&` and `|` operators are equivalent to `and()` and `or()`, respectively:
+///
+/// ```rust
+/// # #[cfg(all(feature = "detect-tty", feature = "detect-env"))] {
+/// use yansi::Condition;
+///
+/// let stdout_color = Condition::STDOUT_IS_TTY.and(Condition::CLICOLOR);
+/// yansi::whenever(stdout_color | Condition::CI);
+///
+/// let uncolored = !Condition::YES_COLOR;
+/// # }
+/// ```
+///
+/// A `Condition` remains a plain function pointer, so a combined condition
+/// cannot capture its operands. Instead, each distinct combination is recorded
+/// in one of a fixed number of internal slots, and the returned condition
+/// dispatches to the recorded operands when checked. Combining the same
+/// conditions in the same way again reuses the existing slot, so repeated
+/// calls are cheap and never exhaust the slots. Combinations with
+/// [`Condition::ALWAYS`] and [`Condition::NEVER`] are simplified and use no
+/// slot. Because the combinators need these slots, they are not `const`: to
+/// combine conditions in a `const` or `static`, use [`Condition::from()`] as
+/// above. Conditions created with [`Condition::from_atomic()`] and
+/// [`Condition::boxed()`] share the same slots.
+///
+/// There are 64 slots, and they are never released. A program that builds
+/// an unbounded number of distinct combinations, say from user input, should
+/// use [`Condition::try_and()`], [`Condition::try_or()`], and
+/// [`Condition::try_not()`], which return `None` when every slot is taken,
+/// instead of `and()`, `or()`, and `!`, which panic.
 #[repr(transparent)]
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Clone)]
 pub struct Condition(
     /// The function that gets called to check the condition.
     pub fn() -> bool
 );
 
+/// The global condition. A null pointer marks the default condition,
+/// [`Condition::DEFAULT`], as distinct from any explicitly set condition.
 #[repr(transparent)]
 pub struct AtomicCondition(AtomicPtr<()>);
 
+/// A lazily computed `bool`. The low two bits hold the state, the rest the
+/// generation, as per `GENERATION`, in which the value was computed.
 #[allow(unused)]
 #[repr(transparent)]
-pub struct CachedBool(AtomicU8);
+pub struct CachedBool(AtomicUsize);
+
+/// The current generation of cached values, advanced by `recheck()`.
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
 
 impl Condition {
     /// A condition that evaluates to `true` if the OS supports coloring.
@@ -131,6 +180,139 @@ impl Condition {
         Condition(f)
     }
 
+    /// Creates a condition that evaluates to the current value of `flag`.
+    ///
+    /// This lets an existing flag, say one toggled by a command-line option or
+    /// a settings menu, drive styling directly. The flag is loaded each time
+    /// the condition is checked, so changes take effect immediately. Because
+    /// the condition may be checked at any point in the future, the flag must
+    /// be `'static`.
+    ///
+    /// Like the [combinators](Condition#combining-conditions), the returned
+    /// condition records `flag` in an internal slot. Calling this method with
+    /// the same `flag` again reuses the slot.
+    ///
+    /// Returns `None` if `flag` has no slot yet and every slot has already
+    /// been claimed by flags, closures, and combinations, including those made
+    /// with [`and()`](Self::and()), [`or()`](Self::or()), `!`, and
+    /// [`boxed()`](Self::boxed()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use yansi::{Paint, Condition};
+    ///
+    /// static COLOR: AtomicBool = AtomicBool::new(true);
+    ///
+    /// let condition = Condition::from_atomic(&COLOR)
+    ///     .expect("a free condition slot");
+    ///
+    /// assert!(condition());
+    ///
+    /// COLOR.store(false, Ordering::Relaxed);
+    /// assert!(!condition());
+    /// assert_eq!("hi".red().whenever(condition).to_string(), "hi");
+    /// ```
+    pub fn from_atomic(flag: &'static AtomicBool) -> Option<Condition> {
+        let flag = flag as *const AtomicBool as *mut ();
+        Composition::ATOMIC.try_record(flag, core::ptr::null_mut())
+    }
+
+    /// Creates a condition that calls the closure `f`, which may capture
+    /// state.
+    ///
+    /// Unlike a plain `fn() -> bool`, `f` can consult runtime state, say a
+    /// shared configuration. `f` is called each and every time the condition
+    /// is checked, from any thread that checks it, and so it must be `Send`,
+    /// `Sync`, and `'static`, and it is expected to be fast.
+    ///
+    /// Like the [combinators](Condition#combining-conditions), the returned
+    /// condition records `f` in one of the fixed number of internal slots, but
+    /// every successful call to this method claims a new slot, and `f` lives
+    /// for the remainder of the program: it is never dropped. Thus, this
+    /// method is intended to be called a bounded number of times, typically
+    /// once at startup per condition. Copying the returned condition is free.
+    ///
+    /// Returns `None`, dropping `f`, if every slot has already been claimed by
+    /// closures, distinct flags, and combinations, including those made with
+    /// [`and()`](Self::and()), [`or()`](Self::or()), `!`, and
+    /// [`from_atomic()`](Self::from_atomic()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, RwLock};
+    /// use yansi::{Paint, Condition};
+    ///
+    /// struct Config {
+    ///     color: bool,
+    /// }
+    ///
+    /// let config = Arc::new(RwLock::new(Config { color: true }));
+    /// let settings = Arc::clone(&config);
+    /// let condition = Condition::boxed(move || settings.read().unwrap().color)
+    ///     .expect("a free condition slot");
+    ///
+    /// assert!(condition());
+    ///
+    /// config.write().unwrap().color = false;
+    /// assert!(!condition());
+    /// assert_eq!("hi".red().whenever(condition).to_string(), "hi");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn boxed<F>(f: F) -> Option<Condition>
+        where F: Fn() -> bool + Send + Sync + 'static
+    {
+        let f = Box::into_raw(Box::new(Box::new(f) as BoxedFn));
+        let condition = Composition::BOXED.try_record(f as *mut (), core::ptr::null_mut());
+        if condition.is_none() {
+            // SAFETY: `f` came from `Box::into_raw()` and was never recorded.
+            drop(unsafe { Box::from_raw(f) });
+        }
+
+        condition
+    }
+
+    /// Creates a condition that evaluates to `true` if `stream` is a TTY.
+    ///
+    /// This generalizes the built-in [TTY detectors](Condition#impl-Condition-1)
+    /// to arbitrary streams: any file, socket, pipe, or other type that
+    /// implements [`is_terminal::IsTerminal`], typically via `AsFd` on Unix
+    /// and `AsHandle` on Windows, including references to such types.
+    ///
+    /// `stream` is checked exactly once, when this method is called, and the
+    /// result is [cached](Condition::cached()): the returned condition is
+    /// either [`ALWAYS`](Self::ALWAYS) or [`NEVER`](Self::NEVER). As such,
+    /// `stream` is not retained, and calling this method any number of times
+    /// is free of cost.
+    ///
+    /// [`is_terminal::IsTerminal`]: https://docs.rs/is-terminal
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "detect-tty")] {
+    /// use std::net::TcpStream;
+    /// use yansi::{Paint, Condition};
+    ///
+    /// fn styled_for(socket: &TcpStream) -> Condition {
+    ///     Condition::from_tty(socket)
+    /// }
+    ///
+    /// let log = std::fs::File::open("Cargo.toml").unwrap();
+    /// let condition = Condition::from_tty(&log);
+    /// assert!(!condition());
+    /// assert_eq!("hi".red().whenever(condition).to_string(), "hi");
+    /// # }
+    /// ```
+    #[cfg(feature = "detect-tty")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "detect-tty")))]
+    pub fn from_tty<S: is_terminal::IsTerminal>(stream: S) -> Condition {
+        Condition::cached(stream.is_terminal())
+    }
+
     /// Creates a condition that is [`ALWAYS`](Self::ALWAYS) when `value` is
     /// `true` and [`NEVER`](Self::NEVER) otherwise.
     ///
@@ -155,6 +337,37 @@ impl Condition {
         }
     }
 
+    /// Invalidates the cached results of the built-in conditions, causing each
+    /// to be checked anew, once, the next time it is checked.
+    ///
+    /// Built-in conditions like, with the respective features,
+    /// `Condition::STDOUT_IS_TTY` and `Condition::CLICOLOR` cache their first
+    /// evaluation. In a long-running process, say, a server whose output is
+    /// redirected or whose environment is reconfigured at runtime, call this
+    /// method to re-detect. Conditions created via [`Condition::cached()`] are
+    /// constant and are not affected. Neither is [`Condition::DEFAULT`], as
+    /// [`Condition::os_support()`] attempts to enable support at most once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "detect-env")] {
+    /// use yansi::Condition;
+    ///
+    /// std::env::set_var("NO_COLOR", "1");
+    /// assert!(!Condition::no_color());
+    ///
+    /// std::env::remove_var("NO_COLOR");
+    /// assert!(!Condition::no_color());
+    ///
+    /// Condition::recheck();
+    /// assert!(Condition::no_color());
+    /// # }
+    /// ```
+    pub fn recheck() {
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+    }
+
     /// The backing function for [`Condition::ALWAYS`]. Returns `true` always.
     pub const fn always() -> bool { true }
 
@@ -170,6 +383,227 @@ impl Condition {
     pub fn os_support() -> bool {
         crate::windows::cache_enable()
     }
+
+    /// Returns a condition that evaluates to `true` when both `self` _and_
+    /// `other` do. `other` is not checked if `self` evaluates to `false`.
+    ///
+    /// Equivalent to `self & other`. See [combining
+    /// conditions](Condition#combining-conditions) for details. This is
+    /// [`try_and()`](Self::try_and()), panicking if there is no free slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combination needs a new slot and every slot has already
+    /// been claimed. Use [`try_and()`](Self::try_and()) to handle this case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Condition};
+    ///
+    /// let condition = Condition::ALWAYS.and(Condition::from(|| 1 + 1 == 2));
+    /// assert!(condition());
+    ///
+    /// let condition = Condition::from(|| true).and(Condition::NEVER);
+    /// assert!(!condition());
+    ///
+    /// # #[cfg(all(feature = "detect-tty", feature = "detect-env"))] {
+    /// let styled = "hi".red().whenever(Condition::STDOUT_IS_TTY.and(Condition::CLICOLOR));
+    /// # }
+    /// ```
+    pub fn and(self, other: Condition) -> Condition {
+        self.try_and(other).unwrap_or_else(|| Composition::exhausted())
+    }
+
+    /// Returns a condition that evaluates to `true` when either `self` _or_
+    /// `other` does. `other` is not checked if `self` evaluates to `true`.
+    ///
+    /// Equivalent to `self | other`. See [combining
+    /// conditions](Condition#combining-conditions) for details. This is
+    /// [`try_or()`](Self::try_or()), panicking if there is no free slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combination needs a new slot and every slot has already
+    /// been claimed. Use [`try_or()`](Self::try_or()) to handle this case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Condition;
+    ///
+    /// let condition = Condition::NEVER.or(Condition::from(|| 1 + 1 == 2));
+    /// assert!(condition());
+    ///
+    /// let condition = Condition::from(|| false).or(Condition::NEVER);
+    /// assert!(!condition());
+    /// ```
+    pub fn or(self, other: Condition) -> Condition {
+        self.try_or(other).unwrap_or_else(|| Composition::exhausted())
+    }
+
+    /// Like [`and()`](Self::and()), but returns `None` instead of panicking
+    /// if the combination needs a new slot and every slot has been claimed.
+    ///
+    /// Combinations that are simplified, like those with
+    /// [`ALWAYS`](Self::ALWAYS) and [`NEVER`](Self::NEVER), and combinations
+    /// that were made before never fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Condition;
+    ///
+    /// let condition = Condition::from(|| true).try_and(Condition::ALWAYS);
+    /// assert!(condition.expect("no slot is needed")());
+    ///
+    /// let condition = Condition::from(|| true).try_and(Condition::from(|| false));
+    /// if let Some(condition) = condition {
+    ///     assert!(!condition());
+    /// }
+    /// ```
+    pub fn try_and(self, other: Condition) -> Option<Condition> {
+        if self == Condition::NEVER || other == Condition::NEVER {
+            Some(Condition::NEVER)
+        } else if self == Condition::ALWAYS || self == other {
+            Some(other)
+        } else if other == Condition::ALWAYS {
+            Some(self)
+        } else {
+            Composition::AND.condition(self, other)
+        }
+    }
+
+    /// Like [`or()`](Self::or()), but returns `None` instead of panicking if
+    /// the combination needs a new slot and every slot has been claimed.
+    ///
+    /// Combinations that are simplified, like those with
+    /// [`ALWAYS`](Self::ALWAYS) and [`NEVER`](Self::NEVER), and combinations
+    /// that were made before never fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Condition;
+    ///
+    /// let condition = Condition::from(|| false).try_or(Condition::ALWAYS);
+    /// assert_eq!(condition, Some(Condition::ALWAYS));
+    ///
+    /// let condition = Condition::from(|| false).try_or(Condition::from(|| true));
+    /// if let Some(condition) = condition {
+    ///     assert!(condition());
+    /// }
+    /// ```
+    pub fn try_or(self, other: Condition) -> Option<Condition> {
+        if self == Condition::ALWAYS || other == Condition::ALWAYS {
+            Some(Condition::ALWAYS)
+        } else if self == Condition::NEVER || self == other {
+            Some(other)
+        } else if other == Condition::NEVER {
+            Some(self)
+        } else {
+            Composition::OR.condition(self, other)
+        }
+    }
+
+    /// Like `!self`, via [`Not`](core::ops::Not), but returns `None` instead
+    /// of panicking if the negation needs a new slot and every slot has been
+    /// claimed.
+    ///
+    /// Negating [`ALWAYS`](Self::ALWAYS) and [`NEVER`](Self::NEVER), and
+    /// negations that were made before, never fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Condition;
+    ///
+    /// assert_eq!(Condition::ALWAYS.try_not(), Some(Condition::NEVER));
+    ///
+    /// if let Some(condition) = Condition::from(|| false).try_not() {
+    ///     assert!(condition());
+    /// }
+    /// ```
+    pub fn try_not(self) -> Option<Condition> {
+        if self == Condition::ALWAYS {
+            Some(Condition::NEVER)
+        } else if self == Condition::NEVER {
+            Some(Condition::ALWAYS)
+        } else {
+            Composition::NOT.condition(self, self)
+        }
+    }
+}
+
+/// Returns a condition that evaluates to `true` exactly when `self` does not.
+///
+/// See [combining conditions](Condition#combining-conditions) for details.
+/// This is [`Condition::try_not()`], panicking if there is no free slot.
+///
+/// # Panics
+///
+/// Panics if the negation needs a new slot and every slot has already been
+/// claimed. Use [`Condition::try_not()`] to handle this case.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Condition;
+///
+/// assert!((!Condition::from(|| false))());
+/// assert_eq!(!Condition::ALWAYS, Condition::NEVER);
+/// ```
+impl core::ops::Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Condition {
+        self.try_not().unwrap_or_else(|| Composition::exhausted())
+    }
+}
+
+impl core::ops::BitAnd for Condition {
+    type Output = Condition;
+
+    fn bitand(self, rhs: Condition) -> Condition {
+        self.and(rhs)
+    }
+}
+
+impl core::ops::BitOr for Condition {
+    type Output = Condition;
+
+    fn bitor(self, rhs: Condition) -> Condition {
+        self.or(rhs)
+    }
+}
+
+// Conditions compare by the address of their function. Function addresses
+// aren't guaranteed to be unique, so comparisons are best-effort: they're
+// never relied upon for correctness, only for `Debug` and deduplication.
+impl PartialEq for Condition {
+    fn eq(&self, other: &Self) -> bool {
+        (self.0 as usize) == (other.0 as usize)
+    }
+}
+
+impl Eq for Condition { }
+
+impl PartialOrd for Condition {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Condition {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.0 as usize).cmp(&(other.0 as usize))
+    }
+}
+
+impl core::hash::Hash for Condition {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (self.0 as usize).hash(state)
+    }
 }
 
 impl Default for Condition {
@@ -187,54 +621,206 @@ impl core::ops::Deref for Condition {
 }
 
 impl AtomicCondition {
-    pub const DEFAULT: AtomicCondition = AtomicCondition::from(Condition::DEFAULT);
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const DEFAULT: AtomicCondition = AtomicCondition(AtomicPtr::new(core::ptr::null_mut()));
+
+    fn to_ptr(cond: Option<Condition>) -> *mut () {
+        cond.map_or(core::ptr::null_mut(), |cond| cond.0 as *mut ())
+    }
+
+    fn from_ptr(ptr: *mut ()) -> Option<Condition> {
+        // SAFETY: Non-null pointers are only ever stored by `to_ptr()`.
+        (!ptr.is_null()).then(|| unsafe {
+            Condition(core::mem::transmute::<*mut (), fn() -> bool>(ptr))
+        })
+    }
+
+    /// Stores `cond`, or marks the default condition as current if `None`.
+    pub fn store(&self, cond: Option<Condition>) {
+        self.0.store(Self::to_ptr(cond), Ordering::Release)
+    }
+
+    /// Returns the explicitly stored condition or `None` for the default.
+    pub fn get(&self) -> Option<Condition> {
+        Self::from_ptr(self.0.load(Ordering::Acquire))
+    }
 
-    pub const fn from(value: Condition) -> Self {
-        AtomicCondition(AtomicPtr::new(value.0 as *mut ()))
+    pub fn load(&self) -> Condition {
+        self.get().unwrap_or(Condition::DEFAULT)
     }
 
-    pub fn store(&self, cond: Condition) {
-        self.0.store(cond.0 as *mut (), Ordering::Release)
+    pub fn swap(&self, cond: Option<Condition>) -> Option<Condition> {
+        Self::from_ptr(self.0.swap(Self::to_ptr(cond), Ordering::AcqRel))
     }
 
     pub fn read(&self) -> bool {
-        let condition = unsafe {
-            Condition(core::mem::transmute(self.0.load(Ordering::Acquire)))
+        self.load()()
+    }
+}
+
+/// A slot recording one combination of conditions: an operation and its
+/// operands. Slots are claimed once and never released.
+///
+/// The operands are `fn() -> bool` pointers except for `Composition::ATOMIC`,
+/// whose `a` points to a `&'static AtomicBool`, and `Composition::BOXED`, whose
+/// `a` points to a leaked `BoxedFn`.
+struct Slot {
+    op: AtomicU8,
+    a: AtomicPtr<()>,
+    b: AtomicPtr<()>,
+}
+
+/// An operation combining conditions.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Composition(u8);
+
+macro_rules! slots {
+    ($($n:literal)*) => (
+        #[allow(clippy::declare_interior_mutable_const)]
+        const EMPTY_SLOT: Slot = Slot {
+            op: AtomicU8::new(Composition::EMPTY.0),
+            a: AtomicPtr::new(core::ptr::null_mut()),
+            b: AtomicPtr::new(core::ptr::null_mut()),
         };
 
-        condition()
+        static SLOTS: [Slot; [$($n),*].len()] = [$({ let _ = $n; EMPTY_SLOT }),*];
+
+        /// The condition functions for each slot, in order.
+        static SLOT_FNS: [fn() -> bool; [$($n),*].len()] = [$(|| SLOTS[$n].check()),*];
+    )
+}
+
+slots! {
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28
+    29 30 31 32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54
+    55 56 57 58 59 60 61 62 63
+}
+
+impl Composition {
+    const EMPTY: Composition = Composition(0);
+    const CLAIMED: Composition = Composition(1);
+    const AND: Composition = Composition(2);
+    const OR: Composition = Composition(3);
+    const NOT: Composition = Composition(4);
+    const ATOMIC: Composition = Composition(5);
+    #[cfg(feature = "alloc")]
+    const BOXED: Composition = Composition(6);
+
+    /// Returns the condition for the slot recording `self` applied to `a` and
+    /// `b`, claiming a new slot if there isn't one already. Returns `None` if
+    /// every slot is taken.
+    fn condition(self, a: Condition, b: Condition) -> Option<Condition> {
+        self.try_record(a.0 as *mut (), b.0 as *mut ())
+    }
+
+    /// Panics, reporting that every slot is taken.
+    #[cold]
+    fn exhausted() -> ! {
+        panic!("yansi: exhausted the {} slots for combined conditions", SLOTS.len())
+    }
+
+    /// Like `condition()`, but records the raw operands `a` and `b`.
+    fn try_record(self, a: *mut (), b: *mut ()) -> Option<Condition> {
+        use core::sync::atomic::Ordering::*;
+
+        for (slot, f) in SLOTS.iter().zip(SLOT_FNS.iter()) {
+            let mut op = slot.op.load(Acquire);
+            if op == Composition::EMPTY.0 {
+                let claimed = Composition::CLAIMED.0;
+                match slot.op.compare_exchange(op, claimed, Acquire, Acquire) {
+                    Ok(_) => {
+                        slot.a.store(a, Relaxed);
+                        slot.b.store(b, Relaxed);
+                        slot.op.store(self.0, Release);
+                        return Some(Condition(*f));
+                    }
+                    Err(current) => op = current,
+                }
+            }
+
+            if op == self.0 && slot.a.load(Relaxed) == a && slot.b.load(Relaxed) == b {
+                return Some(Condition(*f));
+            }
+        }
+
+        None
+    }
+}
+
+impl Slot {
+    fn check(&self) -> bool {
+        let op = Composition(self.op.load(Ordering::Acquire));
+        let (a, b) = (self.a.load(Ordering::Relaxed), self.b.load(Ordering::Relaxed));
+
+        // SAFETY: The operands were recorded by `record()` before `op` was
+        // released. For all but `ATOMIC`, they're `fn() -> bool` pointers.
+        // For `ATOMIC`, `a` is a `&'static AtomicBool`. For `BOXED`, `a` is a
+        // `&'static BoxedFn`.
+        let call = |f: *mut ()| unsafe { core::mem::transmute::<*mut (), fn() -> bool>(f)() };
+        match op {
+            Composition::AND => call(a) && call(b),
+            Composition::OR => call(a) || call(b),
+            Composition::NOT => !call(a),
+            Composition::ATOMIC => unsafe { &*(a as *const AtomicBool) }.load(Ordering::Relaxed),
+            #[cfg(feature = "alloc")]
+            Composition::BOXED => (unsafe { &*(a as *const BoxedFn) })(),
+            _ => unreachable!("condition slot is checked only once recorded"),
+        }
     }
 }
 
 #[allow(unused)]
 impl CachedBool {
-    const TRUE: u8 = 1;
-    const UNINIT: u8 = 2;
-    const INITING: u8 = 3;
+    const TRUE: usize = 1;
+    const UNINIT: usize = 2;
+    const INITING: usize = 3;
+    const STATE: usize = 0b11;
 
     pub const fn new() -> Self {
-        CachedBool(AtomicU8::new(Self::UNINIT))
+        CachedBool(AtomicUsize::new(Self::UNINIT))
     }
 
+    /// Returns the cached value if it was computed in the current generation.
+    /// Otherwise, computes, caches, and returns `f()`.
     pub fn get_or_init(&self, f: impl FnOnce() -> bool) -> bool {
+        let generation = GENERATION.load(core::sync::atomic::Ordering::Acquire);
+        self.get_or_init_in(generation.wrapping_shl(2), f)
+    }
+
+    /// Returns the cached value if it was ever computed. Otherwise, computes,
+    /// caches, and returns `f()`. Unaffected by [`Condition::recheck()`].
+    pub fn get_or_init_once(&self, f: impl FnOnce() -> bool) -> bool {
+        self.get_or_init_in(0, f)
+    }
+
+    fn get_or_init_in(&self, generation: usize, f: impl FnOnce() -> bool) -> bool {
         use core::sync::atomic::Ordering::*;
 
-        match self.0.compare_exchange(Self::UNINIT, Self::INITING, AcqRel, Relaxed) {
-            Ok(_) => {
-                let new_value = f();
-                self.0.store(new_value as u8 /* false = 0, true = 1 */, Release);
-                new_value
-            }
-            Err(Self::INITING) => {
-                let mut value;
-                while { value = self.0.load(Acquire); value } == Self::INITING {
+        let mut f = Some(f);
+        loop {
+            let value = self.0.load(Acquire);
+            match value & Self::STATE {
+                Self::INITING => {
                     #[cfg(feature = "std")]
                     std::thread::yield_now();
                 }
-
-                value == Self::TRUE
-            },
-            Err(value) => value == Self::TRUE,
+                state if state != Self::UNINIT && value & !Self::STATE == generation => {
+                    return state == Self::TRUE;
+                }
+                _ => {
+                    let initing = generation | Self::INITING;
+                    if self.0.compare_exchange(value, initing, AcqRel, Relaxed).is_err() {
+                        continue;
+                    }
+
+                    if let Some(f) = f.take() {
+                        // `false` is stored as `0`, `true` as `Self::TRUE`.
+                        let new_value = f();
+                        self.0.store(generation | new_value as usize, Release);
+                        return new_value;
+                    }
+                }
+            }
         }
     }
 }
@@ -317,19 +903,19 @@ macro_rules! conditions {
 use is_terminal::is_terminal as is_tty;
 
 conditions! { feature = "detect-tty"
-    is_tty(&std::io::stdout()),
+    is_tty(std::io::stdout()),
         STDOUT_IS_TTY: stdout_is_tty,
         STDOUT_IS_TTY_LIVE: stdout_is_tty_live,
 
-    is_tty(&std::io::stderr()),
+    is_tty(std::io::stderr()),
         STDERR_IS_TTY: stderr_is_tty,
         STDERR_IS_TTY_LIVE: stderr_is_tty_live,
 
-    is_tty(&std::io::stdin()),
+    is_tty(std::io::stdin()),
         STDIN_IS_TTY: stdin_is_tty,
         STDIN_IS_TTY_LIVE: stdin_is_tty_live,
 
-    is_tty(&std::io::stdout()) && is_tty(&std::io::stderr()),
+    is_tty(std::io::stdout()) && is_tty(std::io::stderr()),
         STDOUTERR_ARE_TTY: stdouterr_are_tty,
         STDOUTERR_ARE_TTY_LIVE: stdouterr_are_tty_live,
 }
@@ -339,6 +925,16 @@ pub fn env_set_or(name: &str, default: bool) -> bool {
     std::env::var_os(name).map_or(default, |v| v != "0")
 }
 
+/// Unlike `env_set_or()`, also treats an empty value and `false`, as in
+/// `CI=false`, as unset: CI variables are often set to these to opt out.
+#[cfg(feature = "detect-env")]
+pub fn any_env_set(names: &[&str]) -> bool {
+    names.iter().any(|name| match std::env::var_os(name) {
+        Some(v) => !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"),
+        None => false,
+    })
+}
+
 conditions! { feature = "detect-env"
     env_set_or("CLICOLOR_FORCE", false) || env_set_or("CLICOLOR", true),
         CLICOLOR: clicolor,
@@ -347,10 +943,29 @@ conditions! { feature = "detect-env"
     !env_set_or("NO_COLOR", false),
         YES_COLOR: no_color,
         YES_COLOR_LIVE: no_color_live,
+
+    std::env::var_os("TERM").map_or(true, |term| term == "dumb"),
+        TERM_DUMB: term_dumb,
+        TERM_DUMB_LIVE: term_dumb_live,
+
+    std::env::var_os("TERM").map_or(false, |term| term != "dumb"),
+        TERM_SUPPORTS_COLOR: term_supports_color,
+        TERM_SUPPORTS_COLOR_LIVE: term_supports_color_live,
+
+    any_env_set(&[
+        "CI", "GITHUB_ACTIONS", "GITLAB_CI", "BUILDKITE", "CIRCLECI", "TRAVIS",
+        "APPVEYOR", "TF_BUILD", "JENKINS_URL", "TEAMCITY_VERSION", "DRONE",
+    ]),
+        CI: ci,
+        CI_LIVE: ci_live,
 }
 
 conditions! { all(feature = "detect-env", feature = "detect-tty")
     Condition::stdouterr_are_tty() && Condition::clicolor() && Condition::no_color(),
         TTY_AND_COLOR: tty_and_color,
         TTY_AND_COLOR_LIVE: tty_and_color_live,
+
+    Condition::ci() || Condition::stdouterr_are_tty(),
+        CI_OR_TTY: ci_or_tty,
+        CI_OR_TTY_LIVE: ci_or_tty_live,
 }
@@ -2,6 +2,13 @@ use crate::condition::{AtomicCondition, Condition};
 
 static ENABLED: AtomicCondition = AtomicCondition::DEFAULT;
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    static THREAD_ENABLED: core::cell::Cell<Option<bool>> = const {
+        core::cell::Cell::new(None)
+    };
+}
+
 /// Unconditionally disables styling globally.
 ///
 /// # Example
@@ -17,7 +24,7 @@ static ENABLED: AtomicCondition = AtomicCondition::DEFAULT;
 /// assert_eq!("go".green().to_string(), "go".to_string());
 /// ```
 pub fn disable() {
-    ENABLED.store(Condition::NEVER);
+    ENABLED.store(Some(Condition::NEVER));
 }
 
 /// Unconditionally enables styling globally.
@@ -39,7 +46,7 @@ pub fn disable() {
 /// assert_ne!("go".green().to_string(), "go".to_string());
 /// ```
 pub fn enable() {
-    ENABLED.store(Condition::ALWAYS);
+    ENABLED.store(Some(Condition::ALWAYS));
 }
 
 /// Dynamically enables and disables styling globally based on `condition`.
@@ -80,7 +87,152 @@ pub fn enable() {
 /// # }
 /// ```
 pub fn whenever(condition: Condition) {
-    ENABLED.store(condition);
+    ENABLED.store(Some(condition));
+}
+
+/// Sets the global condition to one that honors the environment.
+///
+/// Styling is enabled only if all of the following hold:
+///
+///   * the OS supports styling, as checked by [`Condition::DEFAULT`],
+///   * `NO_COLOR` is unset, as checked by [`Condition::YES_COLOR`],
+///   * `CLICOLOR` is not `0` or `CLICOLOR_FORCE` is set, as checked by
+///     [`Condition::CLICOLOR`],
+///   * with `detect-tty` enabled, `stdout` is a TTY, as checked by
+///     `Condition::STDOUT_IS_TTY`.
+///
+/// Each check is performed once, on first use, and cached thereafter. Only
+/// `stdout` is checked as that's where painted values are typically written:
+/// redirecting `stderr`, say via `2>log`, leaves styling enabled for an
+/// interactive `stdout`. To decide whether to style output written to
+/// `stderr`, additionally check `Condition::STDERR_IS_TTY`.
+///
+/// Enabling the `detect-env` feature does _not_ change the default global
+/// condition, which remains [`Condition::DEFAULT`]: calling this function,
+/// typically once at the start of `main()`, opts in. Like [`whenever()`],
+/// this function replaces any previously set condition, and it can itself be
+/// overridden by later calls to [`enable()`], [`disable()`], or
+/// [`whenever()`].
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// yansi::init();
+///
+/// // Styled if `NO_COLOR` isn't set, `stdout` is a TTY, etc.
+/// println!("{}", "Hello!".green());
+/// ```
+#[cfg(feature = "detect-env")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "detect-env")))]
+pub fn init() {
+    fn env_default() -> bool {
+        #[cfg(feature = "detect-tty")]
+        let tty = Condition::stdout_is_tty();
+
+        #[cfg(not(feature = "detect-tty"))]
+        let tty = true;
+
+        Condition::os_support() && Condition::no_color() && Condition::clicolor() && tty
+    }
+
+    whenever(Condition(env_default));
+}
+
+/// Restores the previous global condition when dropped.
+///
+/// Returned by [`enable_guard()`], [`disable_guard()`], and
+/// [`whenever_guard()`]. See their documentation for details.
+#[derive(Debug)]
+#[must_use = "the previous condition is restored as soon as the guard is dropped"]
+pub struct Guard {
+    previous: Option<Condition>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        ENABLED.store(self.previous);
+    }
+}
+
+/// Like [`enable()`] but returns a guard that restores the previous global
+/// condition when dropped.
+///
+/// See [`whenever_guard()`] for details.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// yansi::disable();
+/// {
+///     let _guard = yansi::enable_guard();
+///     assert_eq!("go".green().to_string(), "\x1b[32mgo\x1b[0m");
+/// }
+///
+/// assert_eq!("go".green().to_string(), "go");
+/// # yansi::enable();
+/// ```
+pub fn enable_guard() -> Guard {
+    whenever_guard(Condition::ALWAYS)
+}
+
+/// Like [`disable()`] but returns a guard that restores the previous global
+/// condition when dropped.
+///
+/// See [`whenever_guard()`] for details.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// yansi::enable();
+/// {
+///     let _guard = yansi::disable_guard();
+///     assert_eq!("go".green().to_string(), "go");
+/// }
+///
+/// assert_eq!("go".green().to_string(), "\x1b[32mgo\x1b[0m");
+/// ```
+pub fn disable_guard() -> Guard {
+    whenever_guard(Condition::NEVER)
+}
+
+/// Like [`whenever()`] but returns a guard that restores the previous global
+/// condition when dropped.
+///
+/// Guards restore exactly the condition that was set when they were created,
+/// so nested guards should be dropped in the reverse order of their creation,
+/// as they are when bound to variables in nested scopes. Like [`whenever()`],
+/// the guard affects every thread: to override the global condition on the
+/// current thread only, use [`with_enabled()`] instead.
+///
+/// [`with_enabled()`]: crate::with_enabled()
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Condition;
+///
+/// yansi::enable();
+/// {
+///     let _outer = yansi::whenever_guard(Condition::NEVER);
+///     assert!(!yansi::is_enabled());
+///     {
+///         let _inner = yansi::whenever_guard(Condition::ALWAYS);
+///         assert!(yansi::is_enabled());
+///     }
+///
+///     assert!(!yansi::is_enabled());
+/// }
+///
+/// assert!(yansi::is_enabled());
+/// ```
+pub fn whenever_guard(condition: Condition) -> Guard {
+    Guard { previous: ENABLED.swap(Some(condition)) }
 }
 
 /// Returns `true` if styling is globally enabled and `false` otherwise.
@@ -88,7 +240,8 @@ pub fn whenever(condition: Condition) {
 /// By default, styling is enabled based on [`Condition::DEFAULT`], which checks
 /// for operating system support. It can be enabled and disabled on-the-fly with
 /// [`enable()`] and [`disable()`] and via a dynamic condition with
-/// [`whenever()`].
+/// [`whenever()`]. Within a call to [`with_enabled()`], the thread-local value
+/// passed to it is returned instead.
 ///
 /// # Example
 ///
@@ -106,5 +259,135 @@ pub fn whenever(condition: Condition) {
 /// assert!(yansi::is_enabled());
 /// ```
 pub fn is_enabled() -> bool {
+    #[cfg(feature = "std")]
+    if let Ok(Some(value)) = THREAD_ENABLED.try_with(|v| v.get()) {
+        return value;
+    }
+
     ENABLED.read()
 }
+
+/// Calls `f` with styling enabled, if `value` is `true`, or disabled
+/// otherwise, on the current thread only.
+///
+/// For the duration of the call, `value` takes precedence over the global
+/// setting on the current thread: [`is_enabled()`] returns `value`, and the
+/// global condition set by [`enable()`], [`disable()`], or [`whenever()`] is
+/// not consulted. Other threads are unaffected and continue to use the global
+/// setting. Changing the global setting from within `f` updates it as usual,
+/// but the change is not observed by the current thread until `f` returns.
+///
+/// When `f` returns or panics, the previous state of the current thread is
+/// restored. Calls can be nested: the innermost `value` applies.
+///
+/// Per-style conditions set with [`Style::whenever()`] are checked as usual.
+///
+/// [`Style::whenever()`]: crate::Style::whenever()
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// yansi::enable();
+/// let plain = yansi::with_enabled(false, || "go".green().to_string());
+/// assert_eq!(plain, "go");
+///
+/// // Outside of the call, and on other threads, the global setting is used.
+/// assert_ne!("go".green().to_string(), "go");
+///
+/// yansi::disable();
+/// let styled = yansi::with_enabled(true, || {
+///     std::thread::spawn(|| assert!(!yansi::is_enabled())).join().unwrap();
+///     "go".green().to_string()
+/// });
+///
+/// assert_eq!(styled, "\x1b[32mgo\x1b[0m");
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "std")))]
+pub fn with_enabled<R>(value: bool, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<bool>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let _ = THREAD_ENABLED.try_with(|v| v.set(self.0));
+        }
+    }
+
+    let _restore = Restore(THREAD_ENABLED.with(|v| v.replace(Some(value))));
+    f()
+}
+
+/// A standard output stream.
+///
+/// Used with [`is_enabled_for()`] to decide whether styling should be applied
+/// to output written to a specific stream.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Stream {
+    /// The standard output stream, `stdout`.
+    Stdout,
+    /// The standard error stream, `stderr`.
+    Stderr,
+}
+
+/// Returns `true` if styling is enabled for output written to `stream` and
+/// `false` otherwise.
+///
+/// If styling is not [globally enabled](is_enabled()), this returns `false`.
+/// Otherwise, if the global condition is the default, i.e., it was not changed
+/// via [`enable()`], [`disable()`], or [`whenever()`], or a [`Guard`] restored
+/// it, and the `detect-tty` feature is enabled, this additionally checks
+/// whether `stream` is a TTY, returning `true` only if it is. Within a call to
+/// [`with_enabled()`], the thread-local value passed to it is returned
+/// irrespective of `stream`.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Stream};
+///
+/// // An explicitly enabled global condition applies to every stream.
+/// {
+///     let _guard = yansi::enable_guard();
+///     assert!(yansi::is_enabled_for(Stream::Stdout));
+///     assert!(yansi::is_enabled_for(Stream::Stderr));
+/// }
+///
+/// {
+///     let _guard = yansi::disable_guard();
+///     assert!(!yansi::is_enabled_for(Stream::Stdout));
+///     assert!(!yansi::is_enabled_for(Stream::Stderr));
+/// }
+///
+/// // Once the guards are dropped, the default condition is back in effect,
+/// // and the TTY-ness of each stream is considered when `detect-tty` is
+/// // enabled.
+/// if yansi::is_enabled_for(Stream::Stderr) {
+///     eprintln!("{}", "error".red());
+/// } else {
+///     eprintln!("error");
+/// }
+/// ```
+pub fn is_enabled_for(stream: Stream) -> bool {
+    #[cfg(feature = "std")]
+    if let Ok(Some(value)) = THREAD_ENABLED.try_with(|v| v.get()) {
+        return value;
+    }
+
+    let explicit = ENABLED.get();
+    if !explicit.unwrap_or(Condition::DEFAULT)() {
+        return false;
+    }
+
+    #[cfg(feature = "detect-tty")]
+    if explicit.is_none() {
+        return match stream {
+            Stream::Stdout => Condition::stdout_is_tty(),
+            Stream::Stderr => Condition::stderr_is_tty(),
+        };
+    }
+
+    let _ = stream;
+    true
+}
@@ -0,0 +1,101 @@
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+
+use crate::Color;
+
+/// Writes `text` to a new string with each `char` colored by `color`.
+///
+/// `color` is called with the index of each non-whitespace `char` and the
+/// number of `char`s in `text`. Whitespace is written with the color of the
+/// preceding `char`, and a new foreground color sequence is only emitted when
+/// the color changes. Styling is reset at the end. If painting is globally
+/// disabled, `text` is returned as-is.
+fn colorize(text: &str, mut color: impl FnMut(usize, usize) -> Color) -> String {
+    if !crate::is_enabled() {
+        return text.into();
+    }
+
+    let (len, mut last) = (text.chars().count(), None);
+    let mut string = String::with_capacity(text.len() * 4);
+    for (i, c) in text.chars().enumerate() {
+        if !c.is_whitespace() {
+            let color = color(i, len);
+            if last != Some(color) {
+                let _ = color.foreground().fmt_prefix(&mut string);
+                last = Some(color);
+            }
+        }
+
+        string.push(c);
+    }
+
+    if last.is_some() {
+        string.push_str("\x1B[0m");
+    }
+
+    string
+}
+
+/// Returns `text` with its characters' foreground colors interpolated from
+/// `start` to `end`.
+///
+/// The first `char` is colored `start` and the last `end`, as blended by
+/// [`Color::blend()`], with the remaining `char`s evenly spaced in between.
+/// Colors are assigned per `char`, not per byte, so multibyte characters are
+/// colored correctly. Styling is reset at the end of the string.
+///
+/// A foreground color sequence is only emitted when the color changes, and
+/// whitespace is never colored. If painting is [globally
+/// disabled](crate::is_enabled()), `text` is returned unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::Color::*;
+///
+/// println!("{}", yansi::gradient("rainbow", Rgb(255, 0, 0), Rgb(0, 0, 255)));
+///
+/// # yansi::enable();
+/// let text = yansi::gradient("ab", Rgb(255, 0, 0), Rgb(0, 0, 255));
+/// assert_eq!(text, "\x1b[38;2;255;0;0ma\x1b[38;2;0;0;255mb\x1b[0m");
+/// ```
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub fn gradient(text: &str, start: Color, end: Color) -> String {
+    colorize(text, |i, len| match len {
+        0 | 1 => start.blend(end, 0.0),
+        _ => start.blend(end, i as f32 / (len - 1) as f32),
+    })
+}
+
+/// Returns `text` with its characters' foreground colors cycling through the
+/// six primary hues.
+///
+/// The `char`s of `text` are colored [`Red`], [`Yellow`], [`Green`], [`Cyan`],
+/// [`Blue`], and [`Magenta`], in that order, wrapping around to `Red` after
+/// `Magenta`. As with [`gradient()`], whitespace is never colored, styling is
+/// reset at the end of the string, and `text` is returned unchanged if
+/// painting is [globally disabled](crate::is_enabled()).
+///
+/// [`Red`]: Color::Red
+/// [`Yellow`]: Color::Yellow
+/// [`Green`]: Color::Green
+/// [`Cyan`]: Color::Cyan
+/// [`Blue`]: Color::Blue
+/// [`Magenta`]: Color::Magenta
+///
+/// # Example
+///
+/// ```rust
+/// println!("{}", yansi::rainbow("Congratulations!"));
+///
+/// # yansi::enable();
+/// let text = yansi::rainbow("a b");
+/// assert_eq!(text, "\x1b[31ma \x1b[32mb\x1b[0m");
+/// ```
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub fn rainbow(text: &str) -> String {
+    use Color::*;
+
+    const HUES: [Color; 6] = [Red, Yellow, Green, Cyan, Blue, Magenta];
+    colorize(text, |i, _| HUES[i % HUES.len()])
+}
@@ -36,18 +36,58 @@
 //! <span style="background: green; color: black;">our docs</span>
 //! </a>.
 //!
+//! ## Linked Styles
+//!
+//! A [`Style`] can also carry a target URL, set via [`Style::link()`] or
+//! [`Color::link()`]. Since a `Style` is `Copy` and can be constructed in
+//! `const` contexts, the URL must be a `&'static str`. Any value painted with
+//! the style is hyperlinked to the URL:
+//!
+//! ```rust
+//! use yansi::{Paint, Style, Color::*};
+//!
+//! static DOCS: Style = Green.link("https://docs.rs/yansi").bold();
+//!
+//! println!("Go to {}.", "our docs".paint(DOCS));
+//! ```
+//!
+//! The link is opened by the style's [prefix](Style::fmt_prefix()) and closed
+//! by its [suffix](Style::fmt_suffix()), so it respects all of the usual
+//! conditions and quirks. In particular, a [lingering](Quirk::Linger) linked
+//! style leaves the link open. When [combining](Style::combine()) styles, the
+//! link of the overriding style, if any, wins.
+//!
+//! # Escaping
+//!
+//! Target URLs are embedded in an escape sequence that only allows bytes in
+//! the range 32 to 126, and a URL containing an escape character could
+//! terminate the sequence early. To ensure that the sequence is always well
+//! formed, even for arbitrary user-supplied URLs, URLs are written with all
+//! control characters, including `ESC` and `BEL`, removed and with spaces and
+//! every byte of non-ASCII characters percent-encoded:
+//!
+//! ```rust
+//! use yansi::hyperlink::HyperlinkExt;
+//!
+//! # yansi::enable();
+//! let link = "docs".link("https://example.com/my docs/\x1B]ü").to_string();
+//! assert!(link.starts_with("\x1B]8;;https://example.com/my%20docs/]%C3%BC\x1B\\"));
+//! ```
+//!
+//! Similarly, control characters as well as `;` and `:`, which delimit
+//! parameters, are removed from link [`id`](PaintedLink::id())s.
+//!
 //! # Caveats
 //!
-//! 1. You can only create a link when there is a target value to print, that
-//!    is, when the receiver is something "printable". In other words, you
-//!    _cannot_ apply `link()` to a bare `Style`. This means the following will
-//!    not work:
+//! 1. The target URL of a linked [`Style`] must be a `&'static str`. To link
+//!    to a URL computed at runtime, use [`link()`] on the value instead. This
+//!    means the following will not work:
 //!
 //!    ```rust,compile_fail
 //!    use yansi::{Paint, Style, Color::*};
-//!    use yansi::hyperlink::HyperlinkExt;
 //!
-//!    static LINKED: Style = Green.link("https://docs.rs/yansi");
+//!    let url = format!("https://docs.rs/{}", "yansi");
+//!    let linked: Style = Green.link(&url);
 //!    ```
 //!    <br/>
 //!
@@ -63,6 +103,7 @@ use crate::*;
 pub struct PaintedLink<T> {
     painted: Painted<T>,
     link: String,
+    id: Option<String>,
 }
 
 /// Extension trait to apply hyperlinks to any value, implemented for all types.
@@ -71,7 +112,9 @@ pub struct PaintedLink<T> {
 pub trait HyperlinkExt {
     /// Create a painted hyperlink with a target URL of `url`.
     ///
-    /// See [`hyperlink`] for details.
+    /// Control characters in `url` are removed while spaces and non-ASCII
+    /// characters are percent-encoded. See [`hyperlink`](crate::hyperlink#escaping)
+    /// for details.
     ///
     /// # Example
     ///
@@ -84,17 +127,20 @@ pub trait HyperlinkExt {
 }
 
 impl<T> PaintedLink<T> {
-    fn fmt_args(
+    fn fmt_with(
         &self,
         fmt: &dyn Fn(&Painted<T>, &mut fmt::Formatter) -> fmt::Result,
         f: &mut fmt::Formatter,
-        _args: fmt::Arguments<'_>,
     ) -> fmt::Result {
         if !self.painted.enabled() {
             return fmt(&self.painted, f);
         }
 
-        write!(f, "\x1B]8;;{}\x1B\\", self.link)?;
+        match self.id {
+            Some(ref id) => write!(f, "\x1B]8;id={};{}\x1B\\", id, Url(&self.link))?,
+            None => write!(f, "\x1B]8;;{}\x1B\\", Url(&self.link))?,
+        }
+
         fmt(&self.painted, f)?;
         write!(f, "\x1B]8;;\x1B\\")
     }
@@ -102,9 +148,35 @@ impl<T> PaintedLink<T> {
 
 impl_fmt_traits!(<T> PaintedLink<T> => self.painted (Painted<T>));
 
+/// Displays a URL with control characters removed and spaces as well as
+/// non-ASCII characters percent-encoded.
+pub(crate) struct Url<'a>(pub &'a str);
+
+impl fmt::Display for Url<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in self.0.char_indices() {
+            if c.is_ascii() && !c.is_ascii_control() && c != ' ' {
+                continue;
+            }
+
+            f.write_str(&self.0[start..i])?;
+            start = i + c.len_utf8();
+            if !c.is_control() {
+                let mut buf = [0; 4];
+                for byte in c.encode_utf8(&mut buf).bytes() {
+                    write!(f, "%{:02X}", byte)?;
+                }
+            }
+        }
+
+        f.write_str(&self.0[start..])
+    }
+}
+
 impl<T> HyperlinkExt for T {
     fn link(&self, url: impl ToString) -> PaintedLink<&Self> {
-        PaintedLink { painted: Painted::new(self), link: url.to_string() }
+        PaintedLink { painted: Painted::new(self), link: url.to_string(), id: None }
     }
 }
 
@@ -112,7 +184,9 @@ impl<T> HyperlinkExt for T {
 impl<T> Painted<T> {
     /// Create a painted hyperlink with a target URL of `url`.
     ///
-    /// See [`hyperlink`] for details.
+    /// Control characters in `url` are removed while spaces and non-ASCII
+    /// characters are percent-encoded. See [`hyperlink`](crate::hyperlink#escaping)
+    /// for details.
     ///
     /// # Example
     ///
@@ -123,7 +197,54 @@ impl<T> Painted<T> {
     /// println!("See {}.", "our docs".green().link("https://docs.rs/yansi"));
     /// ```
     pub fn link(&self, url: impl ToString) -> PaintedLink<&Self> {
-        PaintedLink { painted: Painted::new(self), link: url.to_string() }
+        PaintedLink { painted: Painted::new(self), link: url.to_string(), id: None }
+    }
+}
+
+/// Experimental support for hyperlinking.
+impl Style {
+    /// Returns `self` with the hyperlink target URL set to `url`.
+    ///
+    /// Values painted with the returned style are hyperlinked to `url`. See
+    /// [linked styles](crate::hyperlink#linked-styles) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Style};
+    ///
+    /// static DOCS: Style = Style::new().underline().link("https://docs.rs/yansi");
+    ///
+    /// # yansi::enable();
+    /// let link = "our docs".paint(DOCS).to_string();
+    /// assert_eq!(link, "\x1b]8;;https://docs.rs/yansi\x1b\\\
+    ///     \x1b[4mour docs\x1b[0m\x1b]8;;\x1b\\");
+    /// ```
+    pub const fn link(mut self, url: &'static str) -> Style {
+        self.link = Some(url);
+        self
+    }
+}
+
+/// Experimental support for hyperlinking.
+impl Color {
+    /// Returns a style with a foreground color of `self` and a hyperlink
+    /// target URL of `url`.
+    ///
+    /// This is equivalent to `self.foreground().link(url)`. See
+    /// [`Style::link()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Style, Color::*};
+    ///
+    /// static DOCS: Style = Green.link("https://docs.rs/yansi");
+    ///
+    /// println!("Go to {}.", "our docs".paint(DOCS));
+    /// ```
+    pub const fn link(self, url: &'static str) -> Style {
+        self.foreground().link(url)
     }
 }
 
@@ -134,5 +255,33 @@ impl<T> PaintedLink<T> {
         self
     }
 
-    properties!([pub const] constructor(Self) -> Self);
+    /// Sets the link's `id` parameter to `id`.
+    ///
+    /// Terminals treat links with the same target URL _and_ `id` as a single
+    /// link, for instance, highlighting all of them together on hover. This
+    /// allows a link that spans multiple lines or that is printed in pieces
+    /// to be recognized as a single clickable region. Without an `id`,
+    /// terminals decide on their own which links belong together.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::hyperlink::HyperlinkExt;
+    ///
+    /// let url = "https://docs.rs/yansi";
+    /// println!("{}", "our".link(url).id("docs"));
+    /// println!("{}", "docs".link(url).id("docs"));
+    ///
+    /// # yansi::enable();
+    /// let link = "docs".link(url).id("ref-1").to_string();
+    /// assert!(link.starts_with("\x1b]8;id=ref-1;https://docs.rs/yansi\x1b\\"));
+    /// ```
+    pub fn id(mut self, id: impl ToString) -> Self {
+        let mut id = id.to_string();
+        id.retain(|c| !c.is_control() && c != ';' && c != ':');
+        self.id = Some(id);
+        self
+    }
+
+    properties!([#[must_use] pub const] constructor(Self) -> Self);
 }
@@ -6,7 +6,7 @@
 //!
 //!   * This library makes simple things _simple_: `use` [`Paint`] and go!
 //!   * Zero dependencies by default. It really is simple.
-//!   * Zero allocations except as needed by opt-in [wrapping](#wrapping).
+//!   * Zero allocations, even when [wrapping](#wrapping).
 //!   * [Automatic Windows support] for the vast majority (95%+) of Windows
 //!     users.
 //!   * [Featureful `no_std`], no-`alloc`, support with `default-features =
@@ -98,13 +98,28 @@
 //! <span style="color: yellow;"><b>Set</b></span>,
 //! <span style="color: white; background: red;"><u><em>STOP</em></u></span>!
 //!
+//! ## Avoiding Method Collisions
+//!
+//! As [`Paint`] is implemented for every type, importing it adds all of its
+//! builder methods to every value in scope. If these collide with your own
+//! trait methods, import [`Paintable`] instead. It provides only `styled()`,
+//! an alias of `paint()`, while the [`Painted`] it returns retains every
+//! builder method:
+//!
+//! ```rust
+//! use yansi::{Paintable, Color::*};
+//!
+//! println!("{}", "STOP".styled(White.on_red()).bold());
+//! ```
+//!
 //! ## Conditional Styling
 //!
 //! ### Globally
 //!
 //! Styling is enabled by default but can be enabled and disabled globally via
 //! [`enable()`] and [`disable()`]. When styling is disabled, no ANSI escape
-//! codes are emitted, and [_masked_] values are omitted entirely.
+//! codes are emitted, except for [forced](#forcing) styles, and [_masked_]
+//! values are omitted entirely.
 //!
 //! Global styling can also be dynamically enabled and disabled using
 //! [`whenever()`] with an arbitrary [`Condition`]: a function that returns
@@ -112,6 +127,32 @@
 //! is displayed. The associated styling is enabled, and mask values emitted,
 //! exactly when and only when the condition returns `true`.
 //!
+//! To change the global setting temporarily, use [`enable_guard()`],
+//! [`disable_guard()`], or [`whenever_guard()`], which return a [`Guard`] that
+//! restores the previous setting when dropped.
+//!
+//! With the `detect-env` feature, [`init()`] sets the global condition to one
+//! that honors `NO_COLOR` and `CLICOLOR` and, with `detect-tty`, checks that
+//! `stdout` is a TTY. Enabling the feature alone does not change the default
+//! condition: call `init()` to opt in.
+//!
+//! To decide whether to style output written to a specific stream, use
+//! [`is_enabled_for()`]. With the default condition and the `detect-tty`
+//! feature enabled, it additionally checks whether the stream is a TTY. To
+//! return to the default condition after changing it, change it with a
+//! [`Guard`], e.g. via [`whenever_guard()`], and drop the guard.
+//!
+//! With `std`, the global setting can be overridden on the current thread for
+//! the duration of a closure with [`with_enabled()`]. This is useful, for
+//! instance, in tests that run in parallel:
+//!
+//! ```rust
+//! use yansi::Paint;
+//!
+//! let plain = yansi::with_enabled(false, || format!("{}", "hi".red()));
+//! assert_eq!(plain, "hi");
+//! ```
+//!
 //! ### Per-`Style`
 //!
 //! A specific `Style` can itself be conditionally applied by using
@@ -164,10 +205,46 @@
 //!
 //! With styling disabled, this prints: `>` I like colors!
 //!
-//! ## Wrapping
+//! To emit a plain-text fallback in place of a masked value, use
+//! [`or_mask()`](Painted::or_mask()):
+//!
+//! ```rust
+//! use yansi::Paint;
 //!
-//! **Note:** _Either the `std` or `alloc` feature is required for wrapping.
-//! `std` is enabled by default. See [crate features](#crate-features)._
+//! println!("I like colors! {}", "🎨".new().or_mask("(art)"));
+//! ```
+//!
+//! With styling disabled, this prints: `>` I like colors! (art)
+//!
+//! ## Forcing
+//!
+//! The inverse of masking is _forcing_ via [`Quirk::Force`] or the equivalent
+//! [`force()`](Painted::force()) builder of [`Painted`] and [`Style`], which,
+//! like other [`Painted`-only methods](Paint#painted-only-methods), is not
+//! provided by [`Paint`] itself. A forced style is applied even
+//! when styling is disabled globally, whether by [`disable()`], a global
+//! condition set via [`whenever()`], or [`with_enabled()`]. This is intended
+//! for output that is meaningless without styling, such as the highlighting of
+//! a critical error, and should be used sparingly: it overrides the user's
+//! choice to disable styling.
+//!
+//! A forced style's own condition, set via [`Style::whenever()`], continues to
+//! apply. Styling is thus emitted for a forced style exactly when its
+//! condition, if any, returns `true`. In particular, a forced style with
+//! [`Condition::NEVER`] is never applied, and a forced, masked value is
+//! emitted unless its condition returns `false`.
+//!
+//! ```rust
+//! use yansi::{Paint, Condition};
+//!
+//! yansi::disable();
+//! assert_eq!("FATAL".red().bold().to_string(), "FATAL");
+//! assert_eq!("FATAL".red().bold().force().to_string(), "\x1b[1;31mFATAL\x1b[0m");
+//! assert_eq!("FATAL".red().force().whenever(Condition::NEVER).to_string(), "FATAL");
+//! # yansi::enable();
+//! ```
+//!
+//! ## Wrapping
 //!
 //! Styling can _wrap_ via [`Quirk::Wrap`] or the equivalent
 //! [`wrap()`](Painted::wrap()) constructor. A wrapping style modifies any
@@ -200,9 +277,10 @@
 //! Without wrapping, the reset after `"Stop".red()` would not be overwritten:
 //! `>` Hey! <span style="color: red">Stop</span> and <span style="color: green">Go</span>
 //!
-//! Wrapping incurs a performance cost due to an extra allocation and
-//! replacement if the wrapped item has styling applied to it. Otherwise, it
-//! does not allocate nor incur a meaningful performance cost.
+//! Wrapping never allocates, even without `alloc` or `std`, and formats the
+//! wrapped item just once: resets are replaced as they're written, holding back
+//! at most the few bytes of a reset split across writes. Its cost is thus that
+//! of scanning the formatted item for resets.
 //!
 //! ## Lingering
 //!
@@ -218,6 +296,12 @@
 //! the value, irrespective of any lingering applied. It can be used as a way to
 //! finalize a lingering style.
 //!
+//! Both are also available as a single setting via the
+//! [`reset_mode()`](Painted::reset_mode()) constructor: [`ResetMode::Never`]
+//! lingers, [`ResetMode::Always`] force resets, and [`ResetMode::Auto`], the
+//! default, resets only when styling was applied. Unlike `linger()` and
+//! `resetting()`, which combine, `reset_mode()` replaces any previous setting.
+//!
 //! Lingering itself is useful in situations where a given style is to be
 //! repeated across multiple values, or when style is intended to persist even
 //! across values that are not styled with `yansi`. It also allows avoiding
@@ -271,6 +355,43 @@
 //!
 //! `>` <span style="color: red;"> A B <u>C D E</u> </span> F
 //!
+//! Resetting can also occur _before_ a value via [`Quirk::ResetBefore`] or the
+//! equivalent [`reset_before()`](Painted::reset_before()) constructor, which
+//! emits a reset ahead of the style's prefix. This clears any dangling styling,
+//! lingering or from upstream output, so that the style is applied to a clean
+//! slate. It only affects the prefix: whether a suffix is emitted is still
+//! governed by lingering and force resetting. Combined with
+//! [wrapping](#wrapping), resets in the wrapped value are followed by the
+//! style's prefix as usual, without a redundant second reset. When styling is
+//! disabled, no reset is emitted.
+//!
+//! ```rust
+//! use yansi::Paint;
+//!
+//! # yansi::enable();
+//! let text = format!("{} {}", "A".red().linger(), "B".bold().reset_before());
+//! assert_eq!(text, "\x1B[31mA \x1B[0m\x1B[1mB\x1B[0m");
+//! ```
+//!
+//! ## Styling Per Line
+//!
+//! Some terminals, pagers, and log viewers reset styling at line boundaries,
+//! causing the style of a multi-line value to "drop" after its first line.
+//! Others extend a background color to the end of the line. With
+//! [`Quirk::PerLine`] or the equivalent [`per_line()`](Painted::per_line())
+//! constructor, each line is styled separately: the style's suffix is emitted
+//! before and its prefix after every newline in the value. Values without
+//! newlines are unaffected, as is output when styling is disabled.
+//!
+//! ```rust
+//! use yansi::Paint;
+//!
+//! # yansi::enable();
+//! let banner = "Welcome!\nv1.0".bold().on_blue().per_line();
+//! assert_eq!(banner.to_string(),
+//!     "\x1B[1;44mWelcome!\x1B[0m\n\x1B[1;44mv1.0\x1B[0m");
+//! ```
+//!
 //! ## Brightening
 //!
 //! Most pimrary colors are available in regular and _bright_ variants, e.g.,
@@ -308,6 +429,21 @@
 //! The `bright()` quirk can be applied before or after a color is selected
 //! while having the same effect.
 //!
+//! Conversely, the [`Quirk::Dull`] and [`Quirk::OnDull`] quirks, applied via
+//! [`.dull()`](Painted::dull()) and [`.on_dull()`](Painted::on_dull()), select
+//! the regular variant of a bright foreground or background color, overriding
+//! any brightening. This is useful to mute a style that may use bright colors:
+//!
+//! ```rust
+//! use yansi::{Paint, Style, Color::*};
+//!
+//! static THEME: Style = BrightRed.on_bright_black();
+//!
+//! // These are equivalent.
+//! print!("{}", "Muted".paint(THEME).dull().on_dull());
+//! print!("{}", "Muted".red().on_black());
+//! ```
+//!
 //! # Windows
 //!
 //! Styling is supported and enabled automatically on Windows beginning with
@@ -325,19 +461,67 @@
 //! | Feature      | Default? | Also Enables | Notes                            |
 //! |--------------|----------|--------------|----------------------------------|
 //! | `std`        | **Y**    | `alloc`      | Use `std` library.               |
-//! | `alloc`      | **Y**    |              | Use `alloc`. Enables [wrapping]. |
+//! | `alloc`      | **Y**    |              | Use `alloc`. See [`no_std`].     |
 //! | `detect-tty` | N        | `std`        | See [optional conditions].       |
 //! | `detect-env` | N        | `std`        | See [optional conditions].       |
 //! | `hyperlink`  | N        | `std`        | Enables [hyperlinking] support.  |
+//! | `serde`      | N        |              | See [serialization].             |
 //!
 //! With `default-features = false`, this crate is `#[no_std]`.
 //!
-//! Without any features enabled, all functionality except [wrapping] is
-//! available. To recover wrapping _with_ `#[no_std]`, set `default-features =
-//! false` and enable the `alloc` feature, which requires `alloc` support.
+//! Without any features enabled, all functionality, including [wrapping], is
+//! available except for items that return allocated values, such as
+//! [`strip()`] and [`Style::to_css()`]. To recover these _with_ `#[no_std]`,
+//! set `default-features = false` and enable the `alloc` feature, which
+//! requires `alloc` support.
 //!
 //! [optional conditions]: Condition#built-in-conditions
 //! [wrapping]: #wrapping
+//! [`no_std`]: #crate-features
+//! [serialization]: #serialization
+//!
+//! # Serialization
+//!
+//! With the `serde` feature enabled, [`Color`], [`Attribute`], [`Quirk`], and
+//! [`Style`] implement `serde`'s `Serialize` and `Deserialize`. This allows,
+//! for instance, loading styles from a configuration file:
+//!
+//! ```rust
+//! # #[cfg(feature = "serde")] {
+//! use yansi::{Paint, Style, Color};
+//!
+//! let json = r#"{
+//!     "foreground": "bright_red",
+//!     "background": [30, 144, 255],
+//!     "attributes": ["bold", "underline"]
+//! }"#;
+//!
+//! let style: Style = serde_json::from_str(json).unwrap();
+//! assert_eq!(style, Color::BrightRed.on_rgb(30, 144, 255).bold().underline());
+//! println!("{}", "Themed!".paint(style));
+//! # }
+//! ```
+//!
+//! Colors are represented as follows:
+//!
+//!   * [`Color::Primary`] and the base colors as their lowercase, `snake_case`
+//!     names, e.g., `"primary"`, `"red"`, and `"bright_red"`.
+//!   * [`Color::Fixed`] as a number, e.g., `208`.
+//!   * [`Color::Rgb`] as a sequence of three numbers, e.g., `[30, 144, 255]`.
+//!     When deserializing, [hex strings](Color::from_hex()) like `"#1e90ff"`
+//!     are accepted as well.
+//!
+//! Attributes and quirks are represented by the `snake_case` name of the
+//! respective builder method, e.g., `"rapid_blink"` and `"on_bright"`.
+//!
+//! A style's condition and, with the `hyperlink` feature, its link are neither
+//! serialized nor deserialized. Since a style's link counts toward equality, a
+//! linked style does not round-trip: it deserializes without its link.
+//!
+//! Because a color may take any of the forms above, deserializing colors, and
+//! thus styles, requires a self-describing format like JSON, TOML, or YAML.
+//! Formats that rely on the type to describe the data, like `bincode`, can
+//! serialize but not deserialize them.
 
 #![doc(html_logo_url = "https://raw.githubusercontent.com/SergioBenitez/yansi/master/.github/yansi-logo.png")]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -360,14 +544,56 @@ mod paint;
 mod global;
 mod condition;
 mod set;
+mod ansi;
+mod bitops;
+
+#[cfg(feature = "alloc")]
+mod gradient;
+
+#[cfg(feature = "alloc")]
+mod compiled;
+
+#[cfg(feature = "alloc")]
+mod styled_buf;
+
+#[cfg(feature = "serde")]
+mod serde;
 
 #[cfg(feature = "hyperlink")]
 #[cfg_attr(feature = "_nightly", doc(cfg(feature = "hyperlink")))]
 pub mod hyperlink;
 
-pub use paint::{Painted, Paint};
-pub use attr_quirk::{Attribute, Quirk};
-pub use style::Style;
-pub use color::Color;
+pub use paint::{Painted, Paint, Paintable, PaintEach, PaintedIter, PaintFn, OrMask, Repeat, paint_fn};
+pub use paint::{PaintOption, PaintResult, PaintedVariant, Nothing};
+pub use attr_quirk::{Attribute, Capabilities, Quirk, ResetMode};
+pub use style::{Style, StyleDiff, ParseStyleError};
+pub use color::{Color, ColorLevel, ParseColorError, Variant};
+
+#[cfg(all(feature = "detect-env", feature = "detect-tty"))]
+pub use color::detect_color_level;
 pub use condition::Condition;
-pub use global::{enable, whenever, disable, is_enabled};
+pub use global::{enable, whenever, disable, is_enabled, is_enabled_for, Stream};
+pub use global::{enable_guard, disable_guard, whenever_guard, Guard};
+
+#[cfg(feature = "std")]
+pub use global::with_enabled;
+
+#[cfg(feature = "detect-env")]
+pub use global::init;
+
+pub use ansi::visible_width;
+
+#[cfg(feature = "alloc")]
+pub use ansi::{strip, parse_ansi};
+
+#[cfg(feature = "std")]
+pub use ansi::AutoStrip;
+
+#[cfg(feature = "alloc")]
+pub use gradient::{gradient, rainbow};
+
+#[cfg(feature = "alloc")]
+pub use compiled::{CompiledStyle, CompiledPainted};
+
+#[cfg(feature = "alloc")]
+pub use styled_buf::StyledBuf;
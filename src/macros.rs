@@ -17,6 +17,12 @@ macro_rules! set_enum {
                 self.1 |= value.bit_mask();
                 self
             }
+
+            #[must_use]
+            pub const fn remove(mut self, value: $T) -> Self {
+                self.1 &= !value.bit_mask();
+                self
+            }
         }
 
         impl crate::set::SetMember for $T {
@@ -98,7 +104,7 @@ macro_rules! signature {
 
 macro_rules! define_property {
     ([$d:tt] $(#[$attr:meta])* $kind:ident ($A:ty) {
-        $($(#[$pattr:meta])* $prop:ident => $V:path $([$($a:tt)*])?),* $(,)?
+        $($(#[$pattr:meta])* $prop:ident $arrow:tt $V:path $([$($a:tt)*])?),* $(,)?
     }) => {
         macro_rules! $kind {
             ($d ([$d ($qual:tt)*])? $cont:ident ($r:ty) -> $R:ty) => (
@@ -121,19 +127,40 @@ macro_rules! define_property {
     ($($t:tt)*) => { define_property!([$] $($t)*); }
 }
 
-// Check that every variant of a property is covered.
+// Check that every variant of a property is covered. Properties declared with
+// `->` instead of `=>` call a function to compute a value instead of naming a
+// variant: they're skipped.
 macro_rules! check_property_exhaustiveness {
-    ($A:ident $({ $($(#[$pattr:meta])* $p:ident => $V:path $([ $($a:tt)* ])?),* $(,)? })? ) => {
-        const _: () = {$(
+    ($A:ident) => {};
+
+    ($A:ident { $($t:tt)* }) => {
+        check_property_exhaustiveness!(@$A [] $($t)*);
+    };
+
+    (@$A:ident [$($V:path),*]
+        $(#[$pattr:meta])* $p:ident -> $F:path $([ $($a:tt)* ])? $(, $($rest:tt)*)?
+    ) => {
+        check_property_exhaustiveness!(@$A [$($V),*] $($($rest)*)?);
+    };
+
+    (@$A:ident [$($V:path),*]
+        $(#[$pattr:meta])* $p:ident => $W:path $([ $($a:tt)* ])? $(, $($rest:tt)*)?
+    ) => {
+        check_property_exhaustiveness!(@$A [$($V,)* $W] $($($rest)*)?);
+    };
+
+    (@$A:ident [$($V:path),*]) => {
+        const _: () = {
             use crate::*;
+            #[allow(clippy::diverging_sub_expression)]
             fn _check() {
                 #[allow(unreachable_code)]
                 match { let _v: $A = todo!(); _v } {
                     $($V { .. } => { },)*
                 }
             }
-        )?};
-    }
+        };
+    };
 }
 
 macro_rules! define_properties {
@@ -147,12 +174,33 @@ macro_rules! properties {
     ($([$($qual:tt)*])? $cont:ident ($r:ty) -> $R:ty) => (
         fg!($([$($qual)*])? $cont ($r) -> $R);
         bg!($([$($qual)*])? $cont ($r) -> $R);
+        underline_color!($([$($qual)*])? $cont ($r) -> $R);
         attr!($([$($qual)*])? $cont ($r) -> $R);
         quirk!($([$($qual)*])? $cont ($r) -> $R);
         whenever!($([$($qual)*])? $cont ($r) -> $R);
+        reset_mode!($([$($qual)*])? $cont ($r) -> $R);
     )
 }
 
+// Like `$cont`, but skips properties whose names are likely to collide with
+// methods of arbitrary types. Used for the blanket `Paint` implementation, so
+// such properties are only available on `Painted`, `Style`, and `Color`.
+macro_rules! blanket {
+    ($cont:ident [$($q:tt)*] $r:ty, $R:ty, $p:ident, $(#[$pattr:meta])* gray => $($t:tt)*) => {};
+    ($cont:ident [$($q:tt)*] $r:ty, $R:ty, $p:ident, $(#[$pattr:meta])* cube => $($t:tt)*) => {};
+    ($cont:ident [$($q:tt)*] $r:ty, $R:ty, $p:ident, $(#[$pattr:meta])* dull => $($t:tt)*) => {};
+    ($cont:ident [$($q:tt)*] $r:ty, $R:ty, $p:ident, $(#[$pattr:meta])* force => $($t:tt)*) => {};
+    ($cont:ident $($t:tt)*) => { $cont!($($t)*); };
+}
+
+macro_rules! blanket_signature {
+    ($($t:tt)*) => { blanket!(signature $($t)*); };
+}
+
+macro_rules! blanket_constructor {
+    ($($t:tt)*) => { blanket!(constructor $($t)*); };
+}
+
 define_properties! {
     /// Returns a styled value derived from `self` with the foreground set to
     /// `value`.
@@ -185,6 +233,8 @@ define_properties! {
         primary => Color::Primary,
         fixed => Color::Fixed[color: u8],
         rgb => Color::Rgb[r: u8, g: u8, b: u8],
+        gray -> Color::gray[level: u8],
+        cube -> Color::cube[r: u8, g: u8, b: u8],
         black => Color::Black,
         red => Color::Red,
         green => Color::Green,
@@ -234,6 +284,8 @@ define_properties! {
         on_primary => Color::Primary,
         on_fixed => Color::Fixed[color: u8],
         on_rgb => Color::Rgb[r: u8, g: u8, b: u8],
+        on_gray -> Color::gray[level: u8],
+        on_cube -> Color::cube[r: u8, g: u8, b: u8],
         on_black => Color::Black,
         on_red => Color::Red,
         on_green => Color::Green,
@@ -252,6 +304,64 @@ define_properties! {
         on_bright_white => Color::BrightWhite,
     },
 
+    /// Returns a styled value derived from `self` with the underline color
+    /// set to `value`.
+    ///
+    /// The underline color only has a visible effect when an underline
+    /// attribute, such as [`underline()`](Self::underline()) or
+    /// [`curly_underline()`](Self::curly_underline()), is also applied.
+    /// Terminals without support for underline colors will use the
+    /// foreground color instead.
+    ///
+    /// This method should be used rarely. Instead, prefer to use color-specific
+    /// builder methods like [`underline_red()`](Self::underline_red()) and
+    /// [`underline_rgb()`](Self::underline_rgb()), which have the same
+    /// functionality but are pithier.
+    ///
+    /// # Example
+    ///
+    /// Set the underline color to red using `underline_color()`:
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// # let painted = ();
+    /// painted.curly_underline().underline_color(Color::Red);
+    /// ```
+    ///
+    /// Set the underline color to red using
+    /// [`underline_red()`](Self::underline_red()).
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// # let painted = ();
+    /// painted.curly_underline().underline_red();
+    /// ```
+    underline_color(Color) {
+        underline_primary => Color::Primary,
+        underline_fixed => Color::Fixed[color: u8],
+        underline_rgb => Color::Rgb[r: u8, g: u8, b: u8],
+        underline_gray -> Color::gray[level: u8],
+        underline_cube -> Color::cube[r: u8, g: u8, b: u8],
+        underline_black => Color::Black,
+        underline_red => Color::Red,
+        underline_green => Color::Green,
+        underline_yellow => Color::Yellow,
+        underline_blue => Color::Blue,
+        underline_magenta => Color::Magenta,
+        underline_cyan => Color::Cyan,
+        underline_white => Color::White,
+        underline_bright_black => Color::BrightBlack,
+        underline_bright_red => Color::BrightRed,
+        underline_bright_green => Color::BrightGreen,
+        underline_bright_yellow => Color::BrightYellow,
+        underline_bright_blue => Color::BrightBlue,
+        underline_bright_magenta => Color::BrightMagenta,
+        underline_bright_cyan => Color::BrightCyan,
+        underline_bright_white => Color::BrightWhite,
+    },
+
     /// Enables the styling [`Attribute`] `value`.
     ///
     /// This method should be used rarely. Instead, prefer to use
@@ -288,6 +398,11 @@ define_properties! {
         invert => Attribute::Invert,
         conceal => Attribute::Conceal,
         strike => Attribute::Strike,
+        double_underline => Attribute::DoubleUnderline,
+        overline => Attribute::Overline,
+        curly_underline => Attribute::CurlyUnderline,
+        framed => Attribute::Framed,
+        encircled => Attribute::Encircled,
     },
 
     /// Enables the `yansi` [`Quirk`] `value`.
@@ -329,6 +444,11 @@ define_properties! {
         resetting => Quirk::Resetting,
         bright => Quirk::Bright,
         on_bright => Quirk::OnBright,
+        dull => Quirk::Dull,
+        on_dull => Quirk::OnDull,
+        reset_before => Quirk::ResetBefore,
+        per_line => Quirk::PerLine,
+        force => Quirk::Force,
     },
 
     /// Conditionally enable styling based on whether the [`Condition`] `value`
@@ -349,13 +469,40 @@ define_properties! {
     /// # }
     /// ```
     whenever(Condition),
+
+    /// Returns a styled value derived from `self` with styling reset after
+    /// the value as per the [`ResetMode`] `value`. Replaces any previous
+    /// lingering or force resetting.
+    ///
+    /// See [`ResetMode`] and the [crate level docs](crate#lingering) for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// Apply a style that lingers beyond the value:
+    ///
+    /// ```rust
+    /// use yansi::{Paint, ResetMode};
+    ///
+    /// # let painted = ();
+    /// painted.red().reset_mode(ResetMode::Never);
+    /// ```
+    reset_mode(ResetMode),
 }
 
 macro_rules! impl_fmt_trait {
-    ($F:path, $f:literal <$G:ident> $T:ty => $s:ident.$v:ident ($V:ty)) => {
+    ($F:path, <$G:ident> $T:ty => $s:ident.$v:ident ($V:ty)) => {
         impl<$G: $F> $F for $T {
             fn fmt(&$s, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-                $s.fmt_args(&<$V>::fmt, f, format_args!($f, $s.$v))
+                $s.fmt_with(&<$V>::fmt, f)
+            }
+        }
+    };
+
+    ($F:path, <$G:ident, $H:ident> $T:ty => $s:ident.$v:ident ($V:ty, $W:ty)) => {
+        impl<$G: $F, $H: $F> $F for $T {
+            fn fmt(&$s, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                $s.fmt_with(&<$V>::fmt, &<$W>::fmt, f)
             }
         }
     };
@@ -363,14 +510,14 @@ macro_rules! impl_fmt_trait {
 
 macro_rules! impl_fmt_traits {
     ($($t:tt)*) => {
-        impl_fmt_trait!(core::fmt::Display, "{}" $($t)*);
-        impl_fmt_trait!(core::fmt::Debug, "{:?}" $($t)*);
-        impl_fmt_trait!(core::fmt::Octal, "{:o}" $($t)*);
-        impl_fmt_trait!(core::fmt::LowerHex, "{:x}" $($t)*);
-        impl_fmt_trait!(core::fmt::UpperHex, "{:X}" $($t)*);
-        impl_fmt_trait!(core::fmt::Pointer, "{:p}" $($t)*);
-        impl_fmt_trait!(core::fmt::Binary, "{:b}" $($t)*);
-        impl_fmt_trait!(core::fmt::LowerExp, "{:e}" $($t)*);
-        impl_fmt_trait!(core::fmt::UpperExp, "{:E}" $($t)*);
+        impl_fmt_trait!(core::fmt::Display, $($t)*);
+        impl_fmt_trait!(core::fmt::Debug, $($t)*);
+        impl_fmt_trait!(core::fmt::Octal, $($t)*);
+        impl_fmt_trait!(core::fmt::LowerHex, $($t)*);
+        impl_fmt_trait!(core::fmt::UpperHex, $($t)*);
+        impl_fmt_trait!(core::fmt::Pointer, $($t)*);
+        impl_fmt_trait!(core::fmt::Binary, $($t)*);
+        impl_fmt_trait!(core::fmt::LowerExp, $($t)*);
+        impl_fmt_trait!(core::fmt::UpperExp, $($t)*);
     };
 }
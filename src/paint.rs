@@ -1,13 +1,14 @@
 use core::fmt;
 
+use crate::{Color, Attribute, Quirk, Style, Condition, ResetMode};
+use crate::ansi::{Buffer, Measure, Stripper};
+
 #[cfg(all(feature = "alloc", not(feature = "std")))]
-use alloc::{string::{String, ToString}, borrow::Cow};
+use alloc::{borrow::Cow, string::String};
 
 #[cfg(feature = "std")]
 use std::borrow::Cow;
 
-use crate::{Color, Attribute, Quirk, Style, Condition};
-
 /// An arbitrary value with a [`Style`] applied to it.
 ///
 /// A `Painted` can be directly formatted. This results in the internal
@@ -30,7 +31,74 @@ use crate::{Color, Attribute, Quirk, Style, Condition};
 /// let v = format!("{}", "world".red().underline().blink().whenever(Condition::NEVER));
 /// assert_eq!(v, "world");
 /// ```
-#[derive(Copy, Clone)]
+///
+/// When a width is specified, as in `{:>6}`, the value pads itself as it would
+/// unstyled, but the fill characters at either end are written outside of the
+/// styling. Escape sequences written by the value itself don't count toward the
+/// width. This keeps styled values aligned in tables. Values that ignore the
+/// width aren't padded, while zero-padding, as in `{:06}`, is part of the value.
+/// To tell its padding apart from its own spaces, a value that pads itself is
+/// formatted once more without the width.
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// # yansi::enable();
+/// assert_eq!(format!("{:>6}", "hi".red()), "    \u{1b}[31mhi\u{1b}[0m");
+/// assert_eq!(format!("{:<6}|", "hi".red()), "\u{1b}[31mhi\u{1b}[0m    |");
+/// ```
+///
+/// The fill character and alignment are honored as well, which makes for easy
+/// decorative banners:
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// # yansi::enable();
+/// let banner = format!("{:=^13}", " TITLE ".bold());
+/// assert_eq!(banner, "===\u{1b}[1m TITLE \u{1b}[0m===");
+/// ```
+///
+/// # Storing and Sharing
+///
+/// A [`Style`] holds only plain data and `'static` references, so a
+/// `Painted<T>` is `Send`, `Sync`, and `'static` exactly when `T` is. A value
+/// painted via method syntax, like `"hi".red()`, is a `Painted<&T>` that
+/// borrows its receiver. To store a painted value in a long-lived or shared
+/// structure, paint an owned value with [`Paint::into_painted()`] or convert the
+/// borrowed value with [`map()`](Self::map()):
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use yansi::{Paint, Painted};
+///
+/// let name = String::from("world");
+/// let owned: Painted<String> = name.red().map(|v| v.clone());
+/// let shared = Arc::new(owned);
+///
+/// let thread_local = Arc::clone(&shared);
+/// std::thread::spawn(move || println!("Hello, {}!", thread_local)).join().unwrap();
+/// ```
+///
+/// # Equivalence
+///
+/// Two `Painted` values are equal when their values are equal and their styles
+/// are equal. Styles are compared structurally, exactly as by `Style`'s
+/// [`PartialEq`](Style#equivalence-and-ordering) implementation: quirks and
+/// conditions are ignored. Hashing is consistent with
+/// equality. As such, two equal `Painted` values may render differently.
+///
+/// ```rust
+/// use yansi::{Paint, Condition};
+///
+/// assert_eq!("hi".red().bold(), "hi".bold().red());
+/// assert_ne!("hi".red(), "hi".green());
+/// assert_ne!("hi".red(), "bye".red());
+///
+/// // Quirks and conditions are ignored.
+/// assert_eq!("hi".red(), "hi".red().mask().whenever(Condition::NEVER));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Painted<T> {
     /// The value to be styled.
     pub value: T,
@@ -55,7 +123,7 @@ pub struct Painted<T> {
 ///
 /// ### Chaining
 ///
-/// All methods return a [`Painted`] whose methods are exactly those of `Paint`.
+/// All methods return a [`Painted`] which has all of the methods of `Paint`.
 /// This means you can chain `Paint` method calls:
 ///
 /// ```rust
@@ -65,6 +133,38 @@ pub struct Painted<T> {
 /// 1.on_red().blink(); // calls `Paint::red()` + `Painted::blink()`.
 /// ```
 ///
+/// Methods return a new styled value: they neither modify nor print their
+/// receiver. As such, they are marked `#[must_use]`, and the compiler warns
+/// when a result is discarded, say, because it was never printed:
+///
+/// ```rust,compile_fail
+/// #![deny(unused_must_use)]
+/// use yansi::Paint;
+///
+/// "hello".green().bold(); // error: unused `Painted` that must be used
+/// ```
+///
+/// ### `Painted`-Only Methods
+///
+/// A few builders with names likely to collide with methods of other types,
+/// like [`gray()`](Painted::gray()), [`cube()`](Painted::cube()),
+/// [`dull()`](Painted::dull()), [`force()`](Painted::force()),
+/// [`fg_hex()`](Painted::fg_hex()), [`fg_into()`](Painted::fg_into()), and
+/// [`rgb_f32()`](Painted::rgb_f32()), are not provided by `Paint`, only by
+/// [`Painted`] and [`Style`]. To use them, start with [`Paint::new()`] or any
+/// other builder:
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// println!("{}", "shadow".new().gray(5));
+/// println!("{}", "shadow".italic().gray(5).on_gray(20));
+/// println!("{}", "cube".new().cube(0, 2, 5));
+/// println!("{}", "hex".new().fg_hex("#1e90ff").on_gray(3));
+/// println!("{}", "array".new().fg_into([30, 144, 255]));
+/// println!("{}", "float".new().rgb_f32(0.1, 0.6, 1.0));
+/// ```
+///
 /// ### Borrow vs. Owned Receiver
 ///
 /// The returned [`Painted`] type contains a borrow to the receiver:
@@ -75,17 +175,38 @@ pub struct Painted<T> {
 /// let v: Painted<&i32> = 1.red();
 /// ```
 ///
-/// This is nearly always what you want. In the exceedingly rare case that you
-/// _do_ want `Painted` to own its value, use [`Paint::new()`] or the equivalent
-/// [`Painted::new()`]:
+/// This is nearly always what you want. In the rare case that you _do_ want
+/// `Painted` to own its value, say to store it, use [`Paint::into_painted()`] or the
+/// equivalent [`Paint::new()`] or [`Painted::new()`], then chain as usual:
 ///
 /// ```rust
 /// use yansi::{Paint, Painted};
 ///
+/// let v: Painted<i32> = 1.into_painted().red();
 /// let v: Painted<i32> = Paint::new(1);
 /// let v: Painted<i32> = Painted::new(1);
 /// ```
 ///
+/// ### Formatting Arguments
+///
+/// Because [`fmt::Arguments`] is a type like any other, the output of
+/// [`format_args!`] can be styled directly, without an intermediate `String`:
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// let (a, b) = (1, 2);
+/// println!("{}", format_args!("{} + {}", a, b).red());
+///
+/// # yansi::enable();
+/// let string = format!("{}", format_args!("{} + {}", a, b).red().bold());
+/// assert_eq!(string, "\x1b[1;31m1 + 2\x1b[0m");
+/// ```
+///
+/// As `format_args!` borrows temporaries, the result must be used within the
+/// same expression, as above. To style deferred formatting that outlives an
+/// expression, use [`paint_fn()`](crate::paint_fn()).
+///
 /// ### Further Details
 ///
 /// See the [crate level docs](crate#usage) for more details and examples.
@@ -100,11 +221,39 @@ pub trait Paint {
     /// let painted = Paint::new("hello");
     /// assert_eq!(painted.style, yansi::Style::new());
     /// ```
+    #[must_use]
     #[inline(always)]
+    #[allow(clippy::wrong_self_convention)]
     fn new(self) -> Painted<Self> where Self: Sized {
         Painted::new(self)
     }
 
+    /// Create a new [`Painted`] that owns `self` with a default [`Style`].
+    ///
+    /// Equivalent to [`Paint::new()`] but meant to be called with method
+    /// syntax. As the returned `Painted` has all of the chainable methods of
+    /// `Paint`, this is the most convenient way to style an owned value, say,
+    /// to store it in a struct or send it across threads.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Painted};
+    ///
+    /// struct Message {
+    ///     text: Painted<String>,
+    /// }
+    ///
+    /// let name = "Bob";
+    /// let message = Message { text: format!("Hi, {}!", name).into_painted().green().bold() };
+    /// assert_eq!(message.text.value, "Hi, Bob!");
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    fn into_painted(self) -> Painted<Self> where Self: Sized {
+        Painted::new(self)
+    }
+
     #[doc(hidden)]
     #[inline(always)]
     fn apply(&self, a: crate::style::Application) -> Painted<&Self> {
@@ -122,149 +271,1486 @@ pub trait Paint {
     ///
     /// let painted = "hello".paint(DEBUG);
     /// ```
+    #[must_use]
     #[inline(always)]
     fn paint<S: Into<Style>>(&self, style: S) -> Painted<&Self> {
         Painted { value: self, style: style.into() }
     }
 
-    properties!(signature(&Self) -> Painted<&Self>);
+    properties!([#[must_use]] blanket_signature(&Self) -> Painted<&Self>);
 }
 
-#[allow(rustdoc::broken_intra_doc_links)]
+#[allow(rustdoc::broken_intra_doc_links, unused_attributes)]
 impl<T: ?Sized> Paint for T {
-    properties!(constructor(&Self) -> Painted<&Self>);
+    properties!(blanket_constructor(&Self) -> Painted<&Self>);
 }
 
-impl<T> Painted<T> {
-    /// Create a new [`Painted`] with a default [`Style`].
+/// A minimal alternative to [`Paint`] with a single method. Implemented for
+/// all types.
+///
+/// Because [`Paint`] is implemented for every type, importing it brings dozens
+/// of methods, like `red()` and `bold()`, into scope for every value. These can
+/// collide with methods of other traits in scope and clutter autocompletion.
+/// This trait instead provides only [`styled()`](Paintable::styled()), which
+/// is equivalent to [`Paint::paint()`]. The returned [`Painted`] has all of the
+/// chainable builder methods as inherent methods, so they remain available
+/// without importing `Paint`.
+///
+/// To migrate, replace `use yansi::Paint` with `use yansi::Paintable` and apply
+/// styles via `styled()`, chaining further as desired. As the method names
+/// differ, both traits can be in scope at once, as they are with `use
+/// yansi::*`.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paintable, Style, Color::*};
+///
+/// /// A domain trait with a method that collides with `Paint::red()`.
+/// trait Team {
+///     fn red(&self) -> bool;
+/// }
+///
+/// impl Team for str {
+///     fn red(&self) -> bool {
+///         self.starts_with("red")
+///     }
+/// }
+///
+/// static ALERT: Style = Red.bold();
+///
+/// assert!("red team".red());
+/// println!("{}", "red team".styled(ALERT).underline());
+/// println!("{}", "blue team".styled(Blue).italic());
+/// ```
+///
+/// With `Paint` in scope instead, the call to `red()` is ambiguous:
+///
+/// ```rust,compile_fail
+/// use yansi::Paint;
+///
+/// trait Team {
+///     fn red(&self) -> bool;
+/// }
+///
+/// impl Team for str {
+///     fn red(&self) -> bool {
+///         self.starts_with("red")
+///     }
+/// }
+///
+/// let _ = "red team".red(); // error: multiple applicable items in scope
+/// ```
+pub trait Paintable {
+    /// Apply a style wholesale to `self`, exactly as by [`Paint::paint()`].
     ///
     /// # Example
     ///
     /// ```rust
-    /// use yansi::Painted;
+    /// use yansi::{Paintable, Style, Color::*};
     ///
-    /// let painted = Painted::new("hello");
-    /// assert_eq!(painted.style, yansi::Style::new());
+    /// static DEBUG: Style = Black.bold().on_yellow();
+    ///
+    /// let painted = "hello".styled(DEBUG).italic();
+    /// assert_eq!(painted.style, Black.bold().italic().on_yellow());
     /// ```
+    #[must_use]
     #[inline(always)]
-    pub const fn new(value: T) -> Painted<T> {
-        Painted { value, style: Style::new() }
+    fn styled<S: Into<Style>>(&self, style: S) -> Painted<&Self> {
+        Painted { value: self, style: style.into() }
     }
+}
 
+impl<T: ?Sized> Paintable for T { }
+
+/// An extension trait to apply a style to every item of an iterator.
+/// Implemented for all iterators.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{PaintEach, Color::*};
+///
+/// let items = vec!["apple", "banana", "cherry"];
+/// for item in items.iter().paint_each(Red.bold()) {
+///     println!("{}", item);
+/// }
+/// ```
+pub trait PaintEach: Iterator + Sized {
+    /// Returns an iterator that wraps every item of `self` in a [`Painted`]
+    /// with the style `style`.
+    ///
+    /// The returned iterator is lazy: items are only styled as they're
+    /// yielded. As with [`Paint`] methods, items are not cloned or otherwise
+    /// converted: iterating over references yields [`Painted`] references.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{PaintEach, Painted, Style, Color::*};
+    ///
+    /// static ITEM: Style = Green.italic();
+    ///
+    /// let items = vec![1, 2, 3];
+    /// let painted: Vec<Painted<&i32>> = items.iter().paint_each(ITEM).collect();
+    /// assert_eq!(painted[1].value, &2);
+    /// assert_eq!(painted[1].style, ITEM);
+    ///
+    /// let list = items.iter()
+    ///     .paint_each(ITEM)
+    ///     .map(|item| item.to_string())
+    ///     .collect::<Vec<_>>()
+    ///     .join(", ");
+    ///
+    /// println!("{}", list);
+    /// ```
     #[inline(always)]
-    const fn apply(mut self, a: crate::style::Application) -> Self {
-        self.style = self.style.apply(a);
-        self
+    fn paint_each<S: Into<Style>>(self, style: S) -> PaintedIter<Self> {
+        PaintedIter { iter: self, style: style.into() }
     }
+}
+
+impl<I: Iterator> PaintEach for I { }
+
+/// An iterator that paints every item of an underlying iterator.
+///
+/// Returned by [`PaintEach::paint_each()`]. See its documentation for details.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PaintedIter<I> {
+    iter: I,
+    style: Style,
+}
+
+impl<I: Iterator> Iterator for PaintedIter<I> {
+    type Item = Painted<I::Item>;
 
     #[inline]
-    pub(crate) fn enabled(&self) -> bool {
-        crate::is_enabled() && self.style.condition.map_or(true, |c| c())
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|value| Painted { value, style: self.style })
     }
 
-    properties!([pub const] constructor(Self) -> Self);
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
-impl<T> Painted<T> {
-    pub(crate) fn color_fmt_value(
-        &self,
-        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
-        f: &mut fmt::Formatter,
-    ) -> fmt::Result {
-        self.style.fmt_prefix(f)?;
-        fmt(&self.value, f)?;
-        self.style.fmt_suffix(f)
+impl<I: DoubleEndedIterator> DoubleEndedIterator for PaintedIter<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|value| Painted { value, style: self.style })
     }
+}
 
-    #[cfg(feature = "alloc")]
-    pub(crate) fn reset_fmt_args(
-        &self,
-        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
-        f: &mut fmt::Formatter,
-        args: &fmt::Arguments<'_>,
-    ) -> fmt::Result {
-        // A tiny state machine to find escape sequences.
-        enum State { Searching, Open, }
-        let mut state = State::Searching;
-        let escape = |c: char| {
-            match state {
-                State::Searching if c == '\x1B' => { state = State::Open; true }
-                State::Open if c == 'm' => { state = State::Searching; true }
-                State::Searching => false,
-                State::Open => true,
-            }
-        };
+impl<I: ExactSizeIterator> ExactSizeIterator for PaintedIter<I> { }
 
-        // Only replace when the string contains styling.
-        let string = args.as_str()
-            .map(|string| Cow::Borrowed(string))
-            .unwrap_or_else(|| Cow::Owned(args.to_string()));
+impl<I: core::iter::FusedIterator> core::iter::FusedIterator for PaintedIter<I> { }
 
-        if string.contains('\x1B') {
-            f.write_str(&string.replace(escape, ""))
-        } else {
-            fmt(&self.value, f)
-        }
-    }
+/// An extension trait to style the value contained in an [`Option`].
+///
+/// Painting an `Option` via [`Paint`], as in `Some(x).red()`, styles the
+/// `Option` as a whole, so that only its `Debug` output, `Some(..)` included,
+/// can be displayed. This trait instead styles the contained value alone.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{PaintOption, Color::*};
+///
+/// let found: Option<usize> = Some(42);
+/// println!("found: {}", found.paint_some(Green));
+/// ```
+pub trait PaintOption<T> {
+    /// Returns a [`PaintedVariant`] that formats the contained value styled
+    /// with `style` or, if there is no contained value, formats `None`,
+    /// unstyled. Use [`PaintedVariant::otherwise()`] to style `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, PaintOption, Color::*};
+    ///
+    /// # yansi::enable();
+    /// let value: Option<usize> = Some(42);
+    /// assert_eq!(value.paint_some(Green).to_string(), 42.green().to_string());
+    ///
+    /// let value: Option<usize> = None;
+    /// assert_eq!(value.paint_some(Green).to_string(), "None");
+    /// assert_eq!(value.paint_some(Green).otherwise(Red).to_string(), "None".red().to_string());
+    /// ```
+    fn paint_some<S: Into<Style>>(&self, style: S) -> PaintedVariant<&T, Nothing>;
+}
 
-    #[cfg(feature = "alloc")]
-    pub(crate) fn color_wrap_fmt_args(
-        &self,
-        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
-        f: &mut fmt::Formatter,
-        args: &fmt::Arguments<'_>,
-    ) -> fmt::Result {
-        // Only replace when the string contains styling.
-        let string = args.as_str()
-            .map(|string| Cow::Borrowed(string))
-            .unwrap_or_else(|| Cow::Owned(args.to_string()));
+impl<T> PaintOption<T> for Option<T> {
+    #[inline]
+    fn paint_some<S: Into<Style>>(&self, style: S) -> PaintedVariant<&T, Nothing> {
+        let value = match self {
+            Some(value) => Ok(Painted { value, style: style.into() }),
+            None => Err(Painted::new(Nothing)),
+        };
 
-        if !string.contains('\x1B') {
-            return self.color_fmt_value(fmt, f);
-        }
+        PaintedVariant { value }
+    }
+}
+
+/// An extension trait to style the success value contained in a [`Result`].
+///
+/// Like [`PaintOption`], but for `Result`s: the `Ok` value is styled while
+/// the `Err` value is formatted, by default unstyled, in its place.
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{PaintResult, Color::*};
+///
+/// let parsed = "42".parse::<usize>();
+/// println!("parsed: {}", parsed.paint_ok(Green).otherwise(Red.bold()));
+/// ```
+pub trait PaintResult<T, E> {
+    /// Returns a [`PaintedVariant`] that formats the `Ok` value styled with
+    /// `style` or else formats the `Err` value, unstyled. Use
+    /// [`PaintedVariant::otherwise()`] to style the `Err` value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, PaintResult, Color::*};
+    ///
+    /// # yansi::enable();
+    /// let value: Result<usize, &str> = Ok(42);
+    /// assert_eq!(value.paint_ok(Green).to_string(), 42.green().to_string());
+    ///
+    /// let value: Result<usize, &str> = Err("bad");
+    /// assert_eq!(value.paint_ok(Green).to_string(), "bad");
+    /// assert_eq!(value.paint_ok(Green).otherwise(Red).to_string(), "bad".red().to_string());
+    /// ```
+    fn paint_ok<S: Into<Style>>(&self, style: S) -> PaintedVariant<&T, &E>;
+}
 
-        // Compute the prefix for the style with a reset in front.
-        let mut prefix = String::new();
-        prefix.push_str("\x1B[0m");
-        self.style.fmt_prefix(&mut prefix)?;
+impl<T, E> PaintResult<T, E> for Result<T, E> {
+    #[inline]
+    fn paint_ok<S: Into<Style>>(&self, style: S) -> PaintedVariant<&T, &E> {
+        let value = match self {
+            Ok(value) => Ok(Painted { value, style: style.into() }),
+            Err(value) => Err(Painted::new(value)),
+        };
 
-        // Write out formatted string, replacing resets with computed prefix.
-        self.style.fmt_prefix(f)?;
-        write!(f, "{}", string.replace("\x1B[0m", &prefix))?;
-        self.style.fmt_suffix(f)
+        PaintedVariant { value }
     }
+}
 
-    pub(crate) fn fmt_args(
-        &self,
-        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
-        f: &mut fmt::Formatter,
-        _args: fmt::Arguments<'_>,
-    ) -> fmt::Result {
-        let enabled = self.enabled();
-        let masked = self.style.quirks.contains(Quirk::Mask);
+impl<T> Painted<T> {
+    /// Create a new [`Painted`] with a default [`Style`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Painted;
+    ///
+    /// let painted = Painted::new("hello");
+    /// assert_eq!(painted.style, yansi::Style::new());
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub const fn new(value: T) -> Painted<T> {
+        Painted { value, style: Style::new() }
+    }
 
-        #[cfg(not(feature = "alloc"))]
-        match (enabled, masked) {
-            (true, _) => self.color_fmt_value(fmt, f),
-            (false, false) => fmt(&self.value, f),
-            (false, true) => Ok(()),
-        }
+    /// Create a new [`Painted`] with the style `style`.
+    ///
+    /// Unlike [`Paint::paint()`], this is a `const` function, so painted values
+    /// can be constructed in `const` and `static` items directly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Painted, Style, Color::*};
+    ///
+    /// const GREEN: Style = Green.bold();
+    /// const OK: Painted<&str> = Painted::with("OK", GREEN);
+    /// static TOKENS: [Painted<&str>; 2] = [OK, Painted::with("FAIL", Red.bold())];
+    ///
+    /// assert_eq!(OK.value, "OK");
+    /// assert_eq!(OK.style, GREEN);
+    /// println!("[{}] [{}]", TOKENS[0], TOKENS[1]);
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub const fn with(value: T, style: Style) -> Painted<T> {
+        Painted { value, style }
+    }
 
-        #[cfg(feature = "alloc")]
-        match (enabled, masked, self.style.quirks.contains(Quirk::Wrap)) {
-            (true, _, true) => self.color_wrap_fmt_args(fmt, f, &_args),
-            (true, _, false) => self.color_fmt_value(fmt, f),
-            (false, false, true) => self.reset_fmt_args(fmt, f, &_args),
-            (false, false, false) => fmt(&self.value, f),
-            (false, true, _) => Ok(()),
-        }
+    /// Returns `self` with its style replaced wholesale by `style`.
+    ///
+    /// Unlike [`Paint::paint()`], which styles a raw value, this method
+    /// operates on an existing `Painted`, retaining its value. Any previous
+    /// style, including its quirks and condition, is discarded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Style, Color::*};
+    ///
+    /// static THEME: Style = Blue.italic();
+    ///
+    /// let painted = "hello".red().bold().with_style(THEME);
+    /// assert_eq!(painted.style, THEME);
+    /// assert_eq!(painted.value, "hello");
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub const fn with_style(mut self, style: Style) -> Painted<T> {
+        self.style = style;
+        self
     }
-}
 
-impl_fmt_traits!(<T> Painted<T> => self.value (T));
+    /// Returns `self` with its style replaced by the result of calling `f`
+    /// with the current style.
+    ///
+    /// This allows applying arbitrary styling logic inline, in the middle of a
+    /// chain of builder methods.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Style, Color::*};
+    ///
+    /// let warn = true;
+    /// let painted = "careful".bold().tap_style(|s| if warn { s.red() } else { s });
+    /// assert_eq!(painted.style, Red.bold());
+    ///
+    /// let painted = "fine".bold().tap_style(|s| if !warn { s.red() } else { s }).italic();
+    /// assert_eq!(painted.style, Style::new().bold().italic());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn tap_style<F: FnOnce(Style) -> Style>(mut self, f: F) -> Painted<T> {
+        self.style = f(self.style);
+        self
+    }
 
-impl<T> From<Painted<T>> for Style {
-    fn from(painted: Painted<T>) -> Self {
-        painted.style
+    /// Maps the painted value to a new value by applying `f` to it, retaining
+    /// the style.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Painted};
+    ///
+    /// let painted: Painted<String> = "hello".red().bold().map(|v| v.to_uppercase());
+    /// assert_eq!(painted.value, "HELLO");
+    /// assert_eq!(painted.style, "hello".red().bold().style);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Painted<U> {
+        Painted { value: f(self.value), style: self.style }
     }
+
+    /// Returns a `Painted` borrowing the value in `self` with the same style.
+    ///
+    /// This allows rendering, passing along, or further styling an owned
+    /// `Painted` without moving or cloning its value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Painted};
+    ///
+    /// let owned: Painted<String> = Painted::new(String::from("hello")).red().bold();
+    /// let borrowed: Painted<&String> = owned.by_ref();
+    /// assert_eq!(borrowed.value, &owned.value);
+    /// assert_eq!(borrowed.style, owned.style);
+    ///
+    /// println!("{} {}", owned.by_ref(), owned.by_ref().italic());
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub const fn by_ref(&self) -> Painted<&T> {
+        Painted { value: &self.value, style: self.style }
+    }
+
+    /// Returns a value that formats as `self` when painting is enabled and as
+    /// an unstyled `fallback` otherwise.
+    ///
+    /// This generalizes [masking](crate#masking): where a masked value is
+    /// omitted when painting is disabled, globally or by the style's condition,
+    /// `fallback` is emitted in its place.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Condition};
+    ///
+    /// # yansi::enable();
+    /// let art = "🎨".bold().or_mask("(art)");
+    /// assert_eq!(art.to_string(), "\x1B[1m🎨\x1B[0m");
+    ///
+    /// let art = "🎨".bold().whenever(Condition::NEVER).or_mask("(art)");
+    /// assert_eq!(art.to_string(), "(art)");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn or_mask(self, fallback: T) -> OrMask<T> {
+        OrMask { painted: self, fallback }
+    }
+
+    /// Returns `self` with the value repeated `count` times.
+    ///
+    /// The repetitions are written between a single styling prefix and suffix,
+    /// not one per repetition, and nothing is allocated. A width, as in
+    /// `{:>10}`, pads all of the repetitions together to 10 characters, aligned
+    /// to the left by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// println!("{}", "─".red().repeat(80));
+    ///
+    /// # yansi::enable();
+    /// assert_eq!("-".red().repeat(3).to_string(), "\x1B[31m---\x1B[0m");
+    /// assert_eq!(format!("{:>5}", "-".red().repeat(3)), "  \x1B[31m---\x1B[0m");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn repeat(self, count: usize) -> Painted<Repeat<T>> {
+        Painted { value: Repeat { value: self.value, count }, style: self.style }
+    }
+
+    /// Returns a reference to the style.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Color};
+    ///
+    /// let painted = "hello".red();
+    /// assert_eq!(painted.style_ref(), &Color::Red.foreground());
+    /// ```
+    #[inline(always)]
+    pub const fn style_ref(&self) -> &Style {
+        &self.style
+    }
+
+    /// Returns a mutable reference to the style.
+    ///
+    /// This allows modifying the style in place without moving `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Painted};
+    ///
+    /// let mut items: Vec<Painted<&str>> = vec!["a".new(), "b".new(), "c".new()];
+    /// for (i, item) in items.iter_mut().enumerate() {
+    ///     if i % 2 == 0 {
+    ///         *item.style_mut() = item.style.bold();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(items[0].style, yansi::Style::new().bold());
+    /// assert_eq!(items[1].style, yansi::Style::new());
+    /// ```
+    #[inline(always)]
+    pub fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    /// Returns `self` with the foreground set to the color parsed from `hex`
+    /// by [`Color::from_hex()`]. If `hex` fails to parse, `self` is returned
+    /// unchanged.
+    ///
+    /// As parsing can fail at runtime, prefer [`rgb()`](Self::rgb()) when the
+    /// color is known ahead of time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// println!("{}", "dodger blue".new().fg_hex("#1e90ff"));
+    /// println!("{}", "on orange".new().bg_hex("#ffa500").fg_hex("#000"));
+    ///
+    /// assert_eq!("hi".new().fg_hex("#1e90ff").style, "hi".rgb(30, 144, 255).style);
+    /// assert_eq!("hi".new().fg_hex("#zzzzzz").style, yansi::Style::new());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn fg_hex(mut self, hex: &str) -> Self {
+        self.style = self.style.fg_hex(hex);
+        self
+    }
+
+    /// Returns `self` with the background set to the color parsed from `hex`
+    /// by [`Color::from_hex()`]. If `hex` fails to parse, `self` is returned
+    /// unchanged.
+    ///
+    /// See [`fg_hex()`](Self::fg_hex()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// assert_eq!("hi".new().bg_hex("#f0a").style, "hi".on_rgb(255, 0, 170).style);
+    /// assert_eq!("hi".new().bg_hex("").style, yansi::Style::new());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn bg_hex(mut self, hex: &str) -> Self {
+        self.style = self.style.bg_hex(hex);
+        self
+    }
+
+    /// Returns `self` with the foreground set to `color` converted into a
+    /// [`Color`].
+    ///
+    /// This is a variant of [`fg()`](Self::fg()) that accepts any value
+    /// convertible into a `Color`, such as an `[r, g, b]` array or an
+    /// `(r, g, b)` tuple.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// println!("{}", "dodger blue".new().fg_into([30, 144, 255]));
+    ///
+    /// assert_eq!("hi".new().fg_into([30, 144, 255]).style, "hi".rgb(30, 144, 255).style);
+    /// assert_eq!("hi".new().fg_into((1, 2, 3)).style, "hi".rgb(1, 2, 3).style);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn fg_into<C: Into<Color>>(mut self, color: C) -> Self {
+        self.style = self.style.fg_into(color);
+        self
+    }
+
+    /// Returns `self` with the background set to `color` converted into a
+    /// [`Color`].
+    ///
+    /// See [`fg_into()`](Self::fg_into()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// assert_eq!("hi".new().bg_into([255, 0, 170]).style, "hi".on_rgb(255, 0, 170).style);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn bg_into<C: Into<Color>>(mut self, color: C) -> Self {
+        self.style = self.style.bg_into(color);
+        self
+    }
+
+    /// Returns `self` with the foreground set to
+    /// [`Color::rgb_f32(r, g, b)`](Color::rgb_f32()).
+    ///
+    /// This is a variant of [`rgb()`](Self::rgb()) that accepts normalized
+    /// components, clamping those outside of `[0.0, 1.0]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// let t = 0.25;
+    /// println!("{}", "fading".new().rgb_f32(1.0 - t, t, 0.0));
+    ///
+    /// assert_eq!("hi".new().rgb_f32(1.0, 0.0, 1.5).style, "hi".rgb(255, 0, 255).style);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn rgb_f32(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.style = self.style.rgb_f32(r, g, b);
+        self
+    }
+
+    /// Returns `self` with the background set to
+    /// [`Color::rgb_f32(r, g, b)`](Color::rgb_f32()).
+    ///
+    /// See [`rgb_f32()`](Self::rgb_f32()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// assert_eq!("hi".new().on_rgb_f32(-1.0, 0.0, 1.0).style, "hi".on_rgb(0, 0, 255).style);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn on_rgb_f32(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.style = self.style.on_rgb_f32(r, g, b);
+        self
+    }
+
+    #[inline(always)]
+    const fn apply(mut self, a: crate::style::Application) -> Self {
+        self.style = self.style.apply(a);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn enabled(&self) -> bool {
+        self.style.effective()
+    }
+
+    properties!([#[must_use] pub const] constructor(Self) -> Self);
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Display> Painted<T> {
+    /// Writes `self`, as rendered by its `Display` implementation, to `w`.
+    ///
+    /// Styling is applied exactly as when `self` is formatted: conditions and
+    /// [masking](crate#masking) are respected. The rendering is written to `w`
+    /// directly, without first building a `String`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// "error".red().bold().write_to(&mut std::io::stderr()).unwrap();
+    ///
+    /// let mut bytes = Vec::new();
+    /// "error".red().bold().whenever(yansi::Condition::NEVER).write_to(&mut bytes).unwrap();
+    /// assert_eq!(bytes, b"error");
+    /// ```
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "std")))]
+    pub fn write_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        write!(w, "{}", self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Painted<T> {
+    /// Returns the styling prefix, a reference to the value, and the styling
+    /// suffix of `self`, in that order.
+    ///
+    /// The parts are exactly those that formatting `self` would write. When
+    /// painting is enabled, the prefix and suffix are those returned by
+    /// [`Style::prefix()`] and [`Style::suffix()`]. Otherwise, both are empty,
+    /// and if `self` is [masked](crate#masking), the value is `None`. This
+    /// allows placing the value and its styling independently, for instance,
+    /// in a template. Like [`Style::write_prefix()`], this method does not
+    /// consider [`Quirk::Wrap`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Condition};
+    ///
+    /// # yansi::enable();
+    /// let painted = "hi".red().bold();
+    /// let (prefix, value, suffix) = painted.parts();
+    /// assert_eq!(format!("<{}{}{}>", prefix, value.unwrap(), suffix), "<\x1b[1;31mhi\x1b[0m>");
+    ///
+    /// let painted = "hi".red().whenever(Condition::NEVER);
+    /// let (prefix, value, suffix) = painted.parts();
+    /// assert_eq!((&*prefix, value, &*suffix), ("", Some(&"hi"), ""));
+    ///
+    /// let masked = "hi".red().mask().whenever(Condition::NEVER);
+    /// let (prefix, value, suffix) = masked.parts();
+    /// assert_eq!((&*prefix, value, &*suffix), ("", None, ""));
+    /// ```
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn parts(&self) -> (Cow<'static, str>, Option<&T>, Cow<'static, str>) {
+        let masked = self.style.quirks.contains(Quirk::Mask);
+        match self.enabled() {
+            true => (self.style.prefix(), Some(&self.value), self.style.suffix()),
+            false if masked => (Cow::Borrowed(""), None, Cow::Borrowed("")),
+            false => (Cow::Borrowed(""), Some(&self.value), Cow::Borrowed("")),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Display> Painted<T> {
+    /// Renders `self` as HTML.
+    ///
+    /// The value, as rendered by its `Display` implementation, is wrapped in a
+    /// `<span>` whose `style` attribute is the CSS returned by
+    /// [`Style::to_css()`]. If the style has no CSS equivalent, the value is
+    /// not wrapped. With the `hyperlink` feature, a style with a
+    /// [`link`](Style::link) is additionally wrapped in an `<a href>`. Any
+    /// escape sequences written by the value are removed, and the HTML-special
+    /// characters `<`, `>`, `&`, `"`, and `'` in both the value and the link
+    /// target are escaped.
+    ///
+    /// Like [`Style::to_css()`], the style's condition is not considered, nor
+    /// is whether styling is globally enabled: the HTML is rendered the same
+    /// way in all cases.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// let html = "<b> & </b>".red().bold().to_html();
+    /// assert_eq!(html, "<span style=\"color:red;font-weight:bold\">\
+    ///     &lt;b&gt; &amp; &lt;/b&gt;</span>");
+    ///
+    /// assert_eq!("1 < 2".new().to_html(), "1 &lt; 2");
+    /// ```
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn to_html(&self) -> String {
+        use fmt::Write;
+
+        let mut html = String::new();
+
+        #[cfg(feature = "hyperlink")]
+        if let Some(url) = self.style.link {
+            let url = crate::hyperlink::Url(url);
+            let _ = write!(html, "<a href=\"");
+            let _ = write!(HtmlEscaper(&mut html), "{}", url);
+            html.push_str("\">");
+        }
+
+        let css = self.style.to_css();
+        if !css.is_empty() {
+            let _ = write!(html, "<span style=\"{}\">", css);
+        }
+
+        let _ = write!(Stripper::new(&mut HtmlEscaper(&mut html)), "{}", self.value);
+
+        if !css.is_empty() {
+            html.push_str("</span>");
+        }
+
+        #[cfg(feature = "hyperlink")]
+        if self.style.link.is_some() {
+            html.push_str("</a>");
+        }
+
+        html
+    }
+}
+
+/// A writer that forwards to the inner writer with HTML-special characters
+/// escaped.
+#[cfg(feature = "alloc")]
+struct HtmlEscaper<'a>(&'a mut dyn fmt::Write);
+
+#[cfg(feature = "alloc")]
+impl fmt::Write for HtmlEscaper<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' => "&quot;",
+                '\'' => "&#39;",
+                _ => continue,
+            };
+
+            self.0.write_str(&s[start..i])?;
+            self.0.write_str(escaped)?;
+            start = i + c.len_utf8();
+        }
+
+        self.0.write_str(&s[start..])
+    }
+}
+
+/// The options of a formatter, forwarded when a value is formatted to another
+/// writer. The fill, which can't be forwarded, is replaced by `Spec::PAD`.
+#[derive(Clone, Copy)]
+struct Spec {
+    align: Option<fmt::Alignment>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+    lower_hex: bool,
+    upper_hex: bool,
+}
+
+/// Writes `$value` to `$out` with a format spec built from the fields of the
+/// `Spec` `$s`, named after the format string, one field at a time.
+macro_rules! write_spec {
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*]) => {
+        write!($out, concat!("{value:", $($spec,)* "}"), value = $value $($arg)*)
+    };
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*] align $($rest:tt)*) => {
+        // The fill is `Spec::PAD`. Zero-padding takes precedence over alignment.
+        match ($s.align, $s.width.is_some() && !$s.zero) {
+            (Some(fmt::Alignment::Left), true) =>
+                write_spec!($out, $value, $s, [$($spec)* "\u{10FFFE}<"] [$($arg)*] $($rest)*),
+            (Some(fmt::Alignment::Center), true) =>
+                write_spec!($out, $value, $s, [$($spec)* "\u{10FFFE}^"] [$($arg)*] $($rest)*),
+            (Some(fmt::Alignment::Right), true) =>
+                write_spec!($out, $value, $s, [$($spec)* "\u{10FFFE}>"] [$($arg)*] $($rest)*),
+            _ => write_spec!($out, $value, $s, [$($spec)*] [$($arg)*] $($rest)*),
+        }
+    };
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*] plus $($rest:tt)*) => {
+        match $s.plus {
+            true => write_spec!($out, $value, $s, [$($spec)* "+"] [$($arg)*] $($rest)*),
+            false => write_spec!($out, $value, $s, [$($spec)*] [$($arg)*] $($rest)*),
+        }
+    };
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*] alternate $($rest:tt)*) => {
+        match $s.alternate {
+            true => write_spec!($out, $value, $s, [$($spec)* "#"] [$($arg)*] $($rest)*),
+            false => write_spec!($out, $value, $s, [$($spec)*] [$($arg)*] $($rest)*),
+        }
+    };
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*] width $($rest:tt)*) => {
+        match ($s.width, $s.zero) {
+            (Some(w), true) => write_spec!($out, $value, $s, [$($spec)* "0w$"] [$($arg)*, w = w] $($rest)*),
+            (Some(w), false) => write_spec!($out, $value, $s, [$($spec)* "w$"] [$($arg)*, w = w] $($rest)*),
+            (None, _) => write_spec!($out, $value, $s, [$($spec)*] [$($arg)*] $($rest)*),
+        }
+    };
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*] precision $($rest:tt)*) => {
+        match $s.precision {
+            Some(p) => write_spec!($out, $value, $s, [$($spec)* ".p$"] [$($arg)*, p = p] $($rest)*),
+            None => write_spec!($out, $value, $s, [$($spec)*] [$($arg)*] $($rest)*),
+        }
+    };
+    ($out:ident, $value:ident, $s:expr, [$($spec:tt)*] [$($arg:tt)*] hex $($rest:tt)*) => {
+        match ($s.lower_hex, $s.upper_hex) {
+            (true, _) => write_spec!($out, $value, $s, [$($spec)* "x?"] [$($arg)*] $($rest)*),
+            (_, true) => write_spec!($out, $value, $s, [$($spec)* "X?"] [$($arg)*] $($rest)*),
+            _ => write_spec!($out, $value, $s, [$($spec)*] [$($arg)*] $($rest)*),
+        }
+    };
+}
+
+impl Spec {
+    /// The fill a value is asked to pad itself with in place of the fill of
+    /// the formatter: a noncharacter, reserved for internal use.
+    const PAD: char = '\u{10FFFE}';
+
+    fn of(f: &fmt::Formatter) -> Spec {
+        let (lower_hex, upper_hex) = Spec::debug_hex_flags(f);
+        Spec {
+            align: f.align(),
+            plus: f.sign_plus(),
+            alternate: f.alternate(),
+            zero: f.sign_aware_zero_pad(),
+            width: f.width(),
+            precision: f.precision(),
+            lower_hex,
+            upper_hex,
+        }
+    }
+
+    /// The debug hex flags, as in `{:x?}` and `{:X?}`, which are otherwise
+    /// unreadable: bits 4 and 5 of the deprecated `Formatter::flags()`. The
+    /// bits are trusted only if the ones with stable accessors agree with
+    /// them; otherwise, both flags read as unset, and values are formatted
+    /// with a plain `{:?}` instead.
+    fn debug_hex_flags(f: &fmt::Formatter) -> (bool, bool) {
+        #[allow(deprecated)]
+        let flags = f.flags();
+
+        let known = f.sign_plus() as u32
+            | (f.sign_minus() as u32) << 1
+            | (f.alternate() as u32) << 2
+            | (f.sign_aware_zero_pad() as u32) << 3;
+
+        match flags & 0b1111 == known && flags >> 6 == 0 {
+            true => (flags & (1 << 4) != 0, flags & (1 << 5) != 0),
+            false => (false, false),
+        }
+    }
+
+    /// The character the value pads itself with when written by `write()`:
+    /// `Spec::PAD` unless it pads to a width with its default alignment, and
+    /// thus with spaces. With no width, `Spec::PAD` is never written.
+    fn pad(&self) -> char {
+        match (self.width, self.align, self.zero) {
+            (Some(_), None, _) | (Some(_), _, true) => ' ',
+            _ => Spec::PAD,
+        }
+    }
+
+    /// Writes `value` to `out` as if formatted by a formatter with `self`'s
+    /// options, but padded with `self.pad()`.
+    fn write(&self, out: &mut dyn fmt::Write, value: &Forward<'_>) -> fmt::Result {
+        write_spec!(out, value, self, [] [] align plus alternate width precision hex)
+    }
+}
+
+/// A value formatted by a formatting function via `Display` and `Debug` alike.
+struct Forward<'a>(&'a dyn Fn(&mut fmt::Formatter) -> fmt::Result);
+
+impl fmt::Display for Forward<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+impl fmt::Debug for Forward<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// A writer that forwards to `out`, writing the prefix of `style` after every
+/// reset sequence, even one split across writes.
+struct Rewrapper<'a> {
+    out: &'a mut dyn fmt::Write,
+    style: &'a Style,
+    /// The number of bytes of a potential reset withheld from `out`.
+    matched: usize,
+}
+
+impl Rewrapper<'_> {
+    const RESET: &'static str = "\x1B[0m";
+
+    /// Writes any withheld bytes of a partial reset.
+    fn finish(self) -> fmt::Result {
+        self.out.write_str(&Self::RESET[..self.matched])
+    }
+}
+
+impl fmt::Write for Rewrapper<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let reset = Self::RESET.as_bytes();
+        let mut start = 0;
+        for (i, byte) in s.bytes().enumerate() {
+            if self.matched > 0 && byte != reset[self.matched] {
+                self.out.write_str(&Self::RESET[..self.matched])?;
+                self.matched = 0;
+                start = i;
+            }
+
+            if byte == reset[self.matched] {
+                if self.matched == 0 {
+                    self.out.write_str(&s[start..i])?;
+                }
+
+                self.matched += 1;
+                start = i + 1;
+                if self.matched == reset.len() {
+                    // A `ResetBefore` prefix begins with the reset itself.
+                    if !self.style.quirks.contains(Quirk::ResetBefore) {
+                        self.out.write_str(Self::RESET)?;
+                    }
+
+                    self.style.fmt_prefix(self.out)?;
+                    self.matched = 0;
+                }
+            }
+        }
+
+        self.out.write_str(&s[start..])
+    }
+}
+
+/// A writer that forwards to `out`, writing `style`'s suffix before and its
+/// prefix after every newline.
+struct LineStyler<'a> {
+    out: &'a mut dyn fmt::Write,
+    style: &'a Style,
+}
+
+impl fmt::Write for LineStyler<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.style.fmt_suffix(self.out)?;
+                self.out.write_char('\n')?;
+                self.style.fmt_prefix(self.out)?;
+            }
+
+            self.out.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The padding of a value written by `Spec::write()`: the number of `char`s
+/// of padding at its start and end, and whether any other `pad` characters are
+/// padding, as within the items of a collection, and not part of the value.
+#[derive(Default)]
+struct Padding {
+    chars: usize,
+    leading: usize,
+    trailing: usize,
+    refill: bool,
+}
+
+impl Padding {
+    /// Computes the padding of a value from the `padded` measure of it written
+    /// per `spec` and the `unpadded` measure of it written without a width.
+    /// Any padding `pad`s that are also at the start or end of the unpadded
+    /// value, say its own spaces, are part of the value. If the value is all
+    /// `pad`s, the padding is placed per the alignment, by default to the left.
+    fn of(spec: &Spec, padded: &Measure, unpadded: &Measure) -> Padding {
+        let padding = padded.chars.saturating_sub(unpadded.chars);
+        let (leading, trailing) = match unpadded.leading == unpadded.chars {
+            true => match spec.align.unwrap_or(fmt::Alignment::Left) {
+                fmt::Alignment::Left => (0, padding),
+                fmt::Alignment::Right => (padding, 0),
+                fmt::Alignment::Center => (padding / 2, (padding + 1) / 2),
+            },
+            false => {
+                let leading = padded.leading.saturating_sub(unpadded.leading).min(padding);
+                let trailing = padded.trailing.saturating_sub(unpadded.trailing);
+                (leading, trailing.min(padding - leading))
+            }
+        };
+
+        Padding {
+            chars: padded.chars,
+            leading,
+            trailing,
+            // If the value has `Spec::PAD`s of its own, none are refilled:
+            // they can't be told apart from any padding within the value.
+            refill: padded.pad == Spec::PAD && unpadded.pads == 0,
+        }
+    }
+}
+
+/// A writer that forwards to `out` a value written by `Spec::write()` without
+/// its leading and trailing `padding`, writing `fill` in place of any other
+/// `pad` character that `padding` says to refill.
+struct Unpadder<'a> {
+    out: &'a mut dyn fmt::Write,
+    padding: &'a Padding,
+    pad: char,
+    fill: char,
+    /// The number of `char`s written to `self`.
+    index: usize,
+}
+
+impl fmt::Write for Unpadder<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = match self.padding.trailing {
+            0 => usize::MAX,
+            trailing => self.padding.chars.saturating_sub(trailing),
+        };
+
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let index = self.index;
+            self.index += 1;
+
+            let padding = index < self.padding.leading || index >= end;
+            if padding || (c == self.pad && self.padding.refill) {
+                self.out.write_str(&s[start..i])?;
+                start = i + c.len_utf8();
+                if !padding {
+                    self.out.write_char(self.fill)?;
+                }
+            }
+        }
+
+        self.out.write_str(&s[start..])
+    }
+}
+
+impl<T> Painted<T> {
+    pub(crate) fn color_fmt_value(
+        &self,
+        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let quirks = self.style.quirks;
+        if f.width().is_some() || quirks.contains(Quirk::Wrap) || quirks.contains(Quirk::PerLine) {
+            let value = |f: &mut fmt::Formatter| fmt(&self.value, f);
+            return self.write_styled(&Forward(&value), f, true);
+        }
+
+        self.style.fmt_prefix(f)?;
+        fmt(&self.value, f)?;
+        self.style.fmt_suffix(f)
+    }
+
+    /// Writes `value`, formatted per `f`'s options, to `f`. If `enabled`, the
+    /// value is written between the style's prefix and suffix, wrapped and
+    /// styled per line if the style's quirks say so, with any padding outside
+    /// of the styling. Otherwise, escape sequences in the value are stripped.
+    fn write_styled(
+        &self,
+        value: &Forward<'_>,
+        f: &mut fmt::Formatter,
+        enabled: bool,
+    ) -> fmt::Result {
+        let (mut spec, fill) = (Spec::of(f), f.fill());
+
+        // Values count the invisible chars of escape sequences toward the
+        // width. If one falls short, it's asked to pad to a larger width. The
+        // value's padding is then whatever it wrote beyond what it writes
+        // without a width, and the output of the measuring pass is reused if
+        // it fits.
+        let (mut padding, mut buffer, mut buffered) = (Padding::default(), None, None);
+        if let Some(width) = spec.width {
+            let measured = buffer.insert(Buffer::<128>::new(spec.pad()));
+            spec.write(measured, value)?;
+            let (chars, visible) = (measured.measure.chars, measured.measure.width);
+            if visible < width && chars > visible {
+                spec.width = Some(width + (chars - visible));
+                *measured = Buffer::new(spec.pad());
+                spec.write(measured, value)?;
+            }
+
+            if measured.measure.pads > 0 {
+                let mut unpadded = Measure::new(spec.pad());
+                Spec { width: None, ..spec }.write(&mut unpadded, value)?;
+                padding = Padding::of(&spec, &measured.measure, &unpadded);
+            }
+
+            buffered = measured.as_str();
+        }
+
+        let plain = Style::new();
+        let style = if enabled { &self.style } else { &plain };
+        let quirk = |quirk| enabled && self.style.quirks.contains(quirk);
+
+        (0..padding.leading).try_for_each(|_| fmt::Write::write_char(f, fill))?;
+        style.fmt_prefix(f)?;
+
+        let (mut lines, mut stripper, mut rewrapper);
+        let out: &mut dyn fmt::Write = match quirk(Quirk::PerLine) {
+            true => { lines = LineStyler { out: f, style }; &mut lines }
+            false => f,
+        };
+
+        let out: &mut dyn fmt::Write = match enabled {
+            true => out,
+            false => { stripper = Stripper::new(out); &mut stripper }
+        };
+
+        rewrapper = None;
+        let out: &mut dyn fmt::Write = match quirk(Quirk::Wrap) {
+            true => rewrapper.insert(Rewrapper { out, style, matched: 0 }),
+            false => out,
+        };
+
+        let pad = spec.pad();
+        let mut unpadder = Unpadder { out, padding: &padding, pad, fill, index: 0 };
+        match buffered {
+            Some(string) => fmt::Write::write_str(&mut unpadder, string)?,
+            None => spec.write(&mut unpadder, value)?,
+        }
+
+        if let Some(rewrapper) = rewrapper {
+            rewrapper.finish()?;
+        }
+
+        style.fmt_suffix(f)?;
+        (0..padding.trailing).try_for_each(|_| fmt::Write::write_char(f, fill))
+    }
+
+    pub(crate) fn reset_fmt_value(
+        &self,
+        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        // Styling in the value is stripped as it's written.
+        let value = |f: &mut fmt::Formatter| fmt(&self.value, f);
+        self.write_styled(&Forward(&value), f, false)
+    }
+
+    pub(crate) fn fmt_with(
+        &self,
+        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        let enabled = self.enabled();
+        let masked = self.style.quirks.contains(Quirk::Mask);
+
+        match (enabled, masked, self.style.quirks.contains(Quirk::Wrap)) {
+            (true, _, _) => self.color_fmt_value(fmt, f),
+            (false, false, true) => self.reset_fmt_value(fmt, f),
+            (false, false, false) => fmt(&self.value, f),
+            (false, true, _) => Ok(()),
+        }
+    }
+}
+
+impl_fmt_traits!(<T> Painted<T> => self.value (T));
+
+impl<T> From<Painted<T>> for Style {
+    fn from(painted: Painted<T>) -> Self {
+        painted.style
+    }
+}
+
+/// Borrows the [`style`](Painted::style) of a `Painted`.
+///
+/// Together with `Style`'s reflexive implementation, this allows generic code
+/// that only inspects a style to accept either a `Style` or a `Painted`:
+///
+/// ```rust
+/// use yansi::{Paint, Style, Color::*};
+///
+/// fn is_loud(style: impl AsRef<Style>) -> bool {
+///     style.as_ref().foreground == Some(Red)
+/// }
+///
+/// assert!(is_loud(Red.bold()));
+/// assert!(is_loud("hi".red()));
+/// assert!(!is_loud(&"hi".green()));
+/// ```
+///
+/// `Painted` intentionally does not implement `Borrow<Style>`: its equality
+/// and hash consider the value as well as the style, violating the contract
+/// of `Borrow`.
+impl<T> AsRef<Style> for Painted<T> {
+    fn as_ref(&self) -> &Style {
+        &self.style
+    }
+}
+
+/// Appends the rendering of each `Painted`, as by its `Display`
+/// implementation, to the string.
+///
+/// Each value is rendered independently, exactly as by `to_string()`. To
+/// avoid emitting escape sequences between adjacent values that share a
+/// style, use [`StyledBuf`](crate::StyledBuf) instead.
+///
+/// ```rust
+/// use yansi::Paint;
+///
+/// let mut line = String::from("> ");
+/// line.extend(["build".green(), "ok".bold()]);
+/// assert_eq!(line, format!("> {}{}", "build".green(), "ok".bold()));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+impl<T: fmt::Display> Extend<Painted<T>> for String {
+    fn extend<I: IntoIterator<Item = Painted<T>>>(&mut self, iter: I) {
+        use core::fmt::Write;
+
+        // Writing to a `String` never fails.
+        iter.into_iter().for_each(|painted| { let _ = write!(self, "{}", painted); });
+    }
+}
+
+/// Collects the rendering of each `Painted`, as by its `Display`
+/// implementation, into a single string.
+///
+/// See the `Extend` implementation for details.
+///
+/// ```rust
+/// use yansi::{Paint, Painted};
+///
+/// let (branch, dirty) = ("main", 2);
+/// let parts: Vec<Painted<String>> = vec![
+///     Painted::new(branch.to_string()).magenta().bold(),
+///     Painted::new(" ".to_string()),
+///     Painted::new(format!("+{}", dirty)).yellow(),
+/// ];
+///
+/// let status: String = parts.into_iter().collect();
+/// println!("{}", status);
+///
+/// let status: String = ["a".red(), "b".blue()].into_iter().collect();
+/// assert_eq!(status, format!("{}{}", "a".red(), "b".blue()));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+impl<T: fmt::Display> FromIterator<Painted<T>> for String {
+    fn from_iter<I: IntoIterator<Item = Painted<T>>>(iter: I) -> String {
+        let mut string = String::new();
+        string.extend(iter);
+        string
+    }
+}
+
+/// A [`Painted`] with an unstyled fallback for when painting is disabled.
+///
+/// Returned by [`Painted::or_mask()`]. See its documentation for details.
+#[derive(Copy, Clone)]
+pub struct OrMask<T> {
+    /// The value formatted when painting is enabled.
+    pub painted: Painted<T>,
+    /// The value formatted, unstyled, when painting is disabled.
+    pub fallback: T,
+}
+
+impl<T> OrMask<T> {
+    fn fmt_with(
+        &self,
+        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        match self.painted.enabled() {
+            true => self.painted.fmt_with(fmt, f),
+            false => fmt(&self.fallback, f),
+        }
+    }
+}
+
+impl_fmt_traits!(<T> OrMask<T> => self.fallback (T));
+
+/// A value formatted `count` times in succession.
+///
+/// Returned, wrapped in a [`Painted`], by [`Painted::repeat()`]. See its
+/// documentation for details.
+#[derive(Copy, Clone)]
+pub struct Repeat<T> {
+    /// The value to repeat.
+    pub value: T,
+    /// The number of times to format `value`.
+    pub count: usize,
+}
+
+impl<T> Repeat<T> {
+    fn fmt_with(
+        &self,
+        fmt: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        // Zero-padding is part of each repetition; other padding is not.
+        let width = match f.width() {
+            Some(width) if !f.sign_aware_zero_pad() => width,
+            _ => return (0..self.count).try_for_each(|_| fmt(&self.value, f)),
+        };
+
+        let (value, spec) = (|f: &mut fmt::Formatter| fmt(&self.value, f), Spec::of(f));
+        let (value, spec) = (Forward(&value), Spec { width: None, ..spec });
+        let mut measure = Measure::default();
+        spec.write(&mut measure, &value)?;
+
+        let padding = width.saturating_sub(measure.width.saturating_mul(self.count));
+        let (pre, post) = match f.align().unwrap_or(fmt::Alignment::Left) {
+            fmt::Alignment::Left => (0, padding),
+            fmt::Alignment::Right => (padding, 0),
+            fmt::Alignment::Center => (padding / 2, (padding + 1) / 2),
+        };
+
+        let fill = f.fill();
+        (0..pre).try_for_each(|_| fmt::Write::write_char(f, fill))?;
+        (0..self.count).try_for_each(|_| spec.write(f, &value))?;
+        (0..post).try_for_each(|_| fmt::Write::write_char(f, fill))
+    }
+}
+
+impl_fmt_traits!(<T> Repeat<T> => self.value (T));
+
+/// Either a styled contained value or a separately styled alternative.
+///
+/// Returned by [`PaintOption::paint_some()`] and [`PaintResult::paint_ok()`].
+/// See their documentation for details.
+#[derive(Copy, Clone)]
+pub struct PaintedVariant<T, E> {
+    /// The painted contained value or, if there is none, the painted
+    /// alternative.
+    pub value: Result<Painted<T>, Painted<E>>,
+}
+
+impl<T, E> PaintedVariant<T, E> {
+    /// Styles the alternative, that is, `None` or the `Err` value, with
+    /// `style`. The alternative is unstyled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, PaintOption, Color::*};
+    ///
+    /// # yansi::enable();
+    /// let value: Option<&str> = None;
+    /// let painted = value.paint_some(Green).otherwise(Red.dim());
+    /// assert_eq!(painted.to_string(), "None".red().dim().to_string());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn otherwise<S: Into<Style>>(mut self, style: S) -> Self {
+        if let Err(ref mut painted) = self.value {
+            painted.style = style.into();
+        }
+
+        self
+    }
+
+    fn fmt_with(
+        &self,
+        fmt_value: &dyn Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        fmt_other: &dyn Fn(&E, &mut fmt::Formatter) -> fmt::Result,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        match &self.value {
+            Ok(painted) => painted.fmt_with(fmt_value, f),
+            Err(painted) => painted.fmt_with(fmt_other, f),
+        }
+    }
+}
+
+impl_fmt_traits!(<T, E> PaintedVariant<T, E> => self.value (T, E));
+
+/// The alternative formatted in place of an absent [`Option`] value: `None`.
+///
+/// Used by [`PaintOption::paint_some()`]. Formats as `None` via every
+/// formatting trait, honoring width, fill, and alignment.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Nothing;
+
+macro_rules! impl_nothing_fmt {
+    ($($F:ident),*) => {
+        $(impl fmt::$F for Nothing {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.pad("None")
+            }
+        })*
+    };
+}
+
+impl_nothing_fmt!(Display, Debug, Octal, LowerHex, UpperHex, Pointer, Binary, LowerExp, UpperExp);
+
+/// A value that formats itself by calling a closure.
+///
+/// Returned, wrapped in a [`Painted`], by [`paint_fn()`]. See its documentation
+/// for details.
+#[derive(Copy, Clone)]
+pub struct PaintFn<F>(
+    /// The closure called to format the value.
+    pub F
+);
+
+impl<F: Fn(&mut fmt::Formatter) -> fmt::Result> fmt::Display for PaintFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Returns a [`Painted`] that formats itself by calling `f` and is styled with
+/// `style`.
+///
+/// This styles the output of arbitrary formatting code, such as several
+/// `write!()` calls, without first formatting it into an intermediate
+/// `String`. `f` is called each time the returned value is displayed. The
+/// returned value is a regular `Painted`, so it respects the global and local
+/// [`Condition`] as well as every [`Quirk`], and it can be further styled with
+/// builder methods. Formatting flags, such as the width, are passed to `f` via
+/// its [`fmt::Formatter`] argument: `f` is responsible for honoring them, for
+/// instance, with [`Formatter::pad()`](fmt::Formatter::pad()).
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Color::*};
+///
+/// let (name, count) = ("apples", 3);
+/// let styled = yansi::paint_fn(Red.bold(), |f| {
+///     write!(f, "{} ", count)?;
+///     write!(f, "{}", name)
+/// });
+///
+/// # yansi::enable();
+/// assert_eq!(styled.to_string(), format!("{} {}", count, name).red().bold().to_string());
+///
+/// let styled = styled.italic().on_white();
+/// println!("{}", styled);
+/// ```
+pub fn paint_fn<F>(style: impl Into<Style>, f: F) -> Painted<PaintFn<F>>
+    where F: Fn(&mut fmt::Formatter) -> fmt::Result
+{
+    Painted { value: PaintFn(f), style: style.into() }
 }
@@ -0,0 +1,244 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Unexpected, Visitor};
+use ::serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::{Attribute, Color, Quirk, Style};
+use crate::color::COLOR_NAMES as COLORS;
+use crate::attr_quirk::{ATTRIBUTE_NAMES as ATTRIBUTES, QUIRK_NAMES as QUIRKS};
+use crate::set::{Set, SetMember};
+
+const STYLE_FIELDS: &[&str] = &[
+    "foreground", "background", "underline_color", "attributes", "quirks",
+];
+
+fn name_of<T: PartialEq>(names: &[(&'static str, T)], value: &T) -> &'static str {
+    names.iter()
+        .find(|(_, v)| v == value)
+        .map(|(name, _)| *name)
+        .expect("every variant has a name")
+}
+
+fn value_of<T: Copy>(names: &[(&str, T)], name: &str) -> Option<T> {
+    names.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+}
+
+/// Deserializes a `T` from one of the names in `names`.
+struct NameVisitor<T: 'static> {
+    names: &'static [(&'static str, T)],
+    expecting: &'static str,
+}
+
+impl<'de, T: Copy> Visitor<'de> for NameVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        value_of(self.names, v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Color::Fixed(num) => serializer.serialize_u8(num),
+            Color::Rgb(r, g, b) => (r, g, b).serialize(serializer),
+            _ => serializer.serialize_str(name_of(COLORS, self)),
+        }
+    }
+}
+
+/// Deserializes any of the representations of a color, and thus requires a
+/// self-describing format. See [serialization](crate#serialization).
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColorVisitor;
+
+        impl<'de> Visitor<'de> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a color name, hex string, 8-bit number, or [r, g, b] triple")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Color, E> {
+                u8::try_from(v)
+                    .map(Color::Fixed)
+                    .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Color, E> {
+                u8::try_from(v)
+                    .map(Color::Fixed)
+                    .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Color, E> {
+                value_of(COLORS, v)
+                    .or_else(|| Color::from_hex(v))
+                    .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Color, A::Error> {
+                let mut next = |i| seq.next_element::<u8>()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self));
+
+                let (r, g, b) = (next(0)?, next(1)?, next(2)?);
+                match seq.next_element::<de::IgnoredAny>()? {
+                    Some(_) => Err(de::Error::invalid_length(4, &self)),
+                    None => Ok(Color::Rgb(r, g, b)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+impl Serialize for Attribute {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(name_of(ATTRIBUTES, self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Attribute {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(NameVisitor { names: ATTRIBUTES, expecting: "an attribute" })
+    }
+}
+
+impl Serialize for Quirk {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(name_of(QUIRKS, self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Quirk {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(NameVisitor { names: QUIRKS, expecting: "a quirk" })
+    }
+}
+
+impl<T: SetMember + Serialize> Serialize for Set<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: SetMember + Deserialize<'de>> Deserialize<'de> for Set<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SetVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: SetMember + Deserialize<'de>> Visitor<'de> for SetVisitor<T> {
+            type Value = Set<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Set<T>, A::Error> {
+                let mut set = Set::EMPTY;
+                while let Some(value) = seq.next_element::<T>()? {
+                    set.1 |= value.bit_mask();
+                }
+
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(SetVisitor(PhantomData))
+    }
+}
+
+/// Only the colors, attributes, and quirks are serialized. The condition and
+/// link are not, so a linked style does not round-trip.
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Style", STYLE_FIELDS.len())?;
+        match self.foreground {
+            Some(ref color) => state.serialize_field("foreground", color)?,
+            None => state.skip_field("foreground")?,
+        }
+
+        match self.background {
+            Some(ref color) => state.serialize_field("background", color)?,
+            None => state.skip_field("background")?,
+        }
+
+        match self.underline_color {
+            Some(ref color) => state.serialize_field("underline_color", color)?,
+            None => state.skip_field("underline_color")?,
+        }
+
+        state.serialize_field("attributes", &self.attributes)?;
+        state.serialize_field("quirks", &self.quirks)?;
+        state.end()
+    }
+}
+
+/// All fields are optional and default to those of [`Style::new()`]. The
+/// condition and link are always `None`. Like colors, styles require a
+/// self-describing format.
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        enum Field { Foreground, Background, UnderlineColor, Attributes, Quirks }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str("a style field")
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+                        match v {
+                            "foreground" => Ok(Field::Foreground),
+                            "background" => Ok(Field::Background),
+                            "underline_color" => Ok(Field::UnderlineColor),
+                            "attributes" => Ok(Field::Attributes),
+                            "quirks" => Ok(Field::Quirks),
+                            _ => Err(E::unknown_field(v, STYLE_FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct StyleVisitor;
+
+        impl<'de> Visitor<'de> for StyleVisitor {
+            type Value = Style;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a style")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Style, A::Error> {
+                let mut style = Style::new();
+                while let Some(field) = map.next_key::<Field>()? {
+                    match field {
+                        Field::Foreground => style.foreground = map.next_value()?,
+                        Field::Background => style.background = map.next_value()?,
+                        Field::UnderlineColor => style.underline_color = map.next_value()?,
+                        Field::Attributes => style.attributes = map.next_value()?,
+                        Field::Quirks => style.quirks = map.next_value()?,
+                    }
+                }
+
+                Ok(style)
+            }
+        }
+
+        deserializer.deserialize_struct("Style", STYLE_FIELDS, StyleVisitor)
+    }
+}
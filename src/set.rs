@@ -22,6 +22,31 @@ impl<T: SetMember> Set<T> {
     }
 }
 
+impl<T> Set<T> {
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Set(PhantomData, self.1 | other.1)
+    }
+
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Set(PhantomData, self.1 & other.1)
+    }
+
+    #[must_use]
+    pub const fn difference(self, other: Self) -> Self {
+        Set(PhantomData, self.1 & !other.1)
+    }
+
+    pub const fn is_subset(self, other: Self) -> bool {
+        self.1 & !other.1 == 0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.1 == 0
+    }
+}
+
 pub struct Iter<T> {
     index: u8,
     set: Set<T>,
@@ -62,7 +87,7 @@ impl<T> Copy for Set<T> { }
 
 impl<T> Clone for Set<T> {
     fn clone(&self) -> Self {
-        Self(self.0, self.1)
+        *self
     }
 }
 
@@ -76,7 +101,7 @@ impl<T> Eq for Set<T> { }
 
 impl<T> PartialOrd for Set<T> {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-        self.1.partial_cmp(&other.1)
+        Some(self.cmp(other))
     }
 }
 
@@ -91,3 +116,36 @@ impl<T> core::hash::Hash for Set<T> {
         self.1.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Set;
+    use crate::Attribute::{self, *};
+
+    #[test]
+    fn algebra() {
+        let empty = Set::<Attribute>::EMPTY;
+        let a = empty.insert(Bold).insert(Italic).insert(Underline);
+        let b = empty.insert(Italic).insert(Strike);
+
+        assert_eq!(a.union(b), a.insert(Strike));
+        assert_eq!(a.intersection(b), empty.insert(Italic));
+        assert_eq!(a.difference(b), empty.insert(Bold).insert(Underline));
+        assert_eq!(b.difference(a), empty.insert(Strike));
+        assert!(a.intersection(b).is_subset(a));
+        assert!(a.intersection(b).is_subset(b));
+        assert!(!a.is_subset(b));
+        assert!(a.is_subset(a));
+
+        assert_eq!(a.union(empty), a);
+        assert_eq!(a.intersection(empty), empty);
+        assert_eq!(a.difference(empty), a);
+        assert_eq!(empty.difference(a), empty);
+        assert_eq!(a.difference(a), empty);
+        assert!(empty.is_subset(a));
+        assert!(empty.is_subset(empty));
+        assert!(!a.is_subset(empty));
+        assert!(empty.is_empty() && !a.is_empty());
+        assert!(a.intersection(empty.insert(Blink)).is_empty());
+    }
+}
@@ -1,8 +1,9 @@
 use core::fmt::{self, Write};
 
-use crate::color::{Color, Variant};
-use crate::attr_quirk::{Attribute, Quirk};
+use crate::color::{Color, ColorLevel, Variant};
+use crate::attr_quirk::{Attribute, Capabilities, Quirk, ResetMode};
 use crate::condition::Condition;
+use crate::paint::Painted;
 use crate::set::Set;
 
 #[cfg(all(feature = "alloc", not(feature = "std")))]
@@ -15,10 +16,10 @@ use std::borrow::Cow;
 ///
 /// ## Equivalence and Ordering
 ///
-/// Only a style's `foreground`, `background`, and set of `attributes` are
-/// considered when testing for equivalence or producing an ordering via
-/// `PartialEq` or `Eq`, and `PartialOrd` or `Ord`. A style's quirks and
-/// conditions are ignored.
+/// Only a style's `foreground`, `background`, `underline_color`, set of
+/// `attributes`, and, with the `hyperlink` feature, `link` are considered when
+/// testing for equivalence or producing an ordering via `PartialEq` or `Eq`,
+/// and `PartialOrd` or `Ord`. A style's quirks and conditions are ignored.
 #[derive(Default, Debug, Copy, Clone)]
 pub struct Style {
     /// The foreground color. Defaults to `None`.
@@ -39,6 +40,30 @@ pub struct Style {
     /// assert_eq!(Style::new().on_red().background, Some(Color::Red));
     /// ```
     pub background: Option<Color>,
+    /// The underline color. Defaults to `None`.
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// assert_eq!(Style::new().underline_color, None);
+    /// assert_eq!(Style::new().underline_red().underline_color, Some(Color::Red));
+    /// ```
+    pub underline_color: Option<Color>,
+    /// The hyperlink target URL. Defaults to `None`.
+    ///
+    /// The URL is escaped when written as described in the
+    /// [`hyperlink`](crate::hyperlink#escaping) module docs. It is not
+    /// (de)serialized by the `serde` implementations.
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// assert_eq!(Style::new().link, None);
+    /// assert_eq!(Color::Red.link("https://a.b").link, Some("https://a.b"));
+    /// ```
+    #[cfg(feature = "hyperlink")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "hyperlink")))]
+    pub link: Option<&'static str>,
     pub(crate) attributes: Set<Attribute>,
     pub(crate) quirks: Set<Quirk>,
     /// The condition.
@@ -67,15 +92,20 @@ struct AnsiSplicer<'a> {
 pub enum Application {
     fg(Color),
     bg(Color),
+    underline_color(Color),
     attr(Attribute),
     quirk(Quirk),
     whenever(Condition),
+    reset_mode(ResetMode),
 }
 
 impl Style {
     const DEFAULT: Style = Style {
         foreground: None,
         background: None,
+        underline_color: None,
+        #[cfg(feature = "hyperlink")]
+        link: None,
         attributes: Set::EMPTY,
         quirks: Set::EMPTY,
         condition: None,
@@ -103,9 +133,11 @@ impl Style {
         match a {
             Application::fg(color) => self.foreground = Some(color),
             Application::bg(color) => self.background = Some(color),
+            Application::underline_color(color) => self.underline_color = Some(color),
             Application::whenever(cond) => self.condition = Some(cond),
             Application::attr(attr) => self.attributes = self.attributes.insert(attr),
             Application::quirk(quirk) => self.quirks = self.quirks.insert(quirk),
+            Application::reset_mode(mode) => self.quirks = mode.apply(self.quirks),
         }
 
         self
@@ -136,6 +168,140 @@ impl Style {
         self.condition.map_or(true, |c| c())
     }
 
+    /// Conditionally enable styling based on whether both the existing
+    /// condition, if any, _and_ `condition` apply.
+    ///
+    /// Unlike [`whenever()`](Self::whenever()), which replaces any previous
+    /// condition, this method restricts it further by combining it with
+    /// `condition` via [`Condition::try_and()`]. If there is no existing
+    /// condition, this is equivalent to `whenever(condition)`. If combining
+    /// requires a new slot and none is free, `self` is returned unchanged,
+    /// with its existing condition, rather than panicking.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Condition, Color::*};
+    ///
+    /// static BASE: Style = Red.bold().whenever(Condition::ALWAYS);
+    ///
+    /// let style = BASE.and_whenever(Condition::NEVER);
+    /// assert!(!style.enabled());
+    ///
+    /// let style = Style::new().and_whenever(Condition::NEVER);
+    /// assert_eq!(style.condition, Some(Condition::NEVER));
+    ///
+    /// # #[cfg(feature = "detect-tty")] {
+    /// let style = BASE.and_whenever(Condition::STDERR_IS_TTY);
+    /// assert_eq!(style.condition, Some(Condition::STDERR_IS_TTY));
+    /// # }
+    /// ```
+    pub fn and_whenever(mut self, condition: Condition) -> Style {
+        self.condition = match self.condition {
+            Some(existing) => match existing.try_and(condition) {
+                Some(combined) => Some(combined),
+                None => return self,
+            },
+            None => Some(condition),
+        };
+
+        self
+    }
+
+    /// Returns `true` if `self` has no foreground, background, or underline
+    /// color, no attributes, and no hyperlink target, i.e., if
+    /// `self == Style::new()`.
+    ///
+    /// A plain style emits no styling sequences. Like `PartialEq`, quirks and
+    /// the condition don't affect the result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Condition, Color::*};
+    ///
+    /// assert!(Style::new().is_plain());
+    /// assert!(Style::new().linger().whenever(Condition::NEVER).is_plain());
+    ///
+    /// assert!(!Red.foreground().is_plain());
+    /// assert!(!Style::new().bold().is_plain());
+    /// assert!(!Style::new().underline_red().is_plain());
+    /// ```
+    #[inline]
+    pub fn is_plain(&self) -> bool {
+        self == &Style::DEFAULT
+    }
+
+    /// Returns `value` painted with `self`.
+    ///
+    /// This is equivalent to [`Paint::paint()`](crate::Paint::paint()) with
+    /// the receiver and argument swapped, except that `value` is taken by
+    /// value instead of by reference.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Style, Color::*};
+    ///
+    /// static ALERT: Style = Red.bold().on_white();
+    ///
+    /// let painted = ALERT.paint("text");
+    /// assert_eq!(painted.to_string(), "text".paint(ALERT).to_string());
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub const fn paint<T>(&self, value: T) -> Painted<T> {
+        Painted { value, style: *self }
+    }
+
+    /// Returns `true` if styling is both globally enabled, or `self` is
+    /// [forced](Quirk::Force), and enabled for `self`, i.e., if this style
+    /// would be applied.
+    #[inline]
+    pub(crate) fn effective(&self) -> bool {
+        (self.quirks.contains(Quirk::Force) || crate::is_enabled()) && self.enabled()
+    }
+
+    /// Returns an iterator over the attributes enabled in `self`.
+    ///
+    /// Attributes are yielded in the order they are declared in [`Attribute`],
+    /// irrespective of the order they were enabled in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Attribute};
+    ///
+    /// let style = Style::new().underline().bold();
+    /// let mut attributes = style.attributes();
+    /// assert_eq!(attributes.next(), Some(Attribute::Bold));
+    /// assert_eq!(attributes.next(), Some(Attribute::Underline));
+    /// assert_eq!(attributes.next(), None);
+    /// ```
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> {
+        self.attributes.iter()
+    }
+
+    /// Returns an iterator over the quirks enabled in `self`.
+    ///
+    /// Quirks are yielded in the order they are declared in [`Quirk`],
+    /// irrespective of the order they were enabled in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Quirk};
+    ///
+    /// let style = Style::new().linger().mask();
+    /// let mut quirks = style.quirks();
+    /// assert_eq!(quirks.next(), Some(Quirk::Mask));
+    /// assert_eq!(quirks.next(), Some(Quirk::Linger));
+    /// assert_eq!(quirks.next(), None);
+    /// ```
+    pub fn quirks(&self) -> impl Iterator<Item = Quirk> {
+        self.quirks.iter()
+    }
+
     /// Writes the ANSI code prefix for the currently set styles.
     ///
     /// This method is intended to be used inside of [`fmt::Display`] and
@@ -144,7 +310,7 @@ impl Style {
     ///
     /// This method writes the ANSI code prefix irrespective of whether painting
     /// is currently enabled or disabled. To write the prefix only if painting
-    /// is enabled, condition a call to this method on [`is_enabled()`].
+    /// is enabled, use [`write_prefix()`](Self::write_prefix()) instead.
     ///
     /// [`fmt::Display`]: fmt::Display
     /// [`fmt::Debug`]: fmt::Debug
@@ -171,16 +337,35 @@ impl Style {
     /// }
     /// ```
     pub fn fmt_prefix(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        if self.quirks.contains(Quirk::ResetBefore) {
+            f.write_str("\x1B[0m")?;
+        }
+
+        #[cfg(feature = "hyperlink")]
+        if let Some(url) = self.link {
+            write!(f, "\x1B]8;;{}\x1B\\", crate::hyperlink::Url(url))?;
+        }
+
+        self.fmt_sgr(f)
+    }
+
+    /// Returns `true` if `self` has no colors or attributes and thus no SGR
+    /// sequence, irrespective of its hyperlink target.
+    fn has_no_sgr(&self) -> bool {
+        self.foreground.is_none()
+            && self.background.is_none()
+            && self.underline_color.is_none()
+            && self.attributes.is_empty()
+    }
+
+    /// Writes the SGR sequence for the colors and attributes in `self`, or
+    /// nothing if there are none.
+    fn fmt_sgr(&self, f: &mut dyn fmt::Write) -> fmt::Result {
         // Give a sequence-free string when no styles are applied.
-        if self == &Style::DEFAULT {
+        if self.has_no_sgr() {
             return Ok(());
         }
 
-        let brighten = |color: Option<Color>, bright: bool| match (color, bright) {
-            (Some(color), true) => Some(color.to_bright()),
-            _ => color
-        };
-
         let mut f = AnsiSplicer { f, splice: false };
         f.write_str("\x1B[")?;
 
@@ -189,20 +374,112 @@ impl Style {
             attr.fmt(&mut f)?;
         }
 
-        if let Some(color) = brighten(self.background, self.quirks.contains(Quirk::OnBright)) {
+        if let Some(color) = self.quirked(self.background, Quirk::OnBright, Quirk::OnDull) {
             f.splice()?;
             color.fmt(&mut f, Variant::Bg)?;
         }
 
-        if let Some(color) = brighten(self.foreground, self.quirks.contains(Quirk::Bright)) {
+        if let Some(color) = self.quirked(self.foreground, Quirk::Bright, Quirk::Dull) {
             f.splice()?;
             color.fmt(&mut f, Variant::Fg)?;
         }
 
+        if let Some(color) = self.underline_color {
+            f.splice()?;
+            color.fmt(&mut f, Variant::Underline)?;
+        }
+
         // All of the sequences end with an `m`.
         f.write_char('m')
     }
 
+    /// Returns `color` adjusted by the `bright` and `dull` quirks, if set.
+    /// The `dull` quirk takes precedence.
+    fn quirked(&self, color: Option<Color>, bright: Quirk, dull: Quirk) -> Option<Color> {
+        match color {
+            Some(color) if self.quirks.contains(dull) => Some(color.to_normal()),
+            Some(color) if self.quirks.contains(bright) => Some(color.to_bright()),
+            _ => color,
+        }
+    }
+
+    /// Returns a value that displays as the ANSI code sequence prefix for the
+    /// style.
+    ///
+    /// The returned value writes the exact same sequence as
+    /// [`fmt_prefix()`](Self::fmt_prefix()) and [`prefix()`](Self::prefix())
+    /// but neither allocates nor requires `alloc`. Like those methods, the
+    /// sequence is written irrespective of whether painting is enabled. To
+    /// write the prefix only if painting is enabled, use
+    /// [`write_prefix()`](Self::write_prefix()) instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static ALERT: Style = Red.on_black().bold();
+    ///
+    /// print!("{}ALERT!", ALERT.ansi());
+    /// println!("{}", Style::new().resetting().ansi());
+    ///
+    /// assert_eq!(ALERT.ansi().to_string(), "\x1b[1;40;31m");
+    /// assert_eq!(ALERT.ansi().to_string(), ALERT.prefix());
+    /// ```
+    pub fn ansi(&self) -> impl fmt::Display {
+        let style = *self;
+        crate::PaintFn(move |f: &mut fmt::Formatter| style.fmt_prefix(f))
+    }
+
+    /// The maximum length, in bytes, of a style's prefix, excluding any
+    /// hyperlink.
+    ///
+    /// A buffer of this length can hold the prefix of any style without a
+    /// [`link`](Self::link). See [`prefix_into()`](Self::prefix_into()).
+    pub const MAX_PREFIX_LEN: usize = 91;
+
+    /// Writes the ANSI code sequence prefix for the style into `buf`,
+    /// returning the written prefix, or `None` if `buf` is too small.
+    ///
+    /// The written sequence is exactly the one returned by
+    /// [`prefix()`](Self::prefix()), but neither allocation nor `alloc` is
+    /// required. The prefix of a style without a hyperlink always fits in a
+    /// buffer of [`Style::MAX_PREFIX_LEN`] bytes. Like
+    /// [`fmt_prefix()`](Self::fmt_prefix()), the sequence is written
+    /// irrespective of whether painting is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static ALERT: Style = Red.on_black().bold();
+    ///
+    /// let mut buf = [0; Style::MAX_PREFIX_LEN];
+    /// assert_eq!(ALERT.prefix_into(&mut buf), Some("\x1b[1;40;31m"));
+    /// assert_eq!(ALERT.prefix_into(&mut [0; 4]), None);
+    /// ```
+    pub fn prefix_into<'b>(&self, buf: &'b mut [u8]) -> Option<&'b str> {
+        struct SliceWriter<'b> {
+            buf: &'b mut [u8],
+            len: usize,
+        }
+
+        impl fmt::Write for SliceWriter<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let end = self.len + s.len();
+                self.buf.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+
+        let mut writer = SliceWriter { buf, len: 0 };
+        self.fmt_prefix(&mut writer).ok()?;
+        let SliceWriter { buf, len } = writer;
+        core::str::from_utf8(&buf[..len]).ok()
+    }
+
     /// Returns the ANSI code sequence prefix for the style as a string.
     ///
     /// This returns a string with the exact same sequence written by
@@ -215,6 +492,87 @@ impl Style {
         prefix.into()
     }
 
+    /// Returns the SGR sequence for the colors and attributes in `self` as a
+    /// string.
+    ///
+    /// Unlike [`prefix()`](Self::prefix()), which writes the colors as
+    /// adjusted by quirks like [`Quirk::Bright`], this writes the colors as
+    /// they are set. Quirks, the condition, and the hyperlink target are not
+    /// written. A plain style yields an empty string.
+    ///
+    /// Without a hyperlink target, the string can be parsed back into an
+    /// equal `Style` via
+    /// [`parse_ansi()`](crate::parse_ansi()), which makes it suitable for
+    /// storing styles as raw escape strings. The one exception is an underline
+    /// color of [`Color::Fixed`] below `16`, which is written exactly as, and
+    /// thus parsed back as, the equivalent base color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static THEME: Style = Red.on_black().bold().bright();
+    ///
+    /// let ansi = THEME.to_ansi_string();
+    /// assert_eq!(ansi, "\x1b[1;40;31m");
+    ///
+    /// // Parse the sequence applied to some text to get the style back.
+    /// let text = format!("{}_", ansi);
+    /// assert_eq!(yansi::parse_ansi(&text)[0].0, THEME);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn to_ansi_string(&self) -> String {
+        let mut style = *self;
+        style.quirks = Set::EMPTY;
+
+        let mut string = String::new();
+        let _ = style.fmt_sgr(&mut string);
+        string
+    }
+
+    /// Returns a one-line preview of `self`: the text `Sample` styled with
+    /// `self` followed by the unstyled [`Debug`](fmt::Debug) representation of
+    /// `self` as a label.
+    ///
+    /// Like [`prefix()`](Self::prefix()), the sample is styled irrespective of
+    /// whether painting is enabled, so that a theme can be previewed even when
+    /// output isn't to a terminal. The sample is always reset, even if `self`
+    /// lingers, so the label is never styled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static THEME: [(&str, Style); 3] = [
+    ///     ("error", Red.bold()),
+    ///     ("warning", Yellow.foreground()),
+    ///     ("note", Blue.italic().linger()),
+    /// ];
+    ///
+    /// for (name, style) in THEME.iter() {
+    ///     println!("{:>8}: {}", name, style.sample());
+    /// }
+    ///
+    /// let sample = Red.bold().sample();
+    /// assert!(sample.starts_with("\x1b[1;31mSample\x1b[0m Style {"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn sample(&self) -> String {
+        let style = self.reset_mode(ResetMode::Auto);
+
+        // Writing to a `String` never fails.
+        let mut string = String::new();
+        let _ = style.fmt_prefix(&mut string);
+        let _ = string.write_str("Sample");
+        let _ = style.fmt_suffix(&mut string);
+        let _ = write!(string, " {:?}", self);
+        string
+    }
+
     /// Writes the ANSI code sequence suffix for the style.
     ///
     /// This method is intended to be used inside of [`fmt::Display`] and
@@ -223,7 +581,7 @@ impl Style {
     ///
     /// This method writes the ANSI code suffix irrespective of whether painting
     /// is currently enabled or disabled. To write the suffix only if painting
-    /// is enabled, condition a call to this method on [`is_enabled()`].
+    /// is enabled, use [`write_suffix()`](Self::write_suffix()) instead.
     ///
     /// [`fmt::Display`]: fmt::Display
     /// [`fmt::Debug`]: fmt::Debug
@@ -250,141 +608,1095 @@ impl Style {
     /// }
     /// ```
     pub fn fmt_suffix(&self, f: &mut dyn fmt::Write) -> fmt::Result {
-        if !self.quirks.contains(Quirk::Resetting) && !self.quirks.contains(Quirk::Clear) {
-            if self.quirks.contains(Quirk::Linger) || self == &Style::DEFAULT {
-                return Ok(());
-            }
+        f.write_str(self.suffix_str())
+    }
+
+    fn suffix_str(&self) -> &'static str {
+        let reset = self.quirks.contains(Quirk::Resetting)
+            || self.quirks.contains(Quirk::Clear)
+            || (!self.quirks.contains(Quirk::Linger) && !self.has_no_sgr());
+
+        // Lingering links stay open, just like lingering styles.
+        #[cfg(feature = "hyperlink")]
+        if self.link.is_some() && !self.quirks.contains(Quirk::Linger) {
+            return match reset {
+                true => "\x1B[0m\x1B]8;;\x1B\\",
+                false => "\x1B]8;;\x1B\\",
+            };
         }
 
-        f.write_str("\x1B[0m")
+        match reset {
+            true => "\x1B[0m",
+            false => "",
+        }
     }
 
-    /// Returns the ANSI code sequence suffix for the style as a string.
+    /// Writes the shortest ANSI code sequence that changes the styling of a
+    /// terminal from `from` to `self`, assuming `from` was applied last.
     ///
-    /// This returns a string with the exact same sequence written by
-    /// [`fmt_suffix()`](Self::fmt_suffix()). See that method for details.
-    #[cfg(feature = "alloc")]
-    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
-    pub fn suffix(&self) -> Cow<'static, str> {
-        if !self.quirks.contains(Quirk::Resetting) && !self.quirks.contains(Quirk::Clear) {
-            if self.quirks.contains(Quirk::Linger) || self == &Style::DEFAULT {
-                return Cow::from("");
+    /// If `self` only adds attributes to or changes the colors of `from`, only
+    /// the added attributes and changed colors are written. If `self` removes
+    /// an attribute or color, styling is reset before `self`'s
+    /// [prefix](Self::fmt_prefix()) is written. If the two styles render
+    /// identically, nothing is written. The [`Bright`](Quirk::Bright) and
+    /// [`Dull`](Quirk::Dull) quirks are taken into account, while conditions
+    /// and other quirks are not. With the `hyperlink` feature, a changed
+    /// [`link`](Self::link) is opened, replacing any previous link, or closed,
+    /// before any SGR sequence.
+    ///
+    /// Like [`fmt_prefix()`](Self::fmt_prefix()), the sequence is written
+    /// irrespective of whether painting is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// fn transition(from: Style, to: Style) -> String {
+    ///     let mut string = String::new();
+    ///     to.fmt_transition(&from, &mut string).unwrap();
+    ///     string
+    /// }
+    ///
+    /// // Only the newly added bold is written.
+    /// assert_eq!(transition(Red.foreground(), Red.bold()), "\x1B[1m");
+    ///
+    /// // Only the changed foreground is written.
+    /// assert_eq!(transition(Red.on_blue(), Green.on_blue()), "\x1B[32m");
+    ///
+    /// // Removing bold requires a reset.
+    /// assert_eq!(transition(Red.bold(), Red.foreground()), "\x1B[0m\x1B[31m");
+    ///
+    /// // Nothing changes, so nothing is written.
+    /// assert_eq!(transition(Red.bold(), Red.bold()), "");
+    /// ```
+    pub fn fmt_transition(&self, from: &Style, f: &mut dyn fmt::Write) -> fmt::Result {
+        #[cfg(feature = "hyperlink")]
+        if self.link != from.link {
+            match self.link {
+                Some(url) => write!(f, "\x1B]8;;{}\x1B\\", crate::hyperlink::Url(url))?,
+                None => f.write_str("\x1B]8;;\x1B\\")?,
             }
         }
 
-        Cow::from("\x1B[0m")
-    }
-
-    properties!([pub const] constructor(Self) -> Self);
-}
-
-impl AnsiSplicer<'_> {
-    fn splice(&mut self) -> fmt::Result {
-        if self.splice { self.f.write_char(';')?; }
-        self.splice = true;
-        Ok(())
-    }
-}
-
-impl fmt::Write for AnsiSplicer<'_> {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.f.write_str(s)
-    }
-}
-
-impl PartialEq for Style {
-    fn eq(&self, other: &Self) -> bool {
-        let Style {
-            foreground: fg_a,
-            background: bg_a,
-            attributes: attrs_a,
-            quirks: _,
-            condition: _,
-        } = self;
+        let colors = |style: &Style| [
+            style.quirked(style.background, Quirk::OnBright, Quirk::OnDull),
+            style.quirked(style.foreground, Quirk::Bright, Quirk::Dull),
+            style.underline_color,
+        ];
 
-        let Style {
-            foreground: fg_b,
-            background: bg_b,
-            attributes: attrs_b,
-            quirks: _,
-            condition: _,
-        } = other;
+        let (old, new) = (colors(from), colors(self));
+        let removed = !from.attributes.is_subset(self.attributes)
+            || old.iter().zip(new.iter()).any(|(old, new)| old.is_some() && new.is_none());
 
-        fg_a == fg_b && bg_a == bg_b && attrs_a == attrs_b
-    }
-}
+        if removed {
+            f.write_str("\x1B[0m")?;
+            return self.fmt_sgr(f);
+        }
 
-impl Eq for Style { }
+        let added = self.attributes.difference(from.attributes);
+        if added.is_empty() && old == new {
+            return Ok(());
+        }
 
-impl core::hash::Hash for Style {
-    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
-        let Style { foreground, background, attributes, quirks: _, condition: _, } = self;
-        foreground.hash(state);
-        background.hash(state);
-        attributes.hash(state);
-    }
-}
+        let mut f = AnsiSplicer { f, splice: false };
+        f.write_str("\x1B[")?;
 
-impl PartialOrd for Style {
-    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-        let Style {
-            foreground: fg_a,
-            background: bg_a,
-            attributes: attrs_a,
-            quirks: _,
-            condition: _,
-        } = self;
+        for attr in added.iter() {
+            f.splice()?;
+            attr.fmt(&mut f)?;
+        }
 
-        let Style {
-            foreground: fg_b,
-            background: bg_b,
-            attributes: attrs_b,
-            quirks: _,
-            condition: _,
-        } = other;
+        let changed = |i: usize| new[i].filter(|&color| old[i] != Some(color));
+        if let Some(color) = changed(0) {
+            f.splice()?;
+            color.fmt(&mut f, Variant::Bg)?;
+        }
 
-        match fg_a.partial_cmp(&fg_b) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
+        if let Some(color) = changed(1) {
+            f.splice()?;
+            color.fmt(&mut f, Variant::Fg)?;
         }
 
-        match bg_a.partial_cmp(&bg_b) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
+        if let Some(color) = changed(2) {
+            f.splice()?;
+            color.fmt(&mut f, Variant::Underline)?;
         }
 
-        attrs_a.partial_cmp(&attrs_b)
+        f.write_char('m')
     }
-}
-
-impl Ord for Style {
-    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-        let Style {
-            foreground: fg_a,
-            background: bg_a,
-            attributes: attrs_a,
-            quirks: _,
-            condition: _,
-        } = self;
 
-        let Style {
+    /// Writes the ANSI code prefix for the currently set styles if painting is
+    /// enabled and nothing otherwise.
+    ///
+    /// Painting is enabled when both [`is_enabled()`] and
+    /// [`enabled()`](Self::enabled()) return `true`, exactly as for
+    /// [`Painted`]. When painting is enabled, this method writes the same
+    /// prefix as [`fmt_prefix()`](Self::fmt_prefix()), which writes the prefix
+    /// unconditionally. Unlike `Painted`, neither this method nor
+    /// [`write_suffix()`](Self::write_suffix()) considers [`Quirk::Mask`].
+    ///
+    /// This method is intended to be used alongside
+    /// [`write_suffix()`](Self::write_suffix()) inside of [`fmt::Display`] and
+    /// [`fmt::Debug`] implementations.
+    ///
+    /// [`fmt::Display`]: fmt::Display
+    /// [`fmt::Debug`]: fmt::Debug
+    /// [`Painted`]: crate::Painted
+    /// [`is_enabled()`]: crate::is_enabled()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use core::fmt;
+    /// use yansi::{Style, Condition, Color::*};
+    ///
+    /// struct CustomItem {
+    ///     item: u32,
+    ///     style: Style
+    /// }
+    ///
+    /// impl fmt::Display for CustomItem {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         self.style.write_prefix(f)?;
+    ///         write!(f, "number: {}", self.item)?;
+    ///         self.style.write_suffix(f)
+    ///     }
+    /// }
+    ///
+    /// # yansi::enable();
+    /// let item = CustomItem { item: 1, style: Red.whenever(Condition::NEVER) };
+    /// assert_eq!(item.to_string(), "number: 1");
+    ///
+    /// let item = CustomItem { item: 1, style: Red.whenever(Condition::ALWAYS) };
+    /// assert_eq!(item.to_string(), "\x1b[31mnumber: 1\x1b[0m");
+    /// ```
+    pub fn write_prefix(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self.effective() {
+            true => self.fmt_prefix(f),
+            false => Ok(()),
+        }
+    }
+
+    /// Writes the ANSI code sequence suffix for the style if painting is
+    /// enabled and nothing otherwise.
+    ///
+    /// This is to [`fmt_suffix()`](Self::fmt_suffix()) as
+    /// [`write_prefix()`](Self::write_prefix()) is to
+    /// [`fmt_prefix()`](Self::fmt_prefix()). See
+    /// [`write_prefix()`](Self::write_prefix()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Condition, Color::*};
+    ///
+    /// # yansi::enable();
+    /// let mut string = String::new();
+    /// Red.whenever(Condition::NEVER).write_suffix(&mut string).unwrap();
+    /// assert_eq!(string, "");
+    ///
+    /// Red.whenever(Condition::ALWAYS).write_suffix(&mut string).unwrap();
+    /// assert_eq!(string, "\x1b[0m");
+    /// ```
+    pub fn write_suffix(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self.effective() {
+            true => self.fmt_suffix(f),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the ANSI code sequence suffix for the style as a string.
+    ///
+    /// This returns a string with the exact same sequence written by
+    /// [`fmt_suffix()`](Self::fmt_suffix()). See that method for details.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn suffix(&self) -> Cow<'static, str> {
+        Cow::from(self.suffix_str())
+    }
+
+    /// Returns an inline CSS declaration block that approximates the style.
+    ///
+    /// Colors are converted as by [`Color::to_css()`], taking the
+    /// [`Quirk::Bright`], [`Quirk::OnBright`], [`Quirk::Dull`], and
+    /// [`Quirk::OnDull`] quirks into account. The
+    /// foreground and background are swapped if [`Attribute::Invert`] is set.
+    /// Attributes are mapped to the closest CSS property:
+    ///
+    /// | attribute             | CSS                                |
+    /// |-----------------------|------------------------------------|
+    /// | `Bold`                | `font-weight:bold`                 |
+    /// | `Dim`                 | `opacity:50%`                      |
+    /// | `Italic`              | `font-style:italic`                |
+    /// | `Underline`           | `text-decoration:underline`        |
+    /// | `DoubleUnderline`     | `text-decoration:underline double` |
+    /// | `CurlyUnderline`      | `text-decoration:underline wavy`   |
+    /// | `Overline`            | `text-decoration:overline`         |
+    /// | `Blink`, `RapidBlink` | `text-decoration:blink`            |
+    /// | `Strike`              | `text-decoration:line-through`     |
+    /// | `Conceal`             | `visibility:hidden`                |
+    ///
+    /// Multiple `text-decoration` values are combined into one declaration.
+    /// CSS applies a single decoration style to every line, so a `double` or
+    /// `wavy` style applies to all lines when `DoubleUnderline` or
+    /// `CurlyUnderline` is set, with `double` preferred when both are. The
+    /// underline color, if any, is emitted as `text-decoration-color`.
+    /// `Framed` and `Encircled` have no CSS equivalent and are ignored.
+    /// Declarations are separated by `;`. The default style returns an empty
+    /// string. Other quirks and the condition are not considered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// let style = Rgb(30, 144, 255).on_black().bold().underline().strike();
+    /// assert_eq!(style.to_css(), "color:rgb(30, 144, 255);background:black;\
+    ///     font-weight:bold;text-decoration:underline line-through");
+    ///
+    /// let html = format!("<span style=\"{}\">hi</span>", Red.italic().to_css());
+    /// assert_eq!(html, "<span style=\"color:red;font-style:italic\">hi</span>");
+    ///
+    /// assert_eq!(Style::new().to_css(), "");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+    pub fn to_css(&self) -> String {
+        let mut css = String::new();
+        let _ = self.fmt_css(&mut css);
+        css
+    }
+
+    #[cfg(feature = "alloc")]
+    fn fmt_css(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let has = |attr: Attribute| self.attributes.contains(attr);
+        let mut fg = self.quirked(self.foreground, Quirk::Bright, Quirk::Dull);
+        let mut bg = self.quirked(self.background, Quirk::OnBright, Quirk::OnDull);
+        if has(Attribute::Invert) {
+            core::mem::swap(&mut fg, &mut bg);
+        }
+
+        // Declarations, like SGR parameters, are separated by a `;`.
+        let mut f = AnsiSplicer { f, splice: false };
+        if let Some(color) = fg {
+            f.splice()?;
+            f.write_str("color:")?;
+            color.fmt_css(&mut f)?;
+        }
+
+        if let Some(color) = bg {
+            f.splice()?;
+            f.write_str("background:")?;
+            color.fmt_css(&mut f)?;
+        }
+
+        let declarations = [
+            (Attribute::Bold, "font-weight:bold"),
+            (Attribute::Dim, "opacity:50%"),
+            (Attribute::Italic, "font-style:italic"),
+        ];
+
+        for (attr, declaration) in declarations {
+            if has(attr) {
+                f.splice()?;
+                f.write_str(declaration)?;
+            }
+        }
+
+        let decorations = [
+            (has(Attribute::Underline)
+                || has(Attribute::DoubleUnderline)
+                || has(Attribute::CurlyUnderline), "underline"),
+            (has(Attribute::Overline), "overline"),
+            (has(Attribute::Blink) || has(Attribute::RapidBlink), "blink"),
+            (has(Attribute::Strike), "line-through"),
+            (has(Attribute::DoubleUnderline), "double"),
+            (has(Attribute::CurlyUnderline) && !has(Attribute::DoubleUnderline), "wavy"),
+        ];
+
+        let mut decorations = decorations.iter().filter(|(set, _)| *set).map(|(_, v)| v);
+        if let Some(first) = decorations.next() {
+            f.splice()?;
+            write!(f, "text-decoration:{}", first)?;
+            for decoration in decorations {
+                write!(f, " {}", decoration)?;
+            }
+        }
+
+        if let Some(color) = self.underline_color {
+            f.splice()?;
+            f.write_str("text-decoration-color:")?;
+            color.fmt_css(&mut f)?;
+        }
+
+        if has(Attribute::Conceal) {
+            f.splice()?;
+            f.write_str("visibility:hidden")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the differences between `self` and `other`, from the point of
+    /// view of a change from `self` to `other`.
+    ///
+    /// Exactly the properties considered by [equivalence](#equivalence-and-ordering)
+    /// are compared: the foreground, background, and underline colors, and the
+    /// set of attributes. Thus, the returned diff is
+    /// [empty](StyleDiff::is_empty()) if and only if `self == other`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Attribute, Color::*};
+    ///
+    /// let diff = Red.bold().diff(&Green.on_blue().italic());
+    /// assert_eq!(diff.foreground, Some(Some(Green)));
+    /// assert_eq!(diff.background, Some(Some(Blue)));
+    /// assert_eq!(diff.underline_color, None);
+    /// assert!(diff.added_attributes().eq([Attribute::Italic]));
+    /// assert!(diff.removed_attributes().eq([Attribute::Bold]));
+    ///
+    /// let diff = Red.on_blue().diff(&Red.foreground());
+    /// assert_eq!(diff.foreground, None);
+    /// assert_eq!(diff.background, Some(None));
+    ///
+    /// assert!(Red.bold().diff(&Red.bold()).is_empty());
+    /// ```
+    pub fn diff(&self, other: &Style) -> StyleDiff {
+        fn changed(old: Option<Color>, new: Option<Color>) -> Option<Option<Color>> {
+            (old != new).then_some(new)
+        }
+
+        StyleDiff {
+            foreground: changed(self.foreground, other.foreground),
+            background: changed(self.background, other.background),
+            underline_color: changed(self.underline_color, other.underline_color),
+            added: other.attributes.difference(self.attributes),
+            removed: self.attributes.difference(other.attributes),
+        }
+    }
+
+    /// Merges `other` into `self`, with `other` taking precedence.
+    ///
+    /// The foreground, background, underline color, and condition of `other`
+    /// replace those of
+    /// `self` when they are `Some`. Otherwise, the values in `self` are kept.
+    /// The attributes and quirks of the returned style are the union of those
+    /// in `self` and `other`.
+    ///
+    /// `self + other` is equivalent to `self.combine(other)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static BASE: Style = Red.on_black().bold();
+    /// static OVERRIDE: Style = Yellow.italic();
+    ///
+    /// let style = BASE.combine(OVERRIDE);
+    /// assert_eq!(style, Yellow.on_black().bold().italic());
+    /// ```
+    pub const fn combine(mut self, other: Style) -> Style {
+        if other.foreground.is_some() {
+            self.foreground = other.foreground;
+        }
+
+        if other.background.is_some() {
+            self.background = other.background;
+        }
+
+        if other.underline_color.is_some() {
+            self.underline_color = other.underline_color;
+        }
+
+        #[cfg(feature = "hyperlink")]
+        if other.link.is_some() {
+            self.link = other.link;
+        }
+
+        if other.condition.is_some() {
+            self.condition = other.condition;
+        }
+
+        self.attributes = self.attributes.union(other.attributes);
+        self.quirks = self.quirks.union(other.quirks);
+        self
+    }
+
+    /// Returns `self` with the attribute `attr` disabled.
+    ///
+    /// If `attr` is not enabled in `self`, `self` is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Attribute, Color::*};
+    ///
+    /// static ALERT: Style = Red.bold().underline();
+    /// static QUIETER: Style = ALERT.without(Attribute::Underline);
+    ///
+    /// assert_eq!(QUIETER, Red.bold());
+    /// assert_eq!(QUIETER.without(Attribute::Italic), Red.bold());
+    /// ```
+    pub const fn without(mut self, attr: Attribute) -> Style {
+        self.attributes = self.attributes.remove(attr);
+        self
+    }
+
+    /// Returns `self` with only the attributes that are also enabled in
+    /// `other`.
+    ///
+    /// Colors, quirks, and the condition of `self` are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static ERROR: Style = Red.bold().underline();
+    /// static NOTE: Style = Blue.bold().italic();
+    ///
+    /// assert_eq!(ERROR.common_attributes(NOTE), Red.bold());
+    /// assert_eq!(ERROR.common_attributes(Style::new()), Red.foreground());
+    /// ```
+    pub const fn common_attributes(mut self, other: Style) -> Style {
+        self.attributes = self.attributes.intersection(other.attributes);
+        self
+    }
+
+    /// Returns `self` with every attribute enabled in `other` disabled.
+    ///
+    /// Colors, quirks, and the condition of `self` are left unchanged. This is
+    /// [`Style::without()`] for every attribute in `other` at once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static ERROR: Style = Red.bold().underline();
+    /// static NOTE: Style = Blue.bold().italic();
+    ///
+    /// assert_eq!(ERROR.without_attributes(NOTE), Red.underline());
+    /// assert_eq!(ERROR.without_attributes(Style::new()), ERROR);
+    /// ```
+    pub const fn without_attributes(mut self, other: Style) -> Style {
+        self.attributes = self.attributes.difference(other.attributes);
+        self
+    }
+
+    /// Returns `true` if every attribute enabled in `other` is also enabled in
+    /// `self`.
+    ///
+    /// Only attributes are compared; colors, quirks, and conditions are
+    /// ignored. Every style has the attributes of a style with none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// static ERROR: Style = Red.bold().underline();
+    ///
+    /// assert!(ERROR.has_attributes_of(&Blue.bold()));
+    /// assert!(!ERROR.has_attributes_of(&Blue.bold().italic()));
+    /// assert!(ERROR.has_attributes_of(&Style::new()));
+    /// assert!(Style::new().has_attributes_of(&Style::new()));
+    /// ```
+    pub const fn has_attributes_of(&self, other: &Style) -> bool {
+        other.attributes.is_subset(self.attributes)
+    }
+
+    /// Returns `self` with every attribute not in `caps` removed.
+    ///
+    /// Colors, quirks, and the condition are left unchanged. Sanitizing to
+    /// [`Capabilities::COMMON`] avoids emitting sequences for attributes that
+    /// are widely unsupported or that produce garbage in some terminals, like
+    /// `blink` and `conceal`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Paint, Style, Capabilities, Color::*};
+    ///
+    /// static WARNING: Style = Yellow.bold().blink();
+    ///
+    /// let style = WARNING.sanitize(Capabilities::COMMON);
+    /// assert_eq!(style, Yellow.bold());
+    ///
+    /// println!("{}", "careful".paint(style));
+    /// ```
+    pub const fn sanitize(mut self, caps: Capabilities) -> Style {
+        self.attributes = self.attributes.intersection(caps.0);
+        self
+    }
+
+    /// Returns `self` with the foreground set to `color`.
+    ///
+    /// Unlike [`fg()`](Self::fg()), `color` is an `Option`: `None` unsets the
+    /// foreground. This avoids branching when the color is computed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// let accent: Option<yansi::Color> = "blue".parse().ok();
+    /// assert_eq!(Style::new().bold().with_foreground(accent), Blue.bold());
+    ///
+    /// assert_eq!(Red.bold().with_foreground(Some(Green)), Green.bold());
+    /// assert_eq!(Red.bold().with_foreground(None), Style::new().bold());
+    /// ```
+    pub const fn with_foreground(mut self, color: Option<Color>) -> Style {
+        self.foreground = color;
+        self
+    }
+
+    /// Returns `self` with the background set to `color`.
+    ///
+    /// Unlike [`bg()`](Self::bg()), `color` is an `Option`: `None` unsets the
+    /// background. This avoids branching when the color is computed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// assert_eq!(Red.on_blue().with_background(Some(Green)), Red.on_green());
+    /// assert_eq!(Red.on_blue().with_background(None), Red.foreground());
+    /// ```
+    pub const fn with_background(mut self, color: Option<Color>) -> Style {
+        self.background = color;
+        self
+    }
+
+    /// Returns `self` with the underline color set to `color`.
+    ///
+    /// Unlike [`underline_color()`](Self::underline_color()), `color` is an
+    /// `Option`: `None` unsets the underline color. This avoids branching when
+    /// the color is computed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color::*};
+    ///
+    /// let style = Red.underline().underline_blue();
+    /// assert_eq!(style.with_underline_color(Some(Green)), Red.underline().underline_green());
+    /// assert_eq!(style.with_underline_color(None), Red.underline());
+    /// ```
+    pub const fn with_underline_color(mut self, color: Option<Color>) -> Style {
+        self.underline_color = color;
+        self
+    }
+
+    /// Returns `self` with the foreground, background, and underline colors
+    /// rewritten to the richest colors displayable at color level `level`.
+    ///
+    /// At [`ColorLevel::TrueColor`], colors are unchanged. At
+    /// [`ColorLevel::Ansi256`], [`Color::Rgb`] colors are replaced with the
+    /// nearest [`Color::Fixed`] color. At [`ColorLevel::Ansi16`], all colors
+    /// except [`Color::Primary`] are replaced with the nearest base color. At
+    /// [`ColorLevel::None`], colors are removed entirely. Attributes, quirks,
+    /// and the condition are always preserved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color, ColorLevel};
+    ///
+    /// static ACCENT: Style = Color::Rgb(30, 144, 255).on_fixed(236).bold();
+    ///
+    /// let style = ACCENT.downgrade(ColorLevel::Ansi256);
+    /// assert_eq!(style.foreground, Some(Color::Fixed(33)));
+    /// assert_eq!(style.background, Some(Color::Fixed(236)));
+    ///
+    /// let style = ACCENT.downgrade(ColorLevel::Ansi16);
+    /// assert_eq!(style.foreground, Some(Color::BrightBlue));
+    /// assert_eq!(style.background, Some(Color::Black));
+    ///
+    /// let style = ACCENT.downgrade(ColorLevel::None);
+    /// assert_eq!(style, Style::new().bold());
+    /// ```
+    pub const fn downgrade(mut self, level: ColorLevel) -> Style {
+        if let Some(color) = self.foreground {
+            self.foreground = color.downgrade(level);
+        }
+
+        if let Some(color) = self.background {
+            self.background = color.downgrade(level);
+        }
+
+        if let Some(color) = self.underline_color {
+            self.underline_color = color.downgrade(level);
+        }
+
+        self
+    }
+
+    /// Returns `self` with the foreground color set to black or white,
+    /// whichever contrasts most with the background color.
+    ///
+    /// Contrast is measured as the [WCAG contrast ratio] between the
+    /// [luminance](Color::luminance()) of the background and that of pure
+    /// black or white. The foreground is set to [`Color::Black`] or
+    /// [`Color::White`] accordingly. If no background color is set, or the
+    /// background is [`Color::Primary`], whose luminance is unknown, `self` is
+    /// returned unchanged.
+    ///
+    /// [WCAG contrast ratio]: https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// let badge = Style::new().on_rgb(255, 215, 0).bold().readable_fg();
+    /// assert_eq!(badge.foreground, Some(Color::Black));
+    ///
+    /// let badge = Style::new().on_rgb(25, 25, 112).bold().readable_fg();
+    /// assert_eq!(badge.foreground, Some(Color::White));
+    ///
+    /// assert_eq!(Color::Red.bold().readable_fg(), Color::Red.bold());
+    /// ```
+    pub fn readable_fg(mut self) -> Style {
+        match self.background {
+            None | Some(Color::Primary) => {},
+            Some(background) => {
+                // Black contrasts more when (l + 0.05) / 0.05 > 1.05 / (l + 0.05).
+                let l = background.luminance() + 0.05;
+                let black = l * l > 0.05 * 1.05;
+                self.foreground = Some(if black { Color::Black } else { Color::White });
+            }
+        }
+
+        self
+    }
+
+    /// Returns `self` with the foreground set to the color parsed from `hex`
+    /// by [`Color::from_hex()`]. If `hex` fails to parse, `self` is returned
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// assert_eq!(Style::new().fg_hex("#1e90ff"), Color::Rgb(30, 144, 255).foreground());
+    /// assert_eq!(Style::new().bold().fg_hex("nope"), Style::new().bold());
+    /// ```
+    pub fn fg_hex(self, hex: &str) -> Style {
+        match Color::from_hex(hex) {
+            Some(color) => self.fg(color),
+            None => self,
+        }
+    }
+
+    /// Returns `self` with the background set to the color parsed from `hex`
+    /// by [`Color::from_hex()`]. If `hex` fails to parse, `self` is returned
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// assert_eq!(Style::new().bg_hex("#f0a"), Style::new().bg(Color::Rgb(255, 0, 170)));
+    /// assert_eq!(Style::new().bold().bg_hex("nope"), Style::new().bold());
+    /// ```
+    pub fn bg_hex(self, hex: &str) -> Style {
+        match Color::from_hex(hex) {
+            Some(color) => self.bg(color),
+            None => self,
+        }
+    }
+
+    /// Returns `self` with the foreground set to `color` converted into a
+    /// [`Color`].
+    ///
+    /// This is a non-`const` variant of [`fg()`](Self::fg()) that accepts any
+    /// value convertible into a `Color`, such as an `[r, g, b]` array or an
+    /// `(r, g, b)` tuple.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// assert_eq!(Style::new().fg_into([30, 144, 255]), Color::Rgb(30, 144, 255).foreground());
+    /// assert_eq!(Style::new().fg_into((1, 2, 3)), Style::new().rgb(1, 2, 3));
+    /// assert_eq!(Style::new().fg_into(Color::Red), Style::new().red());
+    /// ```
+    pub fn fg_into<C: Into<Color>>(self, color: C) -> Style {
+        self.fg(color.into())
+    }
+
+    /// Returns `self` with the background set to `color` converted into a
+    /// [`Color`].
+    ///
+    /// This is a non-`const` variant of [`bg()`](Self::bg()). See
+    /// [`fg_into()`](Self::fg_into()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{Style, Color};
+    ///
+    /// assert_eq!(Style::new().bg_into([255, 0, 170]), Style::new().on_rgb(255, 0, 170));
+    /// assert_eq!(Style::new().bg_into(Color::Red), Style::new().on_red());
+    /// ```
+    pub fn bg_into<C: Into<Color>>(self, color: C) -> Style {
+        self.bg(color.into())
+    }
+
+    /// Returns `self` with the foreground set to
+    /// [`Color::rgb_f32(r, g, b)`](Color::rgb_f32()).
+    ///
+    /// This is a non-`const` variant of [`rgb()`](Self::rgb()) that accepts
+    /// normalized components, clamping those out of gamut.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Style;
+    ///
+    /// assert_eq!(Style::new().rgb_f32(1.0, 0.0, 2.0), Style::new().rgb(255, 0, 255));
+    /// ```
+    pub fn rgb_f32(self, r: f32, g: f32, b: f32) -> Style {
+        self.fg(Color::rgb_f32(r, g, b))
+    }
+
+    /// Returns `self` with the background set to
+    /// [`Color::rgb_f32(r, g, b)`](Color::rgb_f32()).
+    ///
+    /// This is a non-`const` variant of [`on_rgb()`](Self::on_rgb()). See
+    /// [`rgb_f32()`](Self::rgb_f32()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Style;
+    ///
+    /// assert_eq!(Style::new().on_rgb_f32(0.0, -1.0, 1.0), Style::new().on_rgb(0, 0, 255));
+    /// ```
+    pub fn on_rgb_f32(self, r: f32, g: f32, b: f32) -> Style {
+        self.bg(Color::rgb_f32(r, g, b))
+    }
+
+    properties!([#[must_use] pub const] constructor(Self) -> Self);
+}
+
+impl AnsiSplicer<'_> {
+    fn splice(&mut self) -> fmt::Result {
+        if self.splice { self.f.write_char(';')?; }
+        self.splice = true;
+        Ok(())
+    }
+}
+
+impl fmt::Write for AnsiSplicer<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.f.write_str(s)
+    }
+}
+
+impl PartialEq for Style {
+    fn eq(&self, other: &Self) -> bool {
+        let Style {
+            foreground: fg_a,
+            background: bg_a,
+            underline_color: ul_a,
+            #[cfg(feature = "hyperlink")]
+            link: link_a,
+            attributes: attrs_a,
+            quirks: _,
+            condition: _,
+        } = self;
+
+        let Style {
             foreground: fg_b,
             background: bg_b,
+            underline_color: ul_b,
+            #[cfg(feature = "hyperlink")]
+            link: link_b,
             attributes: attrs_b,
             quirks: _,
             condition: _,
         } = other;
 
-        match fg_a.cmp(&fg_b) {
+        #[cfg(feature = "hyperlink")]
+        if link_a != link_b {
+            return false;
+        }
+
+        fg_a == fg_b && bg_a == bg_b && ul_a == ul_b && attrs_a == attrs_b
+    }
+}
+
+impl Eq for Style { }
+
+impl AsRef<Style> for Style {
+    fn as_ref(&self) -> &Style {
+        self
+    }
+}
+
+impl core::hash::Hash for Style {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        let Style {
+            foreground,
+            background,
+            underline_color,
+            #[cfg(feature = "hyperlink")]
+            link,
+            attributes,
+            quirks: _,
+            condition: _,
+        } = self;
+
+        foreground.hash(state);
+        background.hash(state);
+        underline_color.hash(state);
+        attributes.hash(state);
+
+        #[cfg(feature = "hyperlink")]
+        link.hash(state);
+    }
+}
+
+impl PartialOrd for Style {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Style {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let Style {
+            foreground: fg_a,
+            background: bg_a,
+            underline_color: ul_a,
+            #[cfg(feature = "hyperlink")]
+            link: link_a,
+            attributes: attrs_a,
+            quirks: _,
+            condition: _,
+        } = self;
+
+        let Style {
+            foreground: fg_b,
+            background: bg_b,
+            underline_color: ul_b,
+            #[cfg(feature = "hyperlink")]
+            link: link_b,
+            attributes: attrs_b,
+            quirks: _,
+            condition: _,
+        } = other;
+
+        match fg_a.cmp(fg_b) {
+            core::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        match bg_a.cmp(bg_b) {
+            core::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        match attrs_a.cmp(attrs_b) {
+            core::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        match ul_a.cmp(ul_b) {
             core::cmp::Ordering::Equal => {}
             ord => return ord,
         }
 
-        match bg_a.cmp(&bg_b) {
+        #[cfg(feature = "hyperlink")]
+        match link_a.cmp(link_b) {
             core::cmp::Ordering::Equal => {}
             ord => return ord,
         }
 
-        attrs_a.cmp(&attrs_b)
+        core::cmp::Ordering::Equal
+    }
+}
+
+/// The differences between two styles, as returned by [`Style::diff()`].
+///
+/// A color field is `None` when the color is unchanged and `Some(new)` when it
+/// changed, where `new` is the color after the change: `Some(None)` indicates
+/// that a color was unset.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StyleDiff {
+    /// The new foreground color, if it changed.
+    pub foreground: Option<Option<Color>>,
+    /// The new background color, if it changed.
+    pub background: Option<Option<Color>>,
+    /// The new underline color, if it changed.
+    pub underline_color: Option<Option<Color>>,
+    added: Set<Attribute>,
+    removed: Set<Attribute>,
+}
+
+impl StyleDiff {
+    /// Returns an iterator over the attributes enabled by the change.
+    ///
+    /// Attributes are yielded in the order they are declared in [`Attribute`].
+    pub fn added_attributes(&self) -> impl Iterator<Item = Attribute> {
+        self.added.iter()
+    }
+
+    /// Returns an iterator over the attributes disabled by the change.
+    ///
+    /// Attributes are yielded in the order they are declared in [`Attribute`].
+    pub fn removed_attributes(&self) -> impl Iterator<Item = Attribute> {
+        self.removed.iter()
+    }
+
+    /// Returns `true` if nothing changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Color::*;
+    ///
+    /// assert!(Red.bold().diff(&Red.bold().mask()).is_empty());
+    /// assert!(!Red.bold().diff(&Red.italic()).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.foreground.is_none()
+            && self.background.is_none()
+            && self.underline_color.is_none()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+/// The error returned when parsing a [`Style`] from a string fails.
+///
+/// See [`Style`'s `FromStr` implementation](Style#impl-FromStr-for-Style) for
+/// the accepted tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseStyleError {
+    position: usize,
+}
+
+impl ParseStyleError {
+    /// Returns the byte offset of the invalid token in the parsed string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::Style;
+    ///
+    /// let input = "bold fancy red";
+    /// let error = input.parse::<Style>().unwrap_err();
+    /// assert_eq!(error.position(), 5);
+    /// assert!(input[error.position()..].starts_with("fancy"));
+    /// ```
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid style token at byte {}", self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseStyleError { }
+
+/// Parses a style from a whitespace-separated list of tokens.
+///
+/// Each token is applied, in order, as if by calling the builder method of
+/// the same name. The following tokens are accepted, ignoring ASCII case:
+///
+///   * Attribute names: `bold`, `dim`, `italic`, `underline`, `blink`,
+///     `rapid_blink`, `invert`, `conceal`, `strike`, `double_underline`,
+///     `overline`, `curly_underline`, `framed`, and `encircled`.
+///   * Quirk names: `mask`, `wrap`, `linger`, `resetting`, `bright`,
+///     `on_bright`, `dull`, `on_dull`, `reset_before`, `per_line`, and
+///     `force`.
+///   * A color, setting the foreground, or a color prefixed with `on_`,
+///     setting the background. Colors are written as `fixed:n`, `rgb:r,g,b`,
+///     or in any form accepted by [`Color`'s `FromStr`
+///     implementation](Color#impl-FromStr-for-Color) that doesn't contain
+///     whitespace: `red`, `bright_blue`, `#1e90ff`, `fixed(202)`.
+///
+/// An empty string parses as [`Style::new()`].
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{Paint, Style, Color::*};
+///
+/// let style: Style = "bold underline red on_blue".parse().unwrap();
+/// assert_eq!(style, Red.on_blue().bold().underline());
+///
+/// let style: Style = "fixed:100 on_rgb:70,130,180 italic".parse().unwrap();
+/// assert_eq!(style, Fixed(100).on_rgb(70, 130, 180).italic());
+///
+/// println!("{}", "hi".paint("bold red".parse::<Style>().unwrap()));
+/// assert!("bold fancy".parse::<Style>().is_err());
+/// ```
+impl core::str::FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use crate::attr_quirk::{ATTRIBUTE_NAMES, QUIRK_NAMES};
+
+        fn find<T: Copy>(names: &[(&str, T)], token: &str) -> Option<T> {
+            names.iter().find(|(name, _)| name.eq_ignore_ascii_case(token)).map(|(_, v)| *v)
+        }
+
+        fn strip_prefix<'a>(token: &'a str, prefix: &str) -> Option<&'a str> {
+            let head = token.get(..prefix.len()).filter(|h| h.eq_ignore_ascii_case(prefix))?;
+            Some(&token[head.len()..])
+        }
+
+        fn color(token: &str) -> Option<Color> {
+            let byte = |n: &str| n.parse::<u8>().ok();
+            if let Some(n) = strip_prefix(token, "fixed:") {
+                return byte(n).map(Color::Fixed);
+            }
+
+            if let Some(rgb) = strip_prefix(token, "rgb:") {
+                let mut components = rgb.split(',').map(byte);
+                return match (components.next(), components.next(), components.next()) {
+                    (Some(Some(r)), Some(Some(g)), Some(Some(b))) if components.next().is_none() => {
+                        Some(Color::Rgb(r, g, b))
+                    }
+                    _ => None,
+                };
+            }
+
+            token.parse().ok()
+        }
+
+        let mut style = Style::new();
+        for token in s.split_whitespace() {
+            style = if let Some(attr) = find(ATTRIBUTE_NAMES, token) {
+                style.attr(attr)
+            } else if let Some(quirk) = find(QUIRK_NAMES, token) {
+                style.quirk(quirk)
+            } else if let Some(bg) = strip_prefix(token, "on_").and_then(color) {
+                style.bg(bg)
+            } else if let Some(fg) = color(token) {
+                style.fg(fg)
+            } else {
+                let position = token.as_ptr() as usize - s.as_ptr() as usize;
+                return Err(ParseStyleError { position });
+            };
+        }
+
+        Ok(style)
     }
 }
@@ -0,0 +1,108 @@
+use core::fmt::{self, Write};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+
+use crate::{Quirk, Style};
+
+/// A buffer of styled values that emits escape sequences only when the style
+/// changes.
+///
+/// Painting each of many values independently, as with [`Painted`], prefixes
+/// and suffixes every value with escape sequences, even when adjacent values
+/// share a style. A `StyledBuf` instead tracks the active style. Pushing a value
+/// writes only the [transition](Style::fmt_transition()) from the active style
+/// to the value's style, which is nothing at all when the two render the same.
+/// A single reset is written by [`finish()`](Self::finish()).
+///
+/// Like `Painted`, a style only applies if painting is enabled globally and for
+/// the style when the value is pushed. Otherwise, the value is pushed without
+/// styling or, if the style is [masked](crate#masking), not at all. Of the
+/// quirks, only masking, [forcing](crate#forcing), and those that alter
+/// colors, like [`Quirk::Bright`], have an effect. With the `hyperlink` feature,
+/// a style's link is opened and closed as it changes between values.
+///
+/// [`Painted`]: crate::Painted
+///
+/// # Example
+///
+/// ```rust
+/// use yansi::{StyledBuf, Style, Color::*};
+///
+/// static KEYWORD: Style = Magenta.bold();
+/// static IDENT: Style = Style::new();
+///
+/// let mut buf = StyledBuf::new();
+/// for (token, style) in [("fn", KEYWORD), (" ", IDENT), ("main", Blue.foreground())] {
+///     buf.push(token, style);
+/// }
+///
+/// println!("{}", buf.finish());
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "_nightly", doc(cfg(feature = "alloc")))]
+pub struct StyledBuf {
+    string: String,
+    active: Style,
+}
+
+impl StyledBuf {
+    /// Returns a new, empty `StyledBuf`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::StyledBuf;
+    ///
+    /// assert_eq!(StyledBuf::new().finish(), "");
+    /// ```
+    pub fn new() -> StyledBuf {
+        StyledBuf::default()
+    }
+
+    /// Pushes `value` styled with `style`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{StyledBuf, Color::*};
+    ///
+    /// # yansi::enable();
+    /// let mut buf = StyledBuf::new();
+    /// buf.push("a", Red.bold());
+    /// buf.push(1, Red.bold());
+    /// buf.push("b", Red.foreground());
+    /// assert_eq!(buf.finish(), "\x1B[1;31ma1\x1B[0m\x1B[31mb\x1B[0m");
+    /// ```
+    pub fn push(&mut self, value: impl fmt::Display, style: Style) {
+        let style = match style.effective() {
+            true => style,
+            false if style.quirks.contains(Quirk::Mask) => return,
+            false => Style::new(),
+        };
+
+        // Writing to a `String` never fails.
+        let _ = style.fmt_transition(&self.active, &mut self.string);
+        let _ = write!(self.string, "{}", value);
+        self.active = style;
+    }
+
+    /// Consumes `self`, returning the rendered string, ending with a reset if
+    /// any styling is active.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use yansi::{StyledBuf, Style, Color::*};
+    ///
+    /// # yansi::enable();
+    /// let mut buf = StyledBuf::new();
+    /// buf.push("a", Red.foreground());
+    /// buf.push("b", Style::new());
+    /// assert_eq!(buf.finish(), "\x1B[31ma\x1B[0mb");
+    /// ```
+    pub fn finish(mut self) -> String {
+        let _ = Style::new().fmt_transition(&self.active, &mut self.string);
+        self.string
+    }
+}
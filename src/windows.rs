@@ -103,7 +103,7 @@ mod windows_console {
         use crate::condition::CachedBool;
 
         static ENABLED: CachedBool = CachedBool::new();
-        ENABLED.get_or_init(enable)
+        ENABLED.get_or_init_once(enable)
     }
 }
 
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{self, Write};
+
+use yansi::Paint;
+
+/// Counts the allocations made by the current thread.
+struct Counter;
+
+std::thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+/// A fixed-capacity, non-allocating string.
+struct Buf {
+    bytes: [u8; 256],
+    len: usize,
+}
+
+impl Buf {
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl Write for Buf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len.checked_add(s.len()).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or(fmt::Error)?;
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Formats `value` into a `Buf`, returning it and the number of allocations.
+fn render(value: impl fmt::Display) -> (Buf, usize) {
+    let mut buf = Buf { bytes: [0; 256], len: 0 };
+    let before = ALLOCATIONS.with(Cell::get);
+    write!(buf, "{}", value).unwrap();
+    (buf, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn wrap_unstyled_does_not_allocate() {
+    struct Plain(usize);
+
+    impl fmt::Display for Plain {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (0..self.0).try_for_each(|i| write!(f, "{} ", i))
+        }
+    }
+
+    yansi::with_enabled(true, || {
+        let (buf, allocs) = render(Plain(10).red().wrap());
+        assert_eq!(buf.as_str(), "\x1B[31m0 1 2 3 4 5 6 7 8 9 \x1B[0m");
+        assert_eq!(allocs, 0);
+
+        let (buf, allocs) = render(format_args!("{}", 42).green().wrap());
+        assert_eq!(buf.as_str(), "\x1B[32m42\x1B[0m");
+        assert_eq!(allocs, 0);
+    });
+
+    yansi::with_enabled(false, || {
+        let (buf, allocs) = render(Plain(3).red().wrap());
+        assert_eq!(buf.as_str(), "0 1 2 ");
+        assert_eq!(allocs, 0);
+    });
+}
+
+#[test]
+fn wrap_styled_does_not_allocate() {
+    yansi::with_enabled(true, || {
+        let (inner, _) = render(format_args!("a{}b", "x".green()));
+        let (buf, allocs) = render(inner.as_str().red().wrap());
+        assert_eq!(buf.as_str(), "\x1B[31ma\x1B[32mx\x1B[0m\x1B[31mb\x1B[0m");
+        assert_eq!(allocs, 0);
+
+        let (buf, allocs) = render(format_args!("<{}>", "x".green()).red().wrap());
+        assert_eq!(buf.as_str(), "\x1B[31m<\x1B[32mx\x1B[0m\x1B[31m>\x1B[0m");
+        assert_eq!(allocs, 0);
+    });
+}
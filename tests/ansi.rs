@@ -0,0 +1,281 @@
+use yansi::Paint;
+
+#[test]
+#[cfg(feature = "alloc")]
+fn strip_unstyled() {
+    use std::borrow::Cow;
+
+    assert!(matches!(yansi::strip(""), Cow::Borrowed("")));
+    assert!(matches!(yansi::strip("hello"), Cow::Borrowed("hello")));
+    assert!(matches!(yansi::strip("a [31m b"), Cow::Borrowed(_)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn strip_styled() {
+    let _lock = yansi_lock();
+    yansi::enable();
+
+    let styled = "hi".red().to_string();
+    assert_eq!(yansi::strip(&styled), "hi");
+
+    let styled = "hi".rgb(1, 2, 3).on_fixed(4).bold().italic().to_string();
+    assert_eq!(yansi::strip(&styled), "hi");
+
+    let inner = format!("{} b {}", "a".red(), "c".green());
+    let nested = format!("0 {} 1", inner.magenta().wrap());
+    assert_eq!(yansi::strip(&nested), "0 a b c 1");
+
+    let lingering = format!("{} {} {}", "a".red().linger(), "b".bold(), "c");
+    assert_eq!(yansi::strip(&lingering), "a b c");
+
+    yansi::whenever(yansi::Condition::DEFAULT);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn strip_hyperlinks() {
+    let link = "\x1B]8;;https://docs.rs/yansi\x1B\\docs\x1B]8;;\x1B\\";
+    assert_eq!(yansi::strip(link), "docs");
+
+    let link = "see \x1B]8;;https://docs.rs\x1B\\\x1B[32mdocs\x1B[0m\x1B]8;;\x1B\\.";
+    assert_eq!(yansi::strip(link), "see docs.");
+
+    let bel = "\x1B]8;;https://docs.rs\x07docs\x1B]8;;\x07";
+    assert_eq!(yansi::strip(bel), "docs");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn strip_truncated() {
+    assert_eq!(yansi::strip("hi\x1B"), "hi");
+    assert_eq!(yansi::strip("hi\x1B["), "hi");
+    assert_eq!(yansi::strip("hi\x1B[1;3"), "hi");
+    assert_eq!(yansi::strip("hi\x1B]8;;https://docs.rs"), "hi");
+    assert_eq!(yansi::strip("hi\x1B]8;;https://docs.rs\x1B"), "hi");
+    assert_eq!(yansi::strip("\x1B[31mhi\x1B[0"), "hi");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn parse_ansi() {
+    use yansi::{Style, Color::*, Attribute::*};
+
+    assert_eq!(yansi::parse_ansi("\x1B[1;31mhi\x1B[0m world"), vec![
+        (Red.bold(), "hi"),
+        (Style::new(), " world"),
+    ]);
+
+    assert!(yansi::parse_ansi("").is_empty());
+    assert_eq!(yansi::parse_ansi("plain"), vec![(Style::new(), "plain")]);
+    assert_eq!(yansi::parse_ansi("\x1B[1m\x1B[mhi"), vec![(Style::new(), "hi")]);
+
+    // Attributes are enabled and disabled.
+    let runs = yansi::parse_ansi("\x1B[1;2;3;4:3;9ma\x1B[22;24mb\x1B[4;21mc\x1B[4:0md");
+    assert_eq!(runs, vec![
+        (Style::new().bold().dim().italic().curly_underline().strike(), "a"),
+        (Style::new().italic().strike(), "b"),
+        (Style::new().italic().strike().underline().double_underline(), "c"),
+        (Style::new().italic().strike(), "d"),
+    ]);
+
+    // Colors in every form.
+    let runs = yansi::parse_ansi("\x1B[93;104ma\x1B[38;5;10;48;2;1;2;3;58;5;99mb\x1B[39;49;59mc");
+    assert_eq!(runs, vec![
+        (BrightYellow.on_bright_blue(), "a"),
+        (Fixed(10).on_rgb(1, 2, 3).underline_fixed(99), "b"),
+        (Primary.on_primary().underline_primary(), "c"),
+    ]);
+
+    let runs = yansi::parse_ansi("\x1B[38:2:1:2:3ma\x1B[48:2::4:5:6;58:5:7mb");
+    assert_eq!(runs, vec![
+        (Rgb(1, 2, 3).foreground(), "a"),
+        (Rgb(1, 2, 3).on_rgb(4, 5, 6).underline_white(), "b"),
+    ]);
+
+    // Unknown and non-SGR sequences are skipped.
+    let runs = yansi::parse_ansi("\x1B[1;77ma\x1B[2Kb\x1B]8;;https://docs.rs\x1B\\c\x1B[?25l");
+    assert_eq!(runs, vec![(Bold.style(), "a"), (Bold.style(), "b"), (Bold.style(), "c")]);
+    assert_eq!(yansi::parse_ansi("\x1B[38;5m\x1B[31mhi\x1B[1"), vec![(Red.foreground(), "hi")]);
+
+    // Rendering and parsing are inverses.
+    let _lock = yansi_lock();
+    yansi::enable();
+
+    let styles = [
+        Red.bold(),
+        Rgb(1, 2, 3).on_fixed(200).italic().underline_green(),
+        Style::new().blink().invert().overline().curly_underline(),
+    ];
+
+    for style in styles {
+        let rendered = "hi".paint(style).to_string();
+        assert_eq!(yansi::parse_ansi(&rendered), vec![(style, "hi")]);
+    }
+
+    yansi::whenever(yansi::Condition::DEFAULT);
+}
+
+fn yansi_lock() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock().expect("FAIL FAST - LOCK POISONED")
+}
+
+#[test]
+fn visible_width() {
+    let _lock = yansi_lock();
+    yansi::enable();
+
+    assert_eq!(yansi::visible_width(&"hi".red().to_string()), 2);
+    assert_eq!(yansi::visible_width(&"hi".red().on_rgb(1, 2, 3).bold().to_string()), 2);
+
+    let inner = format!("{} b {}", "a".red(), "c".green());
+    let nested = format!("0 {} 1", inner.magenta().wrap());
+    assert_eq!(yansi::visible_width(&nested), 9);
+
+    yansi::whenever(yansi::Condition::DEFAULT);
+
+    for plain in ["", "hello", "héllo wörld", "🎨 colors", "a\tb\nc"] {
+        assert_eq!(yansi::visible_width(plain), plain.chars().count());
+    }
+
+    let link = "see \x1B]8;;https://docs.rs\x1B\\\x1B[32mdocs\x1B[0m\x1B]8;;\x1B\\.";
+    assert_eq!(yansi::visible_width(link), 9);
+    assert_eq!(yansi::visible_width("hi\x1B[1;3"), 2);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn auto_strip() {
+    use std::io::Write;
+    use yansi::AutoStrip;
+
+    let styled = yansi::with_enabled(true, || format!("{} and {}", "Stop".red(), "Go".green()));
+
+    // Enabled: bytes pass through unchanged.
+    let mut writer = AutoStrip::new(Vec::new());
+    yansi::with_enabled(true, || writer.write_all(styled.as_bytes())).unwrap();
+    assert_eq!(writer.get_ref(), styled.as_bytes());
+
+    // Disabled: escape sequences are stripped.
+    let mut writer = AutoStrip::new(Vec::new());
+    yansi::with_enabled(false, || writer.write_all(styled.as_bytes())).unwrap();
+    assert_eq!(writer.into_inner(), b"Stop and Go");
+
+    // Sequences split across writes are stripped in their entirety.
+    yansi::with_enabled(false, || {
+        let mut writer = AutoStrip::new(Vec::new());
+        let chunks: [&[u8]; 5] = [
+            b"a\x1B[3", b"1mb\x1B", b"[0m", b"\x1B]8;;https://docs.rs\x1B", b"\\c\x1B]8;;\x07",
+        ];
+
+        for chunk in chunks {
+            assert_eq!(writer.write(chunk).unwrap(), chunk.len());
+        }
+
+        assert_eq!(writer.get_ref(), b"abc");
+
+        // Every split point of a styled string yields the same result.
+        for i in 0..=styled.len() {
+            let mut writer = AutoStrip::new(Vec::new());
+            writer.write_all(&styled.as_bytes()[..i]).unwrap();
+            writer.write_all(&styled.as_bytes()[i..]).unwrap();
+            writer.flush().unwrap();
+            assert_eq!(writer.get_ref(), b"Stop and Go", "split at {}", i);
+        }
+
+        // Non-ASCII text is untouched.
+        let mut writer = AutoStrip::new(Vec::new());
+        let styled = yansi::with_enabled(true, || "héllo 🦀".blue().to_string());
+        for chunk in styled.as_bytes().chunks(3) {
+            writer.write_all(chunk).unwrap();
+        }
+
+        assert_eq!(writer.get_ref(), "héllo 🦀".as_bytes());
+
+        // A non-ASCII char after an `ESC`, even in another write, is kept whole.
+        for (a, b) in [("\x1B", "é"), ("x\x1B", "🦀y"), ("\x1B]8;;u\x1B", "éz\x07")] {
+            let mut writer = AutoStrip::new(Vec::new());
+            writer.write_all(a.as_bytes()).unwrap();
+            writer.write_all(b.as_bytes()).unwrap();
+            let string = String::from_utf8(writer.into_inner()).expect("valid UTF-8");
+            assert_eq!(string, yansi::strip(&format!("{}{}", a, b)));
+        }
+
+        let mut writer = AutoStrip::new(Vec::new());
+        writer.write_all("a\x1Bé".as_bytes()).unwrap();
+        assert_eq!(writer.get_ref(), "aé".as_bytes());
+    });
+
+    // Enabledness is checked on every write.
+    let mut writer = AutoStrip::new(Vec::new());
+    yansi::with_enabled(true, || write!(writer, "{}", "a".red())).unwrap();
+    yansi::with_enabled(false, || write!(writer, "{}", "b".red())).unwrap();
+    assert_eq!(writer.get_ref(), b"\x1B[31ma\x1B[0mb");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn to_ansi_string_round_trip() {
+    use yansi::{Style, Color, Attribute::*};
+
+    const ATTRIBUTES: [yansi::Attribute; 14] = [
+        Bold, Dim, Italic, Underline, Blink, RapidBlink, Invert, Conceal, Strike,
+        DoubleUnderline, Overline, CurlyUnderline, Framed, Encircled,
+    ];
+
+    /// A tiny xorshift PRNG: deterministic and dependency-free.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn byte(&mut self) -> u8 {
+            self.next() as u8
+        }
+
+        fn color(&mut self, min_fixed: u8) -> Option<Color> {
+            match self.next() % 6 {
+                0 => None,
+                1 => Some(Color::Fixed(min_fixed.max(self.byte()))),
+                2 => Some(Color::Rgb(self.byte(), self.byte(), self.byte())),
+                3 => Some(Color::Primary),
+                4 => Some(Color::BASE_COLORS[self.next() as usize % 8]),
+                _ => Some(Color::BRIGHT_COLORS[self.next() as usize % 8]),
+            }
+        }
+    }
+
+    let mut rng = Rng(0x2545F4914F6CDD1D);
+    for _ in 0..2000 {
+        let mut style = Style::new()
+            .with_foreground(rng.color(0))
+            .with_background(rng.color(0))
+            // Underline colors `Fixed(0..16)` are written as base colors.
+            .with_underline_color(rng.color(16));
+
+        let bits = rng.next();
+        for (i, &attr) in ATTRIBUTES.iter().enumerate() {
+            if bits & (1 << i) != 0 {
+                style = style.attr(attr);
+            }
+        }
+
+        let ansi = style.to_ansi_string();
+        assert_eq!(ansi.is_empty(), style.is_plain());
+
+        let text = format!("{}x", ansi);
+        assert_eq!(yansi::parse_ansi(&text), vec![(style, "x")], "{:?}", ansi);
+    }
+
+    // Quirks adjust the prefix but not the round-tripped string.
+    let style = Color::Red.on_blue().bright().on_dull().reset_before();
+    assert_eq!(style.to_ansi_string(), "\x1B[44;31m");
+    assert_ne!(style.to_ansi_string(), style.prefix());
+    assert_eq!(Style::new().to_ansi_string(), "");
+}
@@ -74,6 +74,23 @@ fn colors_enabled() {
         Paint::new("hi").invert() => "\x1B[7mhi\x1B[0m",
         Paint::new("hi").conceal() => "\x1B[8mhi\x1B[0m",
         Paint::new("hi").strike() => "\x1B[9mhi\x1B[0m",
+        Paint::new("hi").double_underline() => "\x1B[21mhi\x1B[0m",
+        Paint::new("hi").overline() => "\x1B[53mhi\x1B[0m",
+        Paint::red("hi").double_underline().overline() => "\x1B[21;53;31mhi\x1B[0m",
+        Paint::new("hi").overline().bold().underline() => "\x1B[1;4;53mhi\x1B[0m",
+        Paint::new("hi").curly_underline() => "\x1B[4:3mhi\x1B[0m",
+        Paint::new("hi").curly_underline().underline_rgb(255, 0, 0) =>
+            "\x1B[4:3;58;2;255;0;0mhi\x1B[0m",
+        Paint::new("hi").underline().underline_fixed(196) => "\x1B[4;58;5;196mhi\x1B[0m",
+        Paint::red("hi").curly_underline().underline_red() => "\x1B[4:3;31;58;5;1mhi\x1B[0m",
+        Paint::new("hi").underline().underline_bright_blue() => "\x1B[4;58;5;12mhi\x1B[0m",
+        Paint::new("hi").underline().underline_primary() => "\x1B[4;59mhi\x1B[0m",
+        Paint::new("hi").underline_color(Magenta).bright() => "\x1B[58;5;5mhi\x1B[0m",
+        Paint::blue("hi").on_white().bold().curly_underline().underline_rgb(1, 2, 3) =>
+            "\x1B[1;4:3;47;34;58;2;1;2;3mhi\x1B[0m",
+        Paint::new("hi").framed() => "\x1B[51mhi\x1B[0m",
+        Paint::new("hi").encircled() => "\x1B[52mhi\x1B[0m",
+        Paint::green("hi").encircled().framed().bold() => "\x1B[1;51;52;32mhi\x1B[0m",
     }
 }
 
@@ -113,6 +130,10 @@ fn colors_disabled() {
         Paint::new("hi").conceal() => "hi",
         Paint::new("hi").strike() => "hi",
         Paint::new("hi").strike().wrap() => "hi",
+        Paint::new("hi").double_underline() => "hi",
+        Paint::new("hi").overline() => "hi",
+        Paint::new("hi").curly_underline().underline_rgb(255, 0, 0) => "hi",
+        Paint::new("hi").framed().encircled() => "hi",
     }
 }
 
@@ -149,7 +170,6 @@ fn masked_when_enabled() {
 }
 
 #[test]
-#[cfg(feature = "alloc")]
 fn wrapping() {
     let inner = || format!("{} b {}", Paint::red("a"), Paint::green("c"));
     let inner2 = || format!("0 {} 1", Paint::magenta(&inner()).wrap());
@@ -172,6 +192,64 @@ fn wrapping() {
 }
 
 #[test]
+fn wrapping_split_writes() {
+    use std::fmt;
+
+    /// Writes each piece with a separate call to `write_str()`.
+    struct Pieces(&'static [&'static str]);
+
+    impl fmt::Display for Pieces {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.iter().try_for_each(|piece| f.write_str(piece))
+        }
+    }
+
+    {
+        assert_renders! {
+            Pieces(&["a\x1B[", "0m", "b"]).blue().wrap() =>
+                "\x1B[34ma\x1B[0m\x1B[34mb\x1B[0m",
+            Pieces(&["\x1B", "[", "0", "m", "\x1B[0m"]).red().wrap() =>
+                "\x1B[31m\x1B[0m\x1B[31m\x1B[0m\x1B[31m\x1B[0m",
+            Pieces(&["\x1B\x1B[0", "m\x1B[", "01mé\x1B[0"]).red().wrap() =>
+                "\x1B[31m\x1B\x1B[0m\x1B[31m\x1B[01mé\x1B[0\x1B[0m",
+            Pieces(&["\x1B[32m", "é", "\x1B[0m", "ü"]).red().bold().wrap() =>
+                "\x1B[1;31m\x1B[32mé\x1B[0m\x1B[1;31mü\x1B[0m",
+        }
+    }
+
+    {
+        assert_disabled_renders! {
+            Pieces(&["a\x1B[3", "1mé\x1B", "[0m", "b"]).blue().wrap() => "aéb",
+            Pieces(&["\x1B]8;;url\x1B", "\\x\x1B]8;;\x1B\\"]).blue().wrap() => "x",
+            Pieces(&["a", "b\x1B"]).blue().wrap() => "ab",
+        }
+    }
+}
+
+#[test]
+fn brightening_and_dulling() {
+    static THEME: Style = BrightRed.on_bright_blue().bold();
+
+    assert_renders! {
+        Paint::red("hi").bright() => "\x1B[91mhi\x1B[0m",
+        Paint::new("hi").on_red().on_bright() => "\x1B[101mhi\x1B[0m",
+        Paint::bright_red("hi").dull() => "\x1B[31mhi\x1B[0m",
+        Paint::new("hi").on_bright_red().on_dull() => "\x1B[41mhi\x1B[0m",
+        "hi".paint(THEME).dull() => "\x1B[1;104;31mhi\x1B[0m",
+        "hi".paint(THEME).on_dull() => "\x1B[1;44;91mhi\x1B[0m",
+        "hi".paint(THEME).dull().on_dull() => "\x1B[1;44;31mhi\x1B[0m",
+        Paint::new("hi").dull().bright_white() => "\x1B[37mhi\x1B[0m",
+        Paint::new("hi").dull().bright().bright_white() => "\x1B[37mhi\x1B[0m",
+        Paint::red("hi").on_bright().dull().on_bright_blue() => "\x1B[104;31mhi\x1B[0m",
+        Paint::red("hi").dull() => "\x1B[31mhi\x1B[0m",
+        Paint::new("hi").fixed(208).rgb(1, 2, 3).dull() => "\x1B[38;2;1;2;3mhi\x1B[0m",
+        Paint::new("hi").fixed(9).on_dull().dull() => "\x1B[38;5;9mhi\x1B[0m",
+        Paint::new("hi").dull().on_dull() => "hi",
+    }
+}
+
+#[test]
+#[allow(deprecated)]
 fn lingering() {
     let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
     yansi::enable();
@@ -217,6 +295,100 @@ fn lingering() {
     yansi::whenever(Condition::DEFAULT);
 }
 
+#[test]
+fn reset_before() {
+    let inner = || format!("{} b", Paint::red("a"));
+
+    {
+        assert_renders! {
+            Paint::red("hi").reset_before() => "\x1B[0m\x1B[31mhi\x1B[0m",
+            Paint::new("hi").reset_before() => "\x1B[0mhi",
+            Paint::new("hi").reset_before().resetting() => "\x1B[0mhi\x1B[0m",
+            Paint::red("hi").reset_before().linger() => "\x1B[0m\x1B[31mhi",
+            Paint::new(&inner()).fg(Blue).reset_before().wrap() =>
+                "\x1B[0m\x1B[34m\x1B[31ma\x1B[0m\x1B[34m b\x1B[0m",
+        }
+    }
+
+    {
+        assert_disabled_renders! {
+            Paint::red("hi").reset_before() => "hi",
+            Paint::new("hi").reset_before().resetting() => "hi",
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn reset_mode() {
+    use yansi::ResetMode::*;
+
+    {
+        assert_renders! {
+            Paint::red("hi").reset_mode(Auto) => "\x1B[31mhi\x1B[0m",
+            Paint::new("hi").reset_mode(Auto) => "hi",
+            Paint::red("hi").reset_mode(Always) => "\x1B[31mhi\x1B[0m",
+            Paint::new("hi").reset_mode(Always) => "hi\x1B[0m",
+            Paint::red("hi").reset_mode(Never) => "\x1B[31mhi",
+            Paint::new("hi").reset_mode(Never) => "hi",
+
+            // The last setting replaces any previous one.
+            Paint::red("hi").linger().reset_mode(Auto) => "\x1B[31mhi\x1B[0m",
+            Paint::new("hi").resetting().reset_mode(Auto) => "hi",
+            Paint::new("hi").clear().reset_mode(Never) => "hi",
+            Paint::red("hi").resetting().reset_mode(Never) => "\x1B[31mhi",
+            Paint::red("hi").linger().reset_mode(Always) => "\x1B[31mhi\x1B[0m",
+            Paint::red("hi").reset_mode(Never).reset_mode(Auto) => "\x1B[31mhi\x1B[0m",
+            Paint::red("hi").reset_mode(Always).linger() => "\x1B[31mhi\x1B[0m",
+        }
+    }
+
+    assert_eq!(Red.reset_mode(Never), Red.linger());
+
+    #[cfg(feature = "alloc")] {
+        assert_eq!(Style::new().reset_mode(Always).suffix(), Style::new().resetting().suffix());
+        assert_eq!(Red.reset_mode(Never).suffix(), Red.linger().suffix());
+        assert_eq!(Red.linger().reset_mode(Auto).suffix(), Red.foreground().suffix());
+        assert_eq!(Red.reset_mode(Auto).prefix(), Red.foreground().prefix());
+    }
+}
+
+#[test]
+fn forced() {
+    {
+        assert_renders! {
+            Paint::red("hi").force() => "\x1B[31mhi\x1B[0m",
+            Paint::new("hi").force() => "hi",
+            Paint::red("hi").force().mask() => "\x1B[31mhi\x1B[0m",
+        }
+    }
+
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+    for global in [Condition::NEVER, Condition::from(|| false)] {
+        yansi::whenever(global);
+        assert_eq!(Paint::red("hi").to_string(), "hi");
+        assert_eq!(Paint::red("hi").force().to_string(), "\x1B[31mhi\x1B[0m");
+        assert_eq!(Paint::red("hi").bold().force().linger().to_string(), "\x1B[1;31mhi");
+        assert_eq!(Paint::new("hi").force().resetting().to_string(), "hi\x1B[0m");
+        assert_eq!(Paint::red("hi").force().mask().to_string(), "\x1B[31mhi\x1B[0m");
+        assert_eq!(Paint::new("hi").mask().to_string(), "");
+
+        // A per-style condition is still honored.
+        let forced = Paint::red("hi").force();
+        assert_eq!(forced.whenever(Condition::ALWAYS).to_string(), "\x1B[31mhi\x1B[0m");
+        assert_eq!(forced.whenever(Condition::NEVER).to_string(), "hi");
+        assert_eq!(forced.whenever(Condition::NEVER).mask().to_string(), "");
+    }
+
+    #[cfg(feature = "std")] {
+        yansi::enable();
+        let forced = yansi::with_enabled(false, || Paint::red("hi").force().to_string());
+        assert_eq!(forced, "\x1B[31mhi\x1B[0m");
+    }
+
+    yansi::whenever(Condition::DEFAULT);
+}
+
 #[test]
 fn hash_eq() {
     use std::collections::hash_map::DefaultHasher;
@@ -237,3 +409,166 @@ fn hash_eq() {
     assert_eq!(hash(&a), hash(&b));
     assert_eq!(hash(&a), hash(&c));
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn thread_local_override() {
+    // No lock needed: the override is independent of the global state.
+    let styled = yansi::with_enabled(true, || {
+        assert!(yansi::is_enabled());
+        "hi".red().mask().to_string()
+    });
+
+    assert_eq!(styled, "\x1B[31mhi\x1B[0m");
+
+    let plain = yansi::with_enabled(false, || {
+        assert!(!yansi::is_enabled());
+        "hi".red().to_string() + &"!".mask().to_string()
+    });
+
+    assert_eq!(plain, "hi");
+
+    // Nested calls apply the innermost value and restore the outer one.
+    yansi::with_enabled(false, || {
+        yansi::with_enabled(true, || assert!(yansi::is_enabled()));
+        assert!(!yansi::is_enabled());
+
+        // Other threads don't see the override.
+        let other = std::thread::spawn(|| yansi::with_enabled(true, yansi::is_enabled));
+        assert!(other.join().unwrap());
+        assert!(!yansi::is_enabled());
+    });
+
+    // The previous state is restored even when `f` panics.
+    yansi::with_enabled(false, || {
+        let result = std::panic::catch_unwind(|| yansi::with_enabled(true, || panic!()));
+        assert!(result.is_err());
+        assert!(!yansi::is_enabled());
+    });
+
+    // The per-style condition still applies.
+    let plain = yansi::with_enabled(true, || "hi".red().whenever(Condition::NEVER).to_string());
+    assert_eq!(plain, "hi");
+}
+
+#[test]
+fn enabled_for_stream() {
+    use yansi::Stream;
+
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    yansi::enable();
+    assert!(yansi::is_enabled_for(Stream::Stdout));
+    assert!(yansi::is_enabled_for(Stream::Stderr));
+
+    yansi::disable();
+    assert!(!yansi::is_enabled_for(Stream::Stdout));
+    assert!(!yansi::is_enabled_for(Stream::Stderr));
+
+    yansi::whenever(Condition::ALWAYS);
+    assert!(yansi::is_enabled_for(Stream::Stdout));
+
+    #[cfg(feature = "std")] {
+        yansi::disable();
+        assert!(yansi::with_enabled(true, || yansi::is_enabled_for(Stream::Stdout)));
+        yansi::enable();
+        assert!(!yansi::with_enabled(false, || yansi::is_enabled_for(Stream::Stderr)));
+    }
+
+    // Explicitly setting `DEFAULT` applies it to every stream alike.
+    yansi::whenever(Condition::DEFAULT);
+    assert_eq!(yansi::is_enabled_for(Stream::Stderr), Condition::os_support());
+}
+
+#[test]
+fn guards() {
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    yansi::enable();
+    {
+        let _guard = yansi::disable_guard();
+        assert!(!yansi::is_enabled());
+        assert_eq!("hi".red().to_string(), "hi");
+    }
+
+    assert!(yansi::is_enabled());
+    assert_eq!("hi".red().to_string(), "\x1B[31mhi\x1B[0m");
+
+    yansi::disable();
+    {
+        let _guard = yansi::enable_guard();
+        assert!(yansi::is_enabled());
+    }
+
+    assert!(!yansi::is_enabled());
+
+    // The exact previous condition is restored, not just its current value.
+    static FLAG: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    let condition = Condition::from_atomic(&FLAG).unwrap();
+    yansi::whenever(condition);
+    {
+        let _outer = yansi::whenever_guard(Condition::ALWAYS);
+        let _inner = yansi::disable_guard();
+        assert!(!yansi::is_enabled());
+    }
+
+    assert!(!yansi::is_enabled());
+    FLAG.store(true, std::sync::atomic::Ordering::SeqCst);
+    assert!(yansi::is_enabled());
+
+    // Guards restore on unwind, too.
+    #[cfg(feature = "std")] {
+        yansi::enable();
+        let result = std::panic::catch_unwind(|| {
+            let _guard = yansi::disable_guard();
+            panic!("unwind");
+        });
+
+        assert!(result.is_err());
+        assert!(yansi::is_enabled());
+    }
+
+    yansi::whenever(Condition::DEFAULT);
+}
+
+#[test]
+fn per_line() {
+    let inner = || format!("{}\nb", Paint::red("a"));
+
+    {
+        assert_renders! {
+            Paint::new("one").red().per_line() => "\x1B[31mone\x1B[0m",
+            Paint::new("one\ntwo").red().per_line() => "\x1B[31mone\x1B[0m\n\x1B[31mtwo\x1B[0m",
+            Paint::new("a\n\nb\n").bold().per_line() =>
+                "\x1B[1ma\x1B[0m\n\x1B[1m\x1B[0m\n\x1B[1mb\x1B[0m\n\x1B[1m\x1B[0m",
+            Paint::new("one\ntwo").red().linger().per_line() => "\x1B[31mone\n\x1B[31mtwo",
+            Paint::new("one\ntwo").per_line() => "one\ntwo",
+            Paint::new(&inner()).blue().wrap().per_line() =>
+                "\x1B[34m\x1B[31ma\x1B[0m\x1B[34m\x1B[0m\n\x1B[34mb\x1B[0m",
+            Paint::new(12).red().per_line() => "\x1B[31m12\x1B[0m",
+        }
+    }
+
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+    yansi::enable();
+
+    let painted = Paint::new("a\nb").red().per_line();
+    assert_eq!(format!("{:>5}", painted), "  \x1B[31ma\x1B[0m\n\x1B[31mb\x1B[0m");
+
+    // Spaces at either end of the value are styled when there's no width.
+    assert_eq!(Paint::new(" a \nb ").red().per_line().to_string(),
+        "\x1B[31m a \x1B[0m\n\x1B[31mb \x1B[0m");
+
+    // Formatting flags, including the debug hex flags, reach the value.
+    let bytes = Paint::new([10u8, 255]).red().per_line();
+    assert_eq!(format!("{:x?}", bytes), "\x1B[31m[a, ff]\x1B[0m");
+    let expected = "\x1B[31m[\x1B[0m\n\x1B[31m    0xA,\x1B[0m\n\x1B[31m    0xFF,\x1B[0m\n\x1B[31m]\x1B[0m";
+    assert_eq!(format!("{:#X?}", bytes), expected);
+    assert_eq!(format!("{:+.1}", Paint::new(1.25).red().per_line()), "\x1B[31m+1.2\x1B[0m");
+    #[cfg(feature = "alloc")]
+    assert_eq!(painted.style.compile().paint("a\nb").to_string(), painted.to_string());
+
+    yansi::disable();
+    assert_eq!(painted.to_string(), "a\nb");
+    yansi::whenever(Condition::DEFAULT);
+}
@@ -0,0 +1,434 @@
+use yansi::Color::{self, *};
+
+#[test]
+fn from_hex() {
+    assert_eq!(Color::from_hex("#1e90ff"), Some(Rgb(30, 144, 255)));
+    assert_eq!(Color::from_hex("1e90ff"), Some(Rgb(30, 144, 255)));
+    assert_eq!(Color::from_hex("#1E90FF"), Some(Rgb(30, 144, 255)));
+    assert_eq!(Color::from_hex("#1e90Ff"), Some(Rgb(30, 144, 255)));
+    assert_eq!(Color::from_hex("#000000"), Some(Rgb(0, 0, 0)));
+    assert_eq!(Color::from_hex("#ffffff"), Some(Rgb(255, 255, 255)));
+
+    assert_eq!(Color::from_hex("#f0a"), Some(Rgb(0xff, 0x00, 0xaa)));
+    assert_eq!(Color::from_hex("#F0A"), Some(Rgb(0xff, 0x00, 0xaa)));
+    assert_eq!(Color::from_hex("f0a"), Some(Rgb(0xff, 0x00, 0xaa)));
+    assert_eq!(Color::from_hex("#123"), Some(Rgb(0x11, 0x22, 0x33)));
+
+    assert_eq!(Color::from_hex(""), None);
+    assert_eq!(Color::from_hex("#"), None);
+    assert_eq!(Color::from_hex("#12345"), None);
+    assert_eq!(Color::from_hex("#1234567"), None);
+    assert_eq!(Color::from_hex("xyz"), None);
+    assert_eq!(Color::from_hex("#12345g"), None);
+    assert_eq!(Color::from_hex("##123"), None);
+    assert_eq!(Color::from_hex(" #123"), None);
+    assert_eq!(Color::from_hex("#ééé"), None);
+}
+
+#[test]
+fn to_fixed() {
+    assert_eq!(Rgb(0, 0, 0).to_fixed(), Fixed(16));
+    assert_eq!(Rgb(255, 255, 255).to_fixed(), Fixed(231));
+    assert_eq!(Rgb(255, 0, 0).to_fixed(), Fixed(196));
+    assert_eq!(Rgb(0, 255, 0).to_fixed(), Fixed(46));
+    assert_eq!(Rgb(0, 0, 255).to_fixed(), Fixed(21));
+    assert_eq!(Rgb(95, 135, 175).to_fixed(), Fixed(67));
+    assert_eq!(Rgb(100, 130, 180).to_fixed(), Fixed(67));
+    assert_eq!(Rgb(215, 175, 0).to_fixed(), Fixed(178));
+
+    // Grays prefer the grayscale ramp when it's nearer.
+    assert_eq!(Rgb(8, 8, 8).to_fixed(), Fixed(232));
+    assert_eq!(Rgb(128, 128, 128).to_fixed(), Fixed(244));
+    assert_eq!(Rgb(238, 238, 238).to_fixed(), Fixed(255));
+    assert_eq!(Rgb(135, 135, 135).to_fixed(), Fixed(102));
+
+    // Non-RGB colors are untouched.
+    assert_eq!(Primary.to_fixed(), Primary);
+    assert_eq!(Red.to_fixed(), Red);
+    assert_eq!(BrightCyan.to_fixed(), BrightCyan);
+    assert_eq!(Fixed(42).to_fixed(), Fixed(42));
+}
+
+#[test]
+fn downgrade() {
+    use yansi::{Style, ColorLevel};
+
+    let style = Rgb(255, 0, 0).on_rgb(0, 0, 0).bold();
+
+    let downgraded = style.downgrade(ColorLevel::TrueColor);
+    assert_eq!(downgraded, style);
+
+    let downgraded = style.downgrade(ColorLevel::Ansi256);
+    assert_eq!(downgraded, Fixed(196).on_fixed(16).bold());
+
+    let downgraded = style.downgrade(ColorLevel::Ansi16);
+    assert_eq!(downgraded, BrightRed.on_black().bold());
+
+    let downgraded = style.downgrade(ColorLevel::None);
+    assert_eq!(downgraded, Style::new().bold());
+
+    let style = Fixed(1).on_fixed(250).mask();
+    assert_eq!(style.downgrade(ColorLevel::Ansi256), style);
+    assert_eq!(style.downgrade(ColorLevel::Ansi16), Red.on_white());
+    assert!(style.downgrade(ColorLevel::Ansi16).quirks().eq([yansi::Quirk::Mask]));
+
+    let style = Rgb(255, 0, 0).underline_rgb(0, 0, 255).curly_underline();
+    assert_eq!(style.downgrade(ColorLevel::Ansi256).underline_color, Some(Fixed(21)));
+    assert_eq!(style.downgrade(ColorLevel::Ansi16).underline_color, Some(Blue));
+    assert_eq!(style.downgrade(ColorLevel::None), Style::new().curly_underline());
+
+    let style = Primary.on_bright_cyan();
+    assert_eq!(style.downgrade(ColorLevel::Ansi16), style);
+    assert_eq!(style.downgrade(ColorLevel::None), Style::new());
+}
+
+#[test]
+fn from_name() {
+    assert_eq!(Color::from_name("dodgerblue"), Some(Rgb(30, 144, 255)));
+    assert_eq!(Color::from_name("DodgerBlue"), Some(Rgb(30, 144, 255)));
+    assert_eq!(Color::from_name("REBECCAPURPLE"), Some(Rgb(102, 51, 153)));
+    assert_eq!(Color::from_name("rebeccapurple"), Some(Rgb(102, 51, 153)));
+    assert_eq!(Color::from_name("aliceblue"), Some(Rgb(240, 248, 255)));
+    assert_eq!(Color::from_name("yellowgreen"), Some(Rgb(154, 205, 50)));
+    assert_eq!(Color::from_name("gray"), Some(Rgb(128, 128, 128)));
+    assert_eq!(Color::from_name("grey"), Some(Rgb(128, 128, 128)));
+    assert_eq!(Color::from_name("green"), Some(Rgb(0, 128, 0)));
+    assert_eq!(Color::from_name("black"), Some(Rgb(0, 0, 0)));
+
+    assert_eq!(Color::from_name("dodgerbleu"), None);
+    assert_eq!(Color::from_name("dodger blue"), None);
+    assert_eq!(Color::from_name("dodgerblue "), None);
+    assert_eq!(Color::from_name("primary"), None);
+    assert_eq!(Color::from_name(""), None);
+}
+
+#[test]
+fn blend() {
+    let (red, blue) = (Rgb(255, 0, 0), Rgb(0, 0, 255));
+    assert_eq!(red.blend(blue, 0.5), Rgb(127, 0, 127));
+    assert_eq!(blue.blend(red, 0.5), Rgb(127, 0, 127));
+    assert_eq!(red.blend(blue, 0.0), red);
+    assert_eq!(red.blend(blue, 1.0), blue);
+    assert_eq!(red.blend(blue, 0.25), Rgb(191, 0, 63));
+
+    // `t` is clamped.
+    assert_eq!(red.blend(blue, -1.0), red);
+    assert_eq!(red.blend(blue, 2.0), blue);
+    assert_eq!(red.blend(blue, f32::NAN), red);
+
+    // Base and fixed colors are converted to RGB first.
+    assert_eq!(BrightRed.blend(BrightBlue, 0.0), Rgb(255, 0, 0));
+    assert_eq!(Black.blend(BrightWhite, 1.0), Rgb(255, 255, 255));
+    assert_eq!(Fixed(16).blend(Fixed(231), 0.5), Rgb(127, 127, 127));
+    assert_eq!(Fixed(9).blend(Fixed(21), 0.5), Rgb(127, 0, 127));
+    assert_eq!(Fixed(244).blend(Fixed(244), 0.3), Rgb(128, 128, 128));
+
+    // `Primary` has no RGB value: `self` is returned.
+    assert_eq!(Primary.blend(red, 0.5), Primary);
+    assert_eq!(red.blend(Primary, 0.5), red);
+    assert_eq!(Red.blend(Primary, 0.5), Red);
+}
+
+#[test]
+fn luminance() {
+    fn assert_near(color: Color, expected: f32) {
+        let actual = color.luminance();
+        assert!((actual - expected).abs() < 0.0005, "{:?}: {} != {}", color, actual, expected);
+    }
+
+    assert_near(Rgb(0, 0, 0), 0.0);
+    assert_near(Rgb(255, 255, 255), 1.0);
+    assert_near(Rgb(255, 0, 0), 0.2126);
+    assert_near(Rgb(0, 255, 0), 0.7152);
+    assert_near(Rgb(0, 0, 255), 0.0722);
+    assert_near(Rgb(128, 128, 128), 0.2159);
+    assert_near(Rgb(30, 144, 255), 0.2740);
+    assert_near(Rgb(10, 10, 10), 0.0030);
+
+    // Base and fixed colors use xterm's palette.
+    assert_near(Black, 0.0);
+    assert_near(BrightWhite, 1.0);
+    assert_near(Fixed(16), 0.0);
+    assert_near(Fixed(231), 1.0);
+    assert_near(Fixed(244), Rgb(128, 128, 128).luminance());
+    assert_near(Primary, 0.5);
+
+    assert!(Black.is_dark());
+    assert!(Blue.is_dark());
+    assert!(Rgb(128, 128, 128).is_dark());
+    assert!(!Rgb(200, 200, 200).is_dark());
+    assert!(!BrightYellow.is_dark());
+    assert!(!White.is_dark());
+    assert!(!Primary.is_dark());
+}
+
+#[test]
+fn from_str() {
+    let parse = |s: &str| s.parse::<Color>();
+
+    assert_eq!(parse("red"), Ok(Red));
+    assert_eq!(parse("RED"), Ok(Red));
+    assert_eq!(parse("  Magenta\n"), Ok(Magenta));
+    assert_eq!(parse("primary"), Ok(Primary));
+    assert_eq!(parse("bright_red"), Ok(BrightRed));
+    assert_eq!(parse("Bright_White"), Ok(BrightWhite));
+
+    assert_eq!(parse("0"), Ok(Fixed(0)));
+    assert_eq!(parse("202"), Ok(Fixed(202)));
+    assert_eq!(parse("123"), Ok(Fixed(123)));
+    assert_eq!(parse("fixed(255)"), Ok(Fixed(255)));
+    assert_eq!(parse("FIXED( 7 )"), Ok(Fixed(7)));
+
+    assert_eq!(parse("rgb(30,144,255)"), Ok(Rgb(30, 144, 255)));
+    assert_eq!(parse("rgb(30, 144, 255)"), Ok(Rgb(30, 144, 255)));
+    assert_eq!(parse("RGB (0, 0, 0)"), Ok(Rgb(0, 0, 0)));
+
+    assert_eq!(parse("#1e90ff"), Ok(Rgb(30, 144, 255)));
+    assert_eq!(parse("#1E90FF"), Ok(Rgb(30, 144, 255)));
+    assert_eq!(parse("#f0a"), Ok(Rgb(255, 0, 170)));
+    assert_eq!(parse("#123"), Ok(Rgb(17, 34, 51)));
+
+    assert_eq!(parse("dodgerblue"), Ok(Rgb(30, 144, 255)));
+    assert_eq!(parse("Gray"), Ok(Rgb(128, 128, 128)));
+
+    for invalid in ["", "  ", "256", "-1", "fixed()", "fixed(256)", "fixed(1, 2)"] {
+        assert!(parse(invalid).is_err(), "{:?}", invalid);
+    }
+
+    for invalid in ["rgb(1, 2)", "rgb(1, 2, 3, 4)", "rgb(1, 2, 300)", "rgb(1, 2, 3", "#12345"] {
+        assert!(parse(invalid).is_err(), "{:?}", invalid);
+    }
+
+    for invalid in ["#ggg", "redd", "bright red", "bright_", "bright_dodgerblue", "fixed"] {
+        assert!(parse(invalid).is_err(), "{:?}", invalid);
+    }
+
+    // Hex strings require a `#`, so words made of hex digits aren't colors.
+    for invalid in ["bad", "add", "Bee", "facade", "1E90FF", "f0a"] {
+        assert!(parse(invalid).is_err(), "{:?}", invalid);
+    }
+
+    let error = |s: &str| parse(s).unwrap_err().to_string();
+    assert!(error("").contains("empty"));
+    assert!(error("300").contains("fixed"));
+    assert!(error("fixed(x)").contains("fixed"));
+    assert!(error("rgb(1, 2)").contains("RGB"));
+    assert!(error("#12").contains("hex"));
+    assert!(error("reed").contains("unknown"));
+}
+
+#[test]
+fn code() {
+    use yansi::{Attribute, Variant};
+
+    fn codes(color: Color, variant: Variant) -> Vec<u8> {
+        color.code(variant).collect()
+    }
+
+    assert_eq!(Attribute::Bold.code(), 1);
+    assert_eq!(Attribute::Invert.code(), 7);
+    assert_eq!(Attribute::DoubleUnderline.code(), 21);
+    assert_eq!(Attribute::Encircled.code(), 52);
+    assert_eq!(Attribute::CurlyUnderline.code(), 4);
+
+    assert_eq!(codes(Primary, Variant::Fg), [39]);
+    assert_eq!(codes(Red, Variant::Fg), [31]);
+    assert_eq!(codes(BrightCyan, Variant::Fg), [96]);
+    assert_eq!(codes(Red, Variant::Bg), [41]);
+    assert_eq!(codes(BrightCyan, Variant::Bg), [106]);
+    assert_eq!(codes(Fixed(100), Variant::Fg), [38, 5, 100]);
+    assert_eq!(codes(Fixed(100), Variant::Bg), [48, 5, 100]);
+    assert_eq!(codes(Rgb(1, 2, 3), Variant::Fg), [38, 2, 1, 2, 3]);
+    assert_eq!(codes(Rgb(1, 2, 3), Variant::Bg), [48, 2, 1, 2, 3]);
+
+    assert_eq!(codes(Primary, Variant::Underline), [59]);
+    assert_eq!(codes(Red, Variant::Underline), [58, 5, 1]);
+    assert_eq!(codes(BrightCyan, Variant::Underline), [58, 5, 14]);
+    assert_eq!(codes(Fixed(100), Variant::Underline), [58, 5, 100]);
+    assert_eq!(codes(Rgb(1, 2, 3), Variant::Underline), [58, 2, 1, 2, 3]);
+
+    assert_eq!(Rgb(1, 2, 3).code(Variant::Fg).len(), 5);
+
+    // Written sequences agree with the codes.
+    #[cfg(feature = "alloc")] {
+        for n in 0..=255 {
+            assert_eq!(Fixed(n).foreground().prefix(), format!("\x1B[38;5;{}m", n));
+            assert_eq!(Rgb(n, 0, 9).background().prefix(), format!("\x1B[48;2;{};0;9m", n));
+        }
+
+        for attr in [Attribute::Bold, Attribute::Strike, Attribute::DoubleUnderline, Attribute::Overline] {
+            assert_eq!(attr.style().prefix(), format!("\x1B[{}m", attr.code()));
+        }
+
+        assert_eq!(Attribute::CurlyUnderline.style().prefix(), "\x1B[4:3m");
+    }
+}
+
+#[test]
+fn rgb_components() {
+    for rgb in [(0, 0, 0), (30, 144, 255), (255, 255, 255), (1, 2, 3)] {
+        let (r, g, b) = rgb;
+        assert_eq!(Color::from(rgb), Rgb(r, g, b));
+        assert_eq!(Color::from([r, g, b]), Rgb(r, g, b));
+        assert_eq!(Color::from(rgb).rgb_components(), Some(rgb));
+        assert_eq!(Color::from([r, g, b]).rgb_components(), Some(rgb));
+    }
+
+    // Every cube and grayscale color round-trips through its components.
+    for n in 16..=255 {
+        let rgb = Fixed(n).rgb_components().unwrap();
+        assert_eq!(Color::from(rgb).to_fixed(), Fixed(n));
+    }
+
+    assert_eq!(Black.rgb_components(), Fixed(0).rgb_components());
+    assert_eq!(BrightWhite.rgb_components(), Fixed(15).rgb_components());
+    assert_eq!(Fixed(16).rgb_components(), Some((0, 0, 0)));
+    assert_eq!(Fixed(231).rgb_components(), Some((255, 255, 255)));
+    assert_eq!(Fixed(255).rgb_components(), Some((238, 238, 238)));
+    assert_eq!(Primary.rgb_components(), None);
+}
+
+#[test]
+fn gray() {
+    use yansi::{Paint, Style};
+
+    assert_eq!(Color::gray(0), Fixed(232));
+    assert_eq!(Color::gray(5), Fixed(237));
+    assert_eq!(Color::gray(23), Fixed(255));
+    assert_eq!(Color::gray(24), Fixed(255));
+    assert_eq!(Color::gray(u8::MAX), Fixed(255));
+
+    for level in 0..=23 {
+        let (v, _, _) = Color::gray(level).rgb_components().unwrap();
+        assert_eq!(v, 8 + level * 10);
+    }
+
+    assert_eq!(Style::new().gray(5), Style::new().fixed(237));
+    assert_eq!(Style::new().on_gray(30), Style::new().on_fixed(255));
+    assert_eq!(Style::new().underline_gray(0), Style::new().underline_fixed(232));
+    assert_eq!(Red.on_gray(1), Red.on_fixed(233));
+    assert_eq!("shadow".new().gray(5).style, "shadow".fixed(237).style);
+    assert_eq!("shadow".on_gray(5).style.background, Some(Fixed(237)));
+}
+
+#[test]
+fn cube() {
+    use yansi::{Paint, Style};
+
+    assert_eq!(Color::cube(0, 0, 0), Fixed(16));
+    assert_eq!(Color::cube(5, 5, 5), Fixed(231));
+    assert_eq!(Color::cube(5, 0, 0), Fixed(196));
+    assert_eq!(Color::cube(0, 5, 0), Fixed(46));
+    assert_eq!(Color::cube(0, 0, 5), Fixed(21));
+    assert_eq!(Color::cube(1, 2, 3), Fixed(16 + 36 + 12 + 3));
+
+    assert_eq!(Color::cube(6, 0, 0), Color::cube(5, 0, 0));
+    assert_eq!(Color::cube(0, 200, 0), Color::cube(0, 5, 0));
+    assert_eq!(Color::cube(0, 0, u8::MAX), Color::cube(0, 0, 5));
+    assert_eq!(Color::cube(u8::MAX, u8::MAX, u8::MAX), Fixed(231));
+
+    // Every cube color round-trips through its components.
+    for (r, g, b) in (0..6).flat_map(|r| (0..6).flat_map(move |g| (0..6).map(move |b| (r, g, b)))) {
+        let rgb = Color::cube(r, g, b).rgb_components().unwrap();
+        assert_eq!(Color::from(rgb).to_fixed(), Color::cube(r, g, b));
+    }
+
+    assert_eq!(Style::new().cube(5, 0, 0), Style::new().fixed(196));
+    assert_eq!(Style::new().on_cube(0, 5, 0), Style::new().on_fixed(46));
+    assert_eq!(Style::new().underline_cube(0, 0, 9), Style::new().underline_fixed(21));
+    assert_eq!(Red.on_cube(0, 0, 0), Red.on_fixed(16));
+    assert_eq!("x".new().cube(5, 0, 0).style, "x".paint(Color::cube(5, 0, 0).foreground()).style);
+}
+
+#[test]
+fn base_colors() {
+    assert_eq!(Color::BASE_COLORS, [Black, Red, Green, Yellow, Blue, Magenta, Cyan, White]);
+    assert_eq!(Color::BRIGHT_COLORS, [
+        BrightBlack, BrightRed, BrightGreen, BrightYellow,
+        BrightBlue, BrightMagenta, BrightCyan, BrightWhite,
+    ]);
+
+    // Palette order matches the 256-color palette's first 16 entries.
+    let all = Color::BASE_COLORS.iter().chain(Color::BRIGHT_COLORS.iter());
+    for (i, color) in all.enumerate() {
+        assert_eq!(color.rgb_components(), Fixed(i as u8).rgb_components());
+    }
+}
+
+#[test]
+fn rotate_hue() {
+    // Red's complement is cyan; rotating it by 120° yields green, by 240° blue.
+    assert_eq!(Red.complement(), Rgb(0, 205, 205));
+    assert_eq!(Red.rotate_hue(120.0), Rgb(0, 205, 0));
+    assert_eq!(Red.rotate_hue(240.0), Rgb(0, 0, 205));
+    assert_eq!(Red.rotate_hue(-120.0), Red.rotate_hue(240.0));
+    assert_eq!(Red.rotate_hue(480.0), Red.rotate_hue(120.0));
+    assert_eq!(Rgb(255, 0, 0).rotate_hue(60.0), Rgb(255, 255, 0));
+    assert_eq!(Fixed(196).complement(), Rgb(0, 255, 255));
+
+    // Rotations compose and are reversible, up to rounding.
+    for color in [Rgb(30, 144, 255), Rgb(200, 100, 50), Fixed(99), Magenta] {
+        let (r, g, b) = color.rgb_components().unwrap();
+        let (r2, g2, b2) = color.rotate_hue(77.0).rotate_hue(-77.0).rgb_components().unwrap();
+        assert!(r.abs_diff(r2) <= 1 && g.abs_diff(g2) <= 1 && b.abs_diff(b2) <= 1, "{:?}", color);
+        assert_eq!(color.complement().complement().rgb_components(), Some((r, g, b)));
+    }
+
+    // Grays, non-finite rotations, and `Primary` are unchanged.
+    assert_eq!(Rgb(7, 7, 7).complement(), Rgb(7, 7, 7));
+    assert_eq!(White.complement(), Rgb(229, 229, 229));
+    assert_eq!(Red.rotate_hue(f32::NAN), Rgb(205, 0, 0));
+    assert_eq!(Red.rotate_hue(f32::INFINITY), Rgb(205, 0, 0));
+    assert_eq!(Primary.rotate_hue(90.0), Primary);
+    assert_eq!(Primary.complement(), Primary);
+}
+
+#[test]
+fn rgb_f32() {
+    use yansi::{Paint, Style};
+
+    assert_eq!(Color::rgb_f32(0.0, 0.0, 0.0), Rgb(0, 0, 0));
+    assert_eq!(Color::rgb_f32(1.0, 1.0, 1.0), Rgb(255, 255, 255));
+    assert_eq!(Color::rgb_f32(0.5, 0.25, 0.75), Rgb(128, 64, 191));
+
+    // Out of gamut components are clamped.
+    assert_eq!(Color::rgb_f32(-0.1, -1.0, f32::NEG_INFINITY), Rgb(0, 0, 0));
+    assert_eq!(Color::rgb_f32(1.1, 2.0, f32::INFINITY), Rgb(255, 255, 255));
+    assert_eq!(Color::rgb_f32(-3.0, 0.5, 7.0), Rgb(0, 128, 255));
+    assert_eq!(Color::rgb_f32(f32::NAN, 1.0, f32::NAN), Rgb(0, 255, 0));
+
+    // Every `u8` round-trips.
+    for v in 0..=255u8 {
+        assert_eq!(Color::rgb_f32(v as f32 / 255.0, 0.0, 0.0), Rgb(v, 0, 0));
+    }
+
+    assert_eq!(Style::new().rgb_f32(2.0, 0.0, -2.0), Style::new().rgb(255, 0, 0));
+    assert_eq!(Style::new().on_rgb_f32(-2.0, 1.0, 2.0), Style::new().on_rgb(0, 255, 255));
+    assert_eq!("x".new().rgb_f32(1.5, 0.0, 0.0).on_rgb_f32(0.0, 0.0, 1.5).style, Rgb(255, 0, 0).on_rgb(0, 0, 255));
+}
+
+#[test]
+fn grayscale() {
+    let red = Rgb(255, 0, 0);
+    let gray = red.grayscale();
+    let (r, g, b) = gray.rgb_components().unwrap();
+    assert!(r == g && g == b);
+    assert_eq!(gray, Rgb(127, 127, 127));
+    assert!((gray.luminance() - red.luminance()).abs() < 0.005);
+
+    // Every gray is its own grayscale.
+    for v in 0..=255 {
+        assert_eq!(Rgb(v, v, v).grayscale(), Rgb(v, v, v));
+    }
+
+    // Luminance is preserved within rounding.
+    for color in (0..=255).map(Fixed).chain([Red, BrightCyan, Rgb(30, 144, 255), Rgb(1, 2, 3)]) {
+        let gray = color.grayscale();
+        let (r, g, b) = gray.rgb_components().unwrap();
+        assert!(r == g && g == b, "{:?} -> {:?}", color, gray);
+        assert!((gray.luminance() - color.luminance()).abs() < 0.005, "{:?} -> {:?}", color, gray);
+        assert_eq!(gray.grayscale(), gray);
+    }
+
+    assert_eq!(Primary.grayscale(), Primary);
+}
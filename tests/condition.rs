@@ -0,0 +1,341 @@
+use yansi::Condition;
+
+#[cfg(feature = "detect-env")]
+static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Runs `f` with the env var `name` set to `value` or removed if `None`.
+#[cfg(feature = "detect-env")]
+fn with_env<R>(name: &str, value: Option<&str>, f: impl FnOnce() -> R) -> R {
+    let previous = std::env::var_os(name);
+    match value {
+        Some(value) => std::env::set_var(name, value),
+        None => std::env::remove_var(name),
+    }
+
+    let result = f();
+    match previous {
+        Some(previous) => std::env::set_var(name, previous),
+        None => std::env::remove_var(name),
+    }
+
+    result
+}
+
+#[test]
+#[cfg(feature = "detect-env")]
+fn term() {
+    let _lock = ENV_LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    let term = |value| with_env("TERM", value, || {
+        let dumb = Condition::term_dumb_live();
+        assert_eq!(dumb, (Condition::TERM_DUMB_LIVE)());
+        assert_eq!(!dumb, Condition::term_supports_color_live());
+        assert_eq!(!dumb, (Condition::TERM_SUPPORTS_COLOR_LIVE)());
+        dumb
+    });
+
+    assert!(term(None));
+    assert!(term(Some("dumb")));
+    assert!(!term(Some("xterm-256color")));
+    assert!(!term(Some("screen")));
+    assert!(!term(Some("")));
+    assert!(!term(Some("DUMB")));
+}
+
+#[test]
+#[cfg(feature = "detect-env")]
+fn ci() {
+    const VARS: &[&str] = &[
+        "CI", "GITHUB_ACTIONS", "GITLAB_CI", "BUILDKITE", "CIRCLECI", "TRAVIS",
+        "APPVEYOR", "TF_BUILD", "JENKINS_URL", "TEAMCITY_VERSION", "DRONE",
+    ];
+
+    let _lock = ENV_LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    // Clear every variable, then set each one individually.
+    fn without_ci<R>(vars: &[&str], f: impl FnOnce() -> R) -> R {
+        match vars.split_first() {
+            Some((var, rest)) => with_env(var, None, || without_ci(rest, f)),
+            None => f(),
+        }
+    }
+
+    without_ci(VARS, || {
+        assert!(!Condition::ci_live());
+        assert!(!(Condition::CI_LIVE)());
+
+        for var in VARS {
+            assert!(with_env(var, Some("true"), Condition::ci_live));
+            assert!(with_env(var, Some("1"), Condition::ci_live));
+            assert!(!with_env(var, Some("0"), Condition::ci_live));
+            assert!(!with_env(var, Some(""), Condition::ci_live));
+            assert!(!with_env(var, Some("false"), Condition::ci_live));
+            assert!(!with_env(var, Some("FALSE"), Condition::ci_live));
+        }
+
+        assert!(!Condition::ci_live());
+
+        // Combos use the cached conditions.
+        #[cfg(feature = "detect-tty")] {
+            let expected = Condition::ci() || Condition::stdouterr_are_tty();
+            assert_eq!(Condition::ci_or_tty_live(), expected);
+            assert_eq!((Condition::CI_OR_TTY)(), expected);
+        }
+    });
+}
+
+#[test]
+fn combinators() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering::SeqCst};
+
+    static A: AtomicBool = AtomicBool::new(false);
+    static B: AtomicBool = AtomicBool::new(false);
+    static CHECKS: AtomicUsize = AtomicUsize::new(0);
+
+    const COND_A: Condition = Condition(|| { CHECKS.fetch_add(1, SeqCst); A.load(SeqCst) });
+    const COND_B: Condition = Condition(|| { CHECKS.fetch_add(1, SeqCst); B.load(SeqCst) });
+
+    let (and, or, not) = (COND_A.and(COND_B), COND_A.or(COND_B), !COND_A);
+    for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+        A.store(a, SeqCst);
+        B.store(b, SeqCst);
+        assert_eq!(and(), a && b);
+        assert_eq!(or(), a || b);
+        assert_eq!(not(), !a);
+        assert_eq!((COND_A & COND_B)(), a && b);
+        assert_eq!((COND_A | COND_B)(), a || b);
+        assert_eq!((!(COND_A & !COND_B))(), !a || b);
+    }
+
+    // Evaluation short-circuits.
+    A.store(false, SeqCst);
+    CHECKS.store(0, SeqCst);
+    assert!(!and());
+    assert_eq!(CHECKS.load(SeqCst), 1);
+
+    A.store(true, SeqCst);
+    CHECKS.store(0, SeqCst);
+    assert!(or());
+    assert_eq!(CHECKS.load(SeqCst), 1);
+
+    // The same combination yields the same condition.
+    assert_eq!(COND_A.and(COND_B), and);
+    assert_ne!(COND_B.and(COND_A), and);
+    assert_ne!(COND_A.or(COND_B), and);
+    assert_eq!(!COND_A, not);
+
+    // Combining with `ALWAYS`, `NEVER`, or `self` is simplified.
+    assert_eq!(COND_A.and(Condition::ALWAYS), COND_A);
+    assert_eq!(Condition::ALWAYS.and(COND_A), COND_A);
+    assert_eq!(COND_A.and(Condition::NEVER), Condition::NEVER);
+    assert_eq!(Condition::NEVER.and(COND_A), Condition::NEVER);
+    assert_eq!(COND_A.and(COND_A), COND_A);
+    assert_eq!(COND_A.or(Condition::ALWAYS), Condition::ALWAYS);
+    assert_eq!(Condition::ALWAYS.or(COND_A), Condition::ALWAYS);
+    assert_eq!(COND_A.or(Condition::NEVER), COND_A);
+    assert_eq!(Condition::NEVER.or(COND_A), COND_A);
+    assert_eq!(COND_A.or(COND_A), COND_A);
+    assert_eq!(!Condition::ALWAYS, Condition::NEVER);
+    assert_eq!(!Condition::NEVER, Condition::ALWAYS);
+
+    // Repeated combinations don't exhaust the slots.
+    for _ in 0..1000 {
+        assert_eq!(COND_A.and(COND_B), and);
+    }
+}
+
+#[test]
+fn from_atomic() {
+    use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+    use yansi::{Paint, Style};
+
+    static FLAG: AtomicBool = AtomicBool::new(false);
+    static OTHER: AtomicBool = AtomicBool::new(true);
+
+    let condition = Condition::from_atomic(&FLAG).unwrap();
+    assert!(!condition());
+    FLAG.store(true, SeqCst);
+    assert!(condition());
+    FLAG.store(false, SeqCst);
+    assert!(!condition());
+
+    // The same flag yields the same condition; distinct flags don't.
+    assert_eq!(Condition::from_atomic(&FLAG), Some(condition));
+    let other = Condition::from_atomic(&OTHER).unwrap();
+    assert_ne!(other, condition);
+    assert!(other());
+
+    // Atomic conditions combine like any other.
+    let both = condition.and(other);
+    assert!(!both());
+    FLAG.store(true, SeqCst);
+    assert!(both());
+    assert!(!(!condition)());
+
+    // Toggling the flag toggles styling.
+    let style = Style::new().red().whenever(condition);
+    assert!(style.enabled());
+    FLAG.store(false, SeqCst);
+    assert!(!style.enabled());
+    assert_eq!("hi".paint(style).to_string(), "hi");
+    FLAG.store(true, SeqCst);
+    assert!(style.enabled());
+
+    for _ in 0..1000 {
+        assert_eq!(Condition::from_atomic(&FLAG), Some(condition));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn boxed() {
+    use std::sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering::SeqCst}};
+    use yansi::{Paint, Style};
+
+    struct Config {
+        color: AtomicBool,
+        checks: AtomicUsize,
+    }
+
+    let config = Arc::new(Config { color: AtomicBool::new(false), checks: AtomicUsize::new(0) });
+    let captured = Arc::clone(&config);
+    let condition = Condition::boxed(move || {
+        captured.checks.fetch_add(1, SeqCst);
+        captured.color.load(SeqCst)
+    }).unwrap();
+
+    // The closure is called on every check and sees updated state.
+    assert!(!condition());
+    config.color.store(true, SeqCst);
+    assert!(condition());
+    assert_eq!(config.checks.load(SeqCst), 2);
+
+    // The closure is kept alive, and never dropped, by the condition.
+    assert_eq!(Arc::strong_count(&config), 2);
+
+    // Distinct closures yield distinct conditions, even when identical.
+    assert_ne!(Condition::boxed(|| true), Condition::boxed(|| true));
+    assert_ne!(Condition::boxed(|| true), Some(condition));
+
+    // Boxed conditions combine and style like any other.
+    let never = Condition::boxed(|| false).unwrap();
+    assert!(!condition.and(never)());
+    assert!(condition.or(never)());
+    assert!((!never)());
+
+    let style = Style::new().red().whenever(condition);
+    assert!(style.enabled());
+    config.color.store(false, SeqCst);
+    assert!(!style.enabled());
+    assert_eq!("hi".paint(style).to_string(), "hi");
+
+    // They can be checked from any thread.
+    config.color.store(true, SeqCst);
+    assert!(std::thread::spawn(condition.0).join().unwrap());
+}
+
+#[test]
+#[cfg(feature = "detect-env")]
+fn color_level() {
+    use yansi::ColorLevel;
+
+    let _lock = ENV_LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    let level = |no_color, term, colorterm| with_env("NO_COLOR", no_color, || {
+        with_env("TERM", term, || with_env("COLORTERM", colorterm, || {
+            with_env("WT_SESSION", None, || with_env("ConEmuANSI", None, ColorLevel::from_env))
+        }))
+    });
+
+    assert_eq!(level(None, Some("xterm"), None), ColorLevel::Ansi16);
+    assert_eq!(level(None, None, None), ColorLevel::Ansi16);
+    assert_eq!(level(None, Some("xterm-256color"), None), ColorLevel::Ansi256);
+    assert_eq!(level(None, Some("screen-256color"), Some("")), ColorLevel::Ansi256);
+    assert_eq!(level(None, Some("xterm-256color"), Some("truecolor")), ColorLevel::TrueColor);
+    assert_eq!(level(None, Some("xterm"), Some("24bit")), ColorLevel::TrueColor);
+    assert_eq!(level(None, None, Some("TrueColor")), ColorLevel::TrueColor);
+    assert_eq!(level(None, Some("xterm"), Some("yes")), ColorLevel::Ansi16);
+
+    assert_eq!(level(Some("1"), Some("xterm-256color"), Some("truecolor")), ColorLevel::None);
+    assert_eq!(level(Some("0"), Some("xterm-256color"), None), ColorLevel::Ansi256);
+    assert_eq!(level(None, Some("dumb"), Some("truecolor")), ColorLevel::None);
+
+    #[cfg(feature = "detect-tty")]
+    with_env("NO_COLOR", None, || {
+        with_env("TERM", Some("xterm-256color"), || match Condition::stdout_is_tty_live() {
+            true => assert_eq!(yansi::detect_color_level(), ColorLevel::Ansi256),
+            false => assert_eq!(yansi::detect_color_level(), ColorLevel::None),
+        })
+    });
+}
+
+#[test]
+#[cfg(feature = "detect-env")]
+fn windows_color_level() {
+    use yansi::ColorLevel;
+
+    let _lock = ENV_LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    let level = |no_color, wt_session, conemu| with_env("NO_COLOR", no_color, || {
+        with_env("TERM", None, || with_env("COLORTERM", None, || {
+            with_env("WT_SESSION", wt_session, || with_env("ConEmuANSI", conemu, ColorLevel::from_env))
+        }))
+    });
+
+    assert_eq!(level(None, None, None), ColorLevel::Ansi16);
+    assert_eq!(level(None, None, Some("OFF")), ColorLevel::Ansi16);
+
+    #[cfg(windows)] {
+        assert_eq!(level(None, Some("5a1e2ef0-8c6e-4f5b-9d1c-2b7a4f3e6d10"), None), ColorLevel::TrueColor);
+        assert_eq!(level(None, Some(""), None), ColorLevel::TrueColor);
+        assert_eq!(level(None, None, Some("ON")), ColorLevel::TrueColor);
+        assert_eq!(level(None, Some("1"), Some("OFF")), ColorLevel::TrueColor);
+
+        // `NO_COLOR` still takes precedence.
+        assert_eq!(level(Some("1"), Some("1"), Some("ON")), ColorLevel::None);
+    }
+
+    #[cfg(not(windows))] {
+        assert_eq!(level(None, Some("5a1e2ef0-8c6e-4f5b-9d1c-2b7a4f3e6d10"), None), ColorLevel::Ansi16);
+        assert_eq!(level(None, None, Some("ON")), ColorLevel::Ansi16);
+    }
+}
+
+#[test]
+#[cfg(feature = "detect-tty")]
+fn from_tty() {
+    use yansi::Paint;
+
+    let file = std::fs::File::open(file!()).unwrap();
+    let condition = Condition::from_tty(&file);
+    assert_eq!(condition, Condition::NEVER);
+    assert_eq!(Condition::from_tty(file), condition);
+    assert_eq!("hi".red().whenever(condition).to_string(), "hi");
+}
+
+#[test]
+#[cfg(feature = "detect-env")]
+fn recheck() {
+    let _lock = ENV_LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+
+    with_env("CLICOLOR", Some("0"), || {
+        Condition::recheck();
+        assert!(!Condition::clicolor());
+    });
+    with_env("CLICOLOR", Some("1"), || {
+        assert!(!Condition::clicolor());
+        assert!(Condition::clicolor_live());
+
+        Condition::recheck();
+        assert!(Condition::clicolor());
+        assert!(Condition::CLICOLOR());
+    });
+
+    with_env("CLICOLOR", Some("0"), || {
+        assert!(Condition::clicolor());
+        Condition::recheck();
+        assert!(!Condition::clicolor());
+    });
+
+    Condition::recheck();
+}
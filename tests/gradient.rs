@@ -0,0 +1,53 @@
+#![cfg(feature = "std")]
+
+use yansi::Color::*;
+
+#[test]
+fn gradient() {
+    let (start, end) = (Rgb(255, 0, 0), Rgb(0, 0, 255));
+
+    yansi::with_enabled(true, || {
+        let text = yansi::gradient("rainbow", start, end);
+        assert!(text.starts_with("\x1B[38;2;255;0;0mr"));
+        assert!(text.ends_with("\x1B[38;2;0;0;255mw\x1B[0m"));
+        assert_eq!(text.matches("\x1B[38;2;").count(), 7);
+        assert_eq!(yansi::strip(&text), "rainbow");
+
+        // Colors are per `char`, whitespace is skipped, and repeats elided.
+        let text = yansi::gradient("é ü", start, end);
+        assert_eq!(text, "\x1B[38;2;255;0;0mé \x1B[38;2;0;0;255mü\x1B[0m");
+        let text = yansi::gradient("aaa", start, start);
+        assert_eq!(text, "\x1B[38;2;255;0;0maaa\x1B[0m");
+
+        assert_eq!(yansi::gradient("x", start, end), "\x1B[38;2;255;0;0mx\x1B[0m");
+        assert_eq!(yansi::gradient("", start, end), "");
+        assert_eq!(yansi::gradient("  ", start, end), "  ");
+        assert_eq!(yansi::gradient("ab", Primary, end), "\x1B[39mab\x1B[0m");
+    });
+
+    yansi::with_enabled(false, || {
+        assert_eq!(yansi::gradient("rainbow", start, end), "rainbow");
+    });
+}
+
+#[test]
+fn rainbow() {
+    yansi::with_enabled(true, || {
+        let text = yansi::rainbow("abcdefgh");
+        assert_eq!(yansi::strip(&text), "abcdefgh");
+        assert_eq!(text.matches("\x1B[3").count(), 8);
+        for code in ["31", "33", "32", "36", "34", "35"] {
+            assert!(text.contains(&format!("\x1B[{}m", code)));
+        }
+
+        assert!(text.starts_with("\x1B[31ma\x1B[33mb"));
+        assert!(text.ends_with("\x1B[35mf\x1B[31mg\x1B[33mh\x1B[0m"));
+
+        let text = yansi::rainbow("hello, world! ☺");
+        assert_eq!(yansi::strip(&text), "hello, world! ☺");
+        assert!(text.contains("\x1B[35m, \x1B[33mw"));
+        assert_eq!(yansi::rainbow(" \n"), " \n");
+    });
+
+    yansi::with_enabled(false, || assert_eq!(yansi::rainbow("a b"), "a b"));
+}
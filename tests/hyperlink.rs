@@ -0,0 +1,160 @@
+#![cfg(feature = "hyperlink")]
+
+use yansi::{Paint, Condition, Style, Color::*};
+use yansi::hyperlink::HyperlinkExt;
+
+#[test]
+fn link() {
+    yansi::with_enabled(true, || {
+        let link = "docs".link("https://docs.rs").to_string();
+        assert_eq!(link, "\x1B]8;;https://docs.rs\x1B\\docs\x1B]8;;\x1B\\");
+
+        let link = "docs".green().link("https://docs.rs").to_string();
+        assert_eq!(link, "\x1B]8;;https://docs.rs\x1B\\\x1B[32mdocs\x1B[0m\x1B]8;;\x1B\\");
+    });
+
+    yansi::with_enabled(false, || {
+        assert_eq!("docs".link("https://docs.rs").to_string(), "docs");
+    });
+
+    let link = "docs".link("https://docs.rs").whenever(Condition::NEVER);
+    assert_eq!(link.to_string(), "docs");
+}
+
+#[test]
+fn link_id() {
+    yansi::with_enabled(true, || {
+        let link = "docs".link("https://docs.rs").id("ref-1").to_string();
+        assert_eq!(link, "\x1B]8;id=ref-1;https://docs.rs\x1B\\docs\x1B]8;;\x1B\\");
+
+        let link = "docs".link("https://docs.rs").id(1).red().to_string();
+        assert_eq!(link, "\x1B]8;id=1;https://docs.rs\x1B\\\x1B[31mdocs\x1B[0m\x1B]8;;\x1B\\");
+
+        let link = "docs".red().link("https://docs.rs").id("a").id("b").to_string();
+        assert!(link.starts_with("\x1B]8;id=b;https://docs.rs\x1B\\"));
+    });
+
+    yansi::with_enabled(false, || {
+        assert_eq!("docs".link("https://docs.rs").id("ref-1").to_string(), "docs");
+    });
+}
+
+#[test]
+fn link_escaping() {
+    fn opening(link: impl std::fmt::Display) -> String {
+        let link = yansi::with_enabled(true, || link.to_string());
+        let end = link.find("\x1B\\").expect("terminated opening sequence");
+        link[..end + 2].to_string()
+    }
+
+    // Spaces and non-ASCII characters are percent-encoded.
+    assert_eq!(opening("x".link("https://a.b/my docs")), "\x1B]8;;https://a.b/my%20docs\x1B\\");
+    assert_eq!(opening("x".link("https://a.b/ü")), "\x1B]8;;https://a.b/%C3%BC\x1B\\");
+    assert_eq!(opening("x".link("https://a.b/🦀")), "\x1B]8;;https://a.b/%F0%9F%A6%80\x1B\\");
+
+    // Control characters are stripped, including those that end the sequence.
+    assert_eq!(opening("x".link("https://a.b/\x1B\\c")), "\x1B]8;;https://a.b/\\c\x1B\\");
+    assert_eq!(opening("x".link("https://a.b/\x07c\n\t\x7F")), "\x1B]8;;https://a.b/c\x1B\\");
+
+    // Everything else, including existing escapes, is left as-is.
+    let url = "https://a.b/p%20q?x=1&y=[2]#~frag";
+    assert_eq!(opening("x".link(url)), format!("\x1B]8;;{}\x1B\\", url));
+
+    // Ids lose control characters and parameter delimiters.
+    let link = "x".link("https://a.b").id("a;b:c\x1Bd");
+    assert_eq!(opening(link), "\x1B]8;id=abcd;https://a.b\x1B\\");
+
+    // The whole link is always stripped cleanly.
+    let red = "x".red();
+    let link = red.link("https://a.b/\x1B]8;;evil\x07 ü").id("\x1B\\");
+    let link = yansi::with_enabled(true, || link.to_string());
+    assert_eq!(yansi::strip(&link), "x");
+}
+
+#[test]
+fn linked_style() {
+    static DOCS: Style = Green.link("https://docs.rs");
+    static PLAIN: Style = Style::new().link("https://docs.rs/my docs");
+
+    yansi::with_enabled(true, || {
+        let link = "x".paint(DOCS).to_string();
+        assert_eq!(link, "\x1B]8;;https://docs.rs\x1B\\\x1B[32mx\x1B[0m\x1B]8;;\x1B\\");
+
+        let link = "x".paint(PLAIN).to_string();
+        assert_eq!(link, "\x1B]8;;https://docs.rs/my%20docs\x1B\\x\x1B]8;;\x1B\\");
+
+        let link = "x".paint(DOCS).linger().to_string();
+        assert_eq!(link, "\x1B]8;;https://docs.rs\x1B\\\x1B[32mx");
+
+        let link = "x".paint(PLAIN).resetting().to_string();
+        assert_eq!(link, "\x1B]8;;https://docs.rs/my%20docs\x1B\\x\x1B[0m\x1B]8;;\x1B\\");
+
+        assert_eq!(format!("{:>3}", "x".paint(PLAIN)), format!("  {}", "x".paint(PLAIN)));
+        assert_eq!(DOCS.prefix(), "\x1B]8;;https://docs.rs\x1B\\\x1B[32m");
+        assert_eq!(DOCS.suffix(), "\x1B[0m\x1B]8;;\x1B\\");
+    });
+
+    yansi::with_enabled(false, || assert_eq!("x".paint(DOCS).to_string(), "x"));
+    assert_eq!("x".paint(DOCS).whenever(Condition::NEVER).to_string(), "x");
+
+    // Links affect equality, ordering, and hashing.
+    assert_ne!(DOCS, Green.foreground());
+    assert_eq!(DOCS, Green.link("https://docs.rs"));
+    assert_ne!(DOCS, Green.link("https://docs.rs/yansi"));
+    assert!(Style::new().link("https://a.b") > Style::new());
+
+    let set: std::collections::HashSet<_> = [DOCS, Green.link("https://docs.rs"), Green.foreground()]
+        .into_iter()
+        .collect();
+
+    assert_eq!(set.len(), 2);
+
+    // The overriding style's link wins.
+    assert_eq!(DOCS.combine(Style::new().bold()).link, Some("https://docs.rs"));
+    assert_eq!(DOCS.combine(PLAIN).link, PLAIN.link);
+    assert_eq!(Style::new().combine(DOCS).link, DOCS.link);
+}
+
+#[test]
+fn linked_html() {
+    static DOCS: Style = Style::new().underline().link("https://docs.rs/yansi?a=1&b=\"2\"");
+
+    assert_eq!("<docs>".paint(DOCS).to_html(),
+        "<a href=\"https://docs.rs/yansi?a=1&amp;b=&quot;2&quot;\">\
+        <span style=\"text-decoration:underline\">&lt;docs&gt;</span></a>");
+
+    let plain = Style::new().link("https://a.b/c d");
+    assert_eq!("x".paint(plain).to_html(), "<a href=\"https://a.b/c%20d\">x</a>");
+    assert_eq!("x".paint(Red.link("https://a.b")).to_html(),
+        "<a href=\"https://a.b\"><span style=\"color:red\">x</span></a>");
+}
+
+#[test]
+fn linked_transition() {
+    fn transition(from: Style, to: Style) -> String {
+        let mut string = String::new();
+        to.fmt_transition(&from, &mut string).unwrap();
+        string
+    }
+
+    // Links are opened, replaced, and closed before any SGR sequence.
+    let (a, b) = (Red.link("https://a.b"), Red.link("https://c.d"));
+    assert_eq!(transition(Red.foreground(), a), "\x1B]8;;https://a.b\x1B\\");
+    assert_eq!(transition(a, b), "\x1B]8;;https://c.d\x1B\\");
+    assert_eq!(transition(a, Red.foreground()), "\x1B]8;;\x1B\\");
+    assert_eq!(transition(a, Red.bold()), "\x1B]8;;\x1B\\\x1B[1m");
+    assert_eq!(transition(a, Style::new()), "\x1B]8;;\x1B\\\x1B[0m");
+    assert_eq!(transition(Style::new(), a), "\x1B]8;;https://a.b\x1B\\\x1B[31m");
+    assert_eq!(transition(a, a.bold()), "\x1B[1m");
+
+    // A `StyledBuf` doesn't lose links.
+    let mut buf = yansi::StyledBuf::new();
+    yansi::with_enabled(true, || {
+        buf.push("a", a);
+        buf.push("b", Red.foreground());
+        buf.push("c", b.bold());
+    });
+
+    assert_eq!(buf.finish(), "\x1B]8;;https://a.b\x1B\\\x1B[31ma\x1B]8;;\x1B\\b\
+        \x1B]8;;https://c.d\x1B\\\x1B[1mc\x1B]8;;\x1B\\\x1B[0m");
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "detect-env")]
+
+// `init()` caches its environment checks for the life of the process, so this
+// test lives in its own binary to control the environment before first use.
+
+use yansi::{Paint, Condition};
+
+#[test]
+fn no_color_suppresses_after_init() {
+    std::env::set_var("NO_COLOR", "1");
+    std::env::set_var("CLICOLOR_FORCE", "1");
+
+    yansi::enable();
+    assert_eq!("hi".red().to_string(), "\x1B[31mhi\x1B[0m");
+
+    yansi::init();
+    assert!(!yansi::is_enabled());
+    assert_eq!("hi".red().to_string(), "hi");
+    assert_eq!(" 🎨".mask().to_string(), "");
+
+    // A style's condition can't override the global one, but `enable()` can.
+    assert_eq!("hi".red().whenever(Condition::ALWAYS).to_string(), "hi");
+    yansi::enable();
+    assert_eq!("hi".red().to_string(), "\x1B[31mhi\x1B[0m");
+
+    yansi::init();
+    assert!(!yansi::is_enabled());
+
+    // Only `stdout` is checked, so a redirected `stderr` doesn't matter.
+    std::env::remove_var("NO_COLOR");
+    Condition::recheck();
+    yansi::init();
+
+    #[cfg(feature = "detect-tty")]
+    assert_eq!(yansi::is_enabled(), Condition::os_support() && Condition::stdout_is_tty());
+
+    #[cfg(not(feature = "detect-tty"))]
+    assert_eq!(yansi::is_enabled(), Condition::os_support());
+}
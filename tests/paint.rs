@@ -0,0 +1,904 @@
+use std::cell::Cell;
+
+use yansi::{Paint, PaintEach, Painted, Style, Condition, Color::*};
+
+static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Runs `f` with styling globally enabled.
+fn enabled<R>(f: impl FnOnce() -> R) -> R {
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+    yansi::enable();
+    let result = f();
+    yansi::whenever(Condition::DEFAULT);
+    result
+}
+
+#[test]
+fn paint_each() {
+    static STYLE: Style = Red.bold();
+
+    let items = ["a", "b", "c"];
+    let painted: Vec<Painted<&&str>> = items.iter().paint_each(STYLE).collect();
+    assert_eq!(painted.len(), 3);
+    for (painted, item) in painted.iter().zip(items.iter()) {
+        assert!(std::ptr::eq(painted.value, item));
+        assert_eq!(painted.style, STYLE);
+    }
+
+    enabled(|| {
+        let expected: Vec<_> = items.iter().map(|i| i.paint(STYLE).to_string()).collect();
+        let actual: Vec<_> = items.iter().paint_each(STYLE).map(|p| p.to_string()).collect();
+        assert_eq!(actual, expected);
+        assert_eq!(actual[0], "\x1B[1;31ma\x1B[0m");
+
+        let owned: Vec<_> = (1..=2).paint_each(Green).map(|p| p.to_string()).collect();
+        assert_eq!(owned, ["\x1B[32m1\x1B[0m", "\x1B[32m2\x1B[0m"]);
+    });
+
+    // Adapters are preserved.
+    let mut iter = items.iter().paint_each(STYLE);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back().map(|p| *p.value), Some("c"));
+    assert_eq!(iter.len(), 2);
+
+    // The iterator is lazy.
+    let count = Cell::new(0);
+    let mut iter = items.iter().inspect(|_| count.set(count.get() + 1)).paint_each(STYLE);
+    assert_eq!(count.get(), 0);
+    assert!(iter.next().is_some());
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn padding() {
+    enabled(|| {
+        assert_eq!(format!("{:>6}", "hi".red()), "    \x1B[31mhi\x1B[0m");
+        assert_eq!(format!("{:<6}|", "hi".red()), "\x1B[31mhi\x1B[0m    |");
+        assert_eq!(format!("{:^6}|", "hi".red()), "  \x1B[31mhi\x1B[0m  |");
+        assert_eq!(format!("{:^7}|", "hi".red()), "  \x1B[31mhi\x1B[0m   |");
+        assert_eq!(format!("{:*>5}", "hi".red()), "***\x1B[31mhi\x1B[0m");
+        assert_eq!(format!("{:>1$}", "hi".red(), 4), "  \x1B[31mhi\x1B[0m");
+
+        // Default alignments are those of the value.
+        assert_eq!(format!("{:6}|", "hi".red()), "\x1B[31mhi\x1B[0m    |");
+        assert_eq!(format!("{:6}|", 42.red()), "    \x1B[31m42\x1B[0m|");
+        assert_eq!(format!("{:6?}|", 42.red()), "    \x1B[31m42\x1B[0m|");
+        assert_eq!(format!("{:6?}|", "hi".red()), "\x1B[31m\"hi\"\x1B[0m|");
+
+        // Values at or above the width aren't padded.
+        assert_eq!(format!("{:>2}", "hi".red()), "\x1B[31mhi\x1B[0m");
+        assert_eq!(format!("{:>1}", "hello".red()), "\x1B[31mhello\x1B[0m");
+
+        // Other flags are preserved.
+        assert_eq!(format!("{:>8.2}", 1.2345.red()), "    \x1B[31m1.23\x1B[0m");
+        assert_eq!(format!("{:<6.3}|", "hello".red()), "\x1B[31mhel\x1B[0m   |");
+        assert_eq!(format!("{:>6x}", 255.red()), "    \x1B[31mff\x1B[0m");
+        assert_eq!(format!("{:>6}", 5.red()), "     \x1B[31m5\x1B[0m");
+        assert_eq!(format!("{:>+6}", 5.red()), "    \x1B[31m+5\x1B[0m");
+        assert_eq!(format!("{:>#6x}", 255.red()), "  \x1B[31m0xff\x1B[0m");
+
+        // Zero-padding is part of the value.
+        assert_eq!(format!("{:06}", (-5).red()), "\x1B[31m-00005\x1B[0m");
+
+        // The width reaches the value, which pads itself as it would unstyled.
+        assert_eq!(format!("{:12x?}", vec![255u8].red()), "\x1B[31m[          ff]\x1B[0m");
+        assert_eq!(format!("{:*>4?}|", [1].red()), "\x1B[31m[***1]\x1B[0m|");
+        assert_eq!(format!("{:>6}", " hi".red()), "   \x1B[31m hi\x1B[0m");
+        assert_eq!(format!("{:<6}|", "hi ".red()), "\x1B[31mhi \x1B[0m   |");
+        assert_eq!(format!("{:8}|", " a ".on_red()), "\x1B[41m a \x1B[0m     |");
+        assert_eq!(format!("{:7}|", 42.on_red()), "     \x1B[41m42\x1B[0m|");
+        assert_eq!(format!("{:^7}|", " a ".on_red()), "  \x1B[41m a \x1B[0m  |");
+        assert_eq!(format!("{:8}|", "   ".on_red()), "\x1B[41m   \x1B[0m     |");
+
+        // Characters in the value are never mistaken for padding.
+        assert_eq!(format!("{:>5}", "a\u{10FFFE}b".red()), "  \x1B[31ma\u{10FFFE}b\x1B[0m");
+        assert_eq!(format!("{:*<5}", "\u{10FFFE}b".red()), "\x1B[31m\u{10FFFE}b\x1B[0m***");
+
+        // Long values are padded all the same.
+        let long = "-".repeat(200);
+        assert_eq!(format!("{:>201}", long.red()), format!(" \x1B[31m{}\x1B[0m", long));
+        assert_eq!(format!("{:>202}", long.red().to_string().bold()),
+            format!("  \x1B[1m\x1B[31m{}\x1B[0m\x1B[0m", long));
+
+        // Nested styling doesn't count toward the width.
+        let inner = "hi".blue().to_string();
+        assert_eq!(format!("{:>4}", inner.bold()), "  \x1B[1m\x1B[34mhi\x1B[0m\x1B[0m");
+
+        // Values that ignore the width aren't padded.
+        struct Fixed;
+
+        impl std::fmt::Display for Fixed {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("fixed")
+            }
+        }
+
+        assert_eq!(format!("{:>8}", Fixed.red()), "\x1B[31mfixed\x1B[0m");
+
+        // Without styling, the value pads itself.
+        assert_eq!(format!("{:>6}", "hi".red().whenever(Condition::NEVER)), "    hi");
+        assert_eq!(format!("{:6}|", "hi".red().whenever(Condition::NEVER)), "hi    |");
+    });
+}
+
+#[test]
+fn padding_fill() {
+    enabled(|| {
+        let title = " TITLE ".bold();
+        assert_eq!(format!("{:=^11}", title), "==\x1B[1m TITLE \x1B[0m==");
+        assert_eq!(format!("{:=^12}", title), "==\x1B[1m TITLE \x1B[0m===");
+        assert_eq!(format!("{:=<11}", title), "\x1B[1m TITLE \x1B[0m====");
+        assert_eq!(format!("{:=>11}", title), "====\x1B[1m TITLE \x1B[0m");
+
+        // Multi-byte fill characters are written whole.
+        assert_eq!(format!("{:─^9}", "hi".red()), "───\x1B[31mhi\x1B[0m────");
+        assert_eq!(format!("{:🎨<4}", "hi".red()), "\x1B[31mhi\x1B[0m🎨🎨");
+
+        // Fill is written outside of wrapped styling, too.
+        let inner = "b".green().to_string();
+        assert_eq!(format!("{:.>4}", inner.red().wrap()),
+            "...\x1B[31m\x1B[32mb\x1B[0m\x1B[31m\x1B[0m");
+
+        // Without styling, the value pads itself with the same fill.
+        assert_eq!(format!("{:=^11}", title.whenever(Condition::NEVER)), "== TITLE ==");
+        assert_eq!(format!("{:-<4}", "hi".red().whenever(Condition::NEVER)), "hi--");
+    });
+}
+
+#[test]
+fn paint_fn() {
+    use std::fmt::Write;
+
+    let parts = ["a", "b", "c"];
+    let joined = yansi::paint_fn(Red.bold(), |f| {
+        for (i, part) in parts.iter().enumerate() {
+            if i != 0 { f.write_char('-')?; }
+            write!(f, "{}", part)?;
+        }
+
+        Ok(())
+    });
+
+    enabled(|| {
+        assert_eq!(joined.to_string(), format!("{}", "a-b-c".red().bold()));
+        assert_eq!(joined.to_string(), "\x1B[1;31ma-b-c\x1B[0m");
+        assert_eq!(joined.italic().to_string(), "a-b-c".red().bold().italic().to_string());
+
+        // Like any value that ignores the width, the closure isn't padded.
+        assert_eq!(format!("{:>7}", joined), "\x1B[1;31ma-b-c\x1B[0m");
+        let padded = yansi::paint_fn(Red, |f| f.pad("hi"));
+        assert_eq!(format!("{:>4}", padded), "  \x1B[31mhi\x1B[0m");
+
+        let masked = yansi::paint_fn(Style::new().mask(), |f| f.write_str("hidden"));
+        assert_eq!(masked.to_string(), "hidden");
+    });
+
+    let disabled = joined.whenever(Condition::NEVER);
+    assert_eq!(disabled.to_string(), "a-b-c");
+
+    let masked = yansi::paint_fn(Red.mask(), |f| f.write_str("hidden"));
+    assert_eq!(masked.whenever(Condition::NEVER).to_string(), "");
+
+    // The closure is called on each display, not before.
+    let calls = Cell::new(0);
+    let counted = yansi::paint_fn(Green, |f| {
+        calls.set(calls.get() + 1);
+        f.write_str("x")
+    });
+
+    assert_eq!(calls.get(), 0);
+    let _ = counted.to_string();
+    let _ = counted.to_string();
+    assert_eq!(calls.get(), 2);
+
+    // Padding calls it once more, without the width, to measure the padding,
+    // and once more again if it writes styling, to widen the width.
+    let calls = Cell::new(0);
+    let padded = yansi::paint_fn(Green, |f| {
+        calls.set(calls.get() + 1);
+        f.pad("x")
+    });
+
+    enabled(|| {
+        assert_eq!(format!("{:>3}", padded), "  \x1B[32mx\x1B[0m");
+        assert_eq!(calls.get(), 2);
+
+        let bold = "x".bold().to_string();
+        let styled = yansi::paint_fn(Green, |f| {
+            calls.set(calls.get() + 1);
+            f.pad(&bold)
+        });
+
+        assert_eq!(format!("{:>3}", styled), "  \x1B[32m\x1B[1mx\x1B[0m\x1B[0m");
+        assert_eq!(calls.get(), 5);
+
+        // Without padding, it's called once, as are wrapping and stripping.
+        assert_eq!(format!("{:>1}", padded), "\x1B[32mx\x1B[0m");
+        assert_eq!(calls.get(), 6);
+        assert_eq!(styled.wrap().to_string(), "\x1B[32m\x1B[1mx\x1B[0m\x1B[32m\x1B[0m");
+        assert_eq!(calls.get(), 7);
+        assert_eq!(styled.wrap().whenever(Condition::NEVER).to_string(), "x");
+        assert_eq!(calls.get(), 8);
+    });
+}
+
+#[test]
+fn map() {
+    let painted: Painted<String> = 1.red().map(|n| format!("#{}", n + 1));
+    assert_eq!(painted.value, "#2");
+    assert_eq!(painted.style, Red.foreground());
+
+    let painted = "hi".red().on_blue().bold().linger().map(str::len);
+    assert_eq!(painted.style, 2.red().on_blue().bold().style);
+    enabled(|| {
+        assert_eq!(1.red().map(|n| n.to_string()).to_string(), "\x1B[31m1\x1B[0m");
+        assert_eq!(painted.to_string(), "\x1B[1;44;31m2");
+    });
+}
+
+#[test]
+fn style_mut() {
+    let mut painted = "hi".red();
+    assert_eq!(painted.style_ref(), &Red.foreground());
+
+    *painted.style_mut() = painted.style.on_blue();
+    painted.style_mut().foreground = Some(Green);
+    assert_eq!(painted.style_ref(), &Green.on_blue());
+
+    let style = painted.style_mut();
+    *style = (*style).underline().linger();
+    enabled(|| assert_eq!(painted.to_string(), "\x1B[4;44;32mhi"));
+}
+
+#[test]
+fn format_args() {
+    fn show(args: std::fmt::Arguments<'_>) -> String {
+        args.red().underline().to_string()
+    }
+
+    fn pad<T: std::fmt::Display>(value: T) -> String {
+        format!("[{:<4}]", value)
+    }
+
+    let (a, b) = (1, "two");
+    enabled(|| {
+        let string = format!("{}", format_args!("{}+{}", a, b).red());
+        assert_eq!(string, "\x1B[31m1+two\x1B[0m");
+        assert_eq!(show(format_args!("{:>3}", a)), "\x1B[4;31m  1\x1B[0m");
+
+        // Like `Arguments` itself, the styled value ignores the width.
+        assert_eq!(pad(format_args!("{}", a)), "[1]");
+        assert_eq!(pad(format_args!("{}", a).blue()), "[\x1B[34m1\x1B[0m]");
+
+        let string = format_args!("<{}>", b.green()).red().wrap().to_string();
+        assert_eq!(string, "\x1B[31m<\x1B[32mtwo\x1B[0m\x1B[31m>\x1B[0m");
+    });
+}
+
+#[test]
+fn hex() {
+    assert_eq!("x".new().fg_hex("#ff0000").style, "x".rgb(255, 0, 0).style);
+    assert_eq!("x".new().bg_hex("00f").style, "x".on_rgb(0, 0, 255).style);
+    assert_eq!("x".red().bold().bg_hex("#1e90ff").style, Red.on_rgb(30, 144, 255).bold());
+    assert_eq!(Style::new().fg_hex("#123").bg_hex("#456"), Rgb(17, 34, 51).on_rgb(68, 85, 102));
+
+    // Invalid colors leave the style as it was.
+    assert_eq!("x".new().fg_hex("#ff00").style, Style::new());
+    assert_eq!("x".red().fg_hex("red").style, Red.foreground());
+    assert_eq!(Blue.on_red().bg_hex("#").fg_hex(""), Blue.on_red());
+
+    enabled(|| {
+        assert_eq!("x".new().fg_hex("#ff0000").to_string(), "x".rgb(255, 0, 0).to_string());
+        assert_eq!("x".new().fg_hex("#ff0000").to_string(), "\x1B[38;2;255;0;0mx\x1B[0m");
+        assert_eq!("x".new().fg_hex("oops").to_string(), "x");
+    });
+}
+
+#[test]
+fn with_style() {
+    use yansi::Quirk;
+
+    let painted = "hi".red().on_blue().bold().linger().mask().whenever(Condition::NEVER);
+    let painted = painted.with_style(Green.italic());
+    assert_eq!(painted.style, Green.italic());
+    assert_eq!(painted.style.condition, None);
+    assert_eq!(painted.style.quirks().count(), 0);
+    assert_eq!(painted.value, "hi");
+
+    let painted = painted.with_style(Style::new().wrap()).on_red();
+    assert_eq!(painted.style, Style::new().on_red());
+    assert!(painted.style.quirks().eq([Quirk::Wrap]));
+
+    enabled(|| {
+        let painted = "hi".red().underline().with_style(Blue.bold());
+        assert_eq!(painted.to_string(), "\x1B[1;34mhi\x1B[0m");
+        assert_eq!(painted.with_style(Style::new()).to_string(), "hi");
+    });
+}
+
+#[test]
+fn pretty_debug() {
+    let pretty = "[\n    1,\n    2,\n]";
+    assert_eq!(format!("{:#?}", [1, 2]), pretty);
+
+    enabled(|| {
+        assert_eq!(format!("{:#?}", [1, 2].red()), format!("\x1B[31m{}\x1B[0m", pretty));
+        assert_eq!(format!("{:?}", [1, 2].red()), "\x1B[31m[1, 2]\x1B[0m");
+        assert_eq!(format!("{:#?}", [1, 2].red().wrap()), format!("\x1B[31m{}\x1B[0m", pretty));
+        assert_eq!(format!("{:#x}", 255.red().wrap()), "\x1B[31m0xff\x1B[0m");
+
+        // The flags also reach wrapped values with styled contents.
+        let nested = [1.green(), 2.green()];
+        let expected = "\x1B[1m[\n    \x1B[32m1\x1B[0m\x1B[1m,\n    \x1B[32m2\x1B[0m\x1B[1m,\n]\x1B[0m";
+        assert_eq!(format!("{:#?}", nested.bold().wrap()), expected);
+
+        // As do the debug hex flags. These are read from the deprecated
+        // `Formatter::flags()`, so these assertions pin its bit layout: if it
+        // changes, the flags are dropped and values format as with `{:?}`.
+        let bytes = [10u8, 255];
+        assert_eq!(format!("{:x?}", bytes.red()), "\x1B[31m[a, ff]\x1B[0m");
+        assert_eq!(format!("{:X?}", bytes.red().wrap()), "\x1B[31m[A, FF]\x1B[0m");
+        let nested = [10.green(), 255.green()];
+        let expected = "\x1B[1m[\x1B[32ma\x1B[0m\x1B[1m, \x1B[32mff\x1B[0m\x1B[1m]\x1B[0m";
+        assert_eq!(format!("{:x?}", nested.bold().wrap()), expected);
+        let expected = "\x1B[1m[\n    \x1B[32m0xA\x1B[0m\x1B[1m,\n    \x1B[32m0xFF\x1B[0m\x1B[1m,\n]\x1B[0m";
+        assert_eq!(format!("{:#X?}", nested.bold().wrap()), expected);
+    });
+
+    assert_eq!(format!("{:#?}", [1, 2].red().whenever(Condition::NEVER)), pretty);
+    let nested = [1.green(), 2.green()];
+    let stripped = format!("{:#?}", nested.bold().wrap().whenever(Condition::NEVER));
+    assert_eq!(stripped, pretty);
+
+    let nested = [10.green(), 255.green()];
+    assert_eq!(format!("{:x?}", nested.bold().wrap().whenever(Condition::NEVER)), "[a, ff]");
+}
+
+#[test]
+fn numeric_flags() {
+    let x = 1.23456;
+    enabled(|| {
+        for painted in [x.red(), x.red().wrap()] {
+            assert_eq!(format!("{:+.2}", painted), "\x1B[31m+1.23\x1B[0m");
+            assert_eq!(format!("{:.0}", painted), "\x1B[31m1\x1B[0m");
+            assert_eq!(format!("{:08.3}", painted), "\x1B[31m0001.235\x1B[0m");
+            assert_eq!(format!("{:+09.3}", painted), "\x1B[31m+0001.235\x1B[0m");
+            assert_eq!(format!("{:08.3e}", painted), "\x1B[31m01.235e0\x1B[0m");
+            assert_eq!(format!("{:+.1E}", painted), "\x1B[31m+1.2E0\x1B[0m");
+            assert_eq!(format!("{:>+9.2e}", painted), "  \x1B[31m+1.23e0\x1B[0m");
+        }
+
+        assert_eq!(format!("{:+06}", (-42).red()), "\x1B[31m-00042\x1B[0m");
+        assert_eq!(format!("{:+}", 42.red().bold()), "\x1B[1;31m+42\x1B[0m");
+        assert_eq!(format!("{:#010b}", 5.red()), "\x1B[31m0b00000101\x1B[0m");
+        assert_eq!(format!("{:+.3e}", 1234.5.red()), "\x1B[31m+1.234e3\x1B[0m");
+
+        // Wrapped values with styled contents are padded by visible width.
+        let inner = "hi".green().to_string();
+        let expected = "  \x1B[1m\x1B[32mhi\x1B[0m\x1B[1m\x1B[0m|";
+        assert_eq!(format!("{:>4}|", inner.bold().wrap()), expected);
+        let expected = "\x1B[1m\x1B[32mhi\x1B[0m\x1B[1m\x1B[0m  |";
+        assert_eq!(format!("{:<4}|", inner.bold().wrap()), expected);
+        assert_eq!(format!("{:>2}|", inner.bold().wrap()), "\x1B[1m\x1B[32mhi\x1B[0m\x1B[1m\x1B[0m|");
+
+        // As are those that zero-pad themselves.
+        let nested = Painted::new(5.green()).bold().wrap();
+        assert_eq!(format!("{:05}", nested), "\x1B[1m\x1B[32m00005\x1B[0m\x1B[1m\x1B[0m");
+        assert_eq!(format!("{:+06}", nested), "\x1B[1m\x1B[32m+00005\x1B[0m\x1B[1m\x1B[0m");
+        let expected = "\x1B[1m\x1B[32m-0001.5\x1B[0m\x1B[1m\x1B[0m";
+        assert_eq!(format!("{:07.1}", Painted::new((-1.5).green()).bold().wrap()), expected);
+    });
+
+    let painted = x.red().whenever(Condition::NEVER);
+    assert_eq!(format!("{:+.2}", painted), "+1.23");
+    assert_eq!(format!("{:08.3e}", painted), "01.235e0");
+    assert_eq!(format!("{:+08.2}", painted.wrap()), "+0001.23");
+    let nested = Painted::new(5.green()).bold().wrap().whenever(Condition::NEVER);
+    assert_eq!(format!("{:05}", nested), "00005");
+}
+
+#[test]
+fn owned() {
+    struct Row {
+        label: Painted<String>,
+        count: Painted<usize>,
+    }
+
+    fn row(name: &str, count: usize) -> Row {
+        let label = format!("{}:", name).into_painted().bold().underline();
+        Row { label, count: count.into_painted().green() }
+    }
+
+    let rows = [row("apples", 3), row("pears", 10)];
+    assert_eq!(rows[0].label.value, "apples:");
+    assert_eq!(rows[0].label.style, Style::new().bold().underline());
+    assert_eq!(rows[1].count.value, 10);
+
+    let rendered = std::thread::spawn(move || {
+        enabled(|| rows.iter().map(|r| format!("{} {}", r.label, r.count)).collect::<Vec<_>>())
+    }).join().unwrap();
+
+    assert_eq!(rendered, [
+        "\x1B[1;4mapples:\x1B[0m \x1B[32m3\x1B[0m",
+        "\x1B[1;4mpears:\x1B[0m \x1B[32m10\x1B[0m",
+    ]);
+}
+
+#[test]
+fn send_sync_static() {
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+    fn assert_static<T: 'static>() {}
+    fn assert_storable<T: Send + Sync + 'static>() {}
+
+    assert_storable::<Style>();
+    assert_storable::<Condition>();
+    assert_storable::<yansi::Color>();
+    assert_storable::<yansi::Attribute>();
+    assert_storable::<yansi::Quirk>();
+
+    assert_storable::<Painted<String>>();
+    assert_storable::<Painted<&'static str>>();
+    assert_storable::<Painted<i32>>();
+    assert_storable::<Painted<std::sync::Arc<str>>>();
+    assert_storable::<Painted<Painted<String>>>();
+
+    // `Painted<T>` is exactly as storable as `T`.
+    assert_send::<Painted<Cell<u8>>>();
+    assert_static::<Painted<Cell<u8>>>();
+    assert_sync::<Painted<std::sync::Mutex<u8>>>();
+
+    // Borrowed values can be made owned.
+    let name = String::from("hi");
+    let owned: Painted<String> = name.red().bold().map(|v| v.clone());
+    drop(name);
+
+    let handle = std::thread::spawn(move || enabled(|| owned.to_string()));
+    assert_eq!(handle.join().unwrap(), "\x1B[1;31mhi\x1B[0m");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn write_to() {
+    use std::io::{self, Write};
+
+    let painted = "hi".red().bold();
+    let mut bytes = vec![];
+    enabled(|| painted.write_to(&mut bytes)).unwrap();
+    assert_eq!(bytes, b"\x1B[1;31mhi\x1B[0m");
+
+    enabled(|| {
+        let mut bytes = b"> ".to_vec();
+        painted.write_to(&mut bytes).unwrap();
+        "!".green().mask().write_to(&mut bytes).unwrap();
+        assert_eq!(bytes, format!("> {}{}", painted, "!".green().mask()).as_bytes());
+        assert_eq!(bytes, b"> \x1B[1;31mhi\x1B[0m\x1B[32m!\x1B[0m");
+    });
+
+    // Conditions and masking are respected.
+    let mut bytes = vec![];
+    painted.whenever(Condition::NEVER).write_to(&mut bytes).unwrap();
+    "masked".blue().mask().whenever(Condition::NEVER).write_to(&mut bytes).unwrap();
+    assert_eq!(bytes, b"hi");
+
+    // Errors from the writer are returned.
+    struct Failing;
+
+    impl Write for Failing {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = "hi".red().write_to(&mut Failing).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn or_mask() {
+    enabled(|| {
+        let art = "🎨".bold().or_mask("(art)");
+        assert_eq!(art.to_string(), "\x1B[1m🎨\x1B[0m");
+        assert_eq!(format!("[{:>3}]", art), "[  \x1B[1m🎨\x1B[0m]");
+
+        // Masking is irrelevant when styling is enabled.
+        let art = "🎨".bold().mask().or_mask("(art)");
+        assert_eq!(art.to_string(), "\x1B[1m🎨\x1B[0m");
+
+        // The style's condition is respected.
+        let art = "🎨".bold().whenever(Condition::NEVER).or_mask("(art)");
+        assert_eq!(art.to_string(), "(art)");
+
+        // Formatting flags apply to the fallback as well.
+        let num = 10.red().whenever(Condition::NEVER).or_mask(&255);
+        assert_eq!(format!("{:x}|{:>5}", num, num), "ff|  255");
+        let num = 10.red().or_mask(&255);
+        assert_eq!(format!("{:x}", num), "\x1B[31ma\x1B[0m");
+    });
+
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+    yansi::disable();
+    assert_eq!("🎨".bold().or_mask("(art)").to_string(), "(art)");
+    assert_eq!("🎨".bold().mask().or_mask("(art)").to_string(), "(art)");
+    assert_eq!("🎨".new().or_mask("(art)").to_string(), "(art)");
+    yansi::whenever(Condition::DEFAULT);
+}
+
+#[test]
+fn repeat() {
+    enabled(|| {
+        let rule = "─".red().repeat(80).to_string();
+        assert_eq!(rule, format!("\x1B[31m{}\x1B[0m", "─".repeat(80)));
+        assert_eq!(rule.matches("\x1B[").count(), 2);
+        assert!(rule.starts_with("\x1B[31m─"));
+        assert!(rule.ends_with("─\x1B[0m"));
+
+        assert_eq!("ab".bold().repeat(2).to_string(), "\x1B[1mabab\x1B[0m");
+        assert_eq!(format!("{:<6}|", "ab".bold().repeat(2)), "\x1B[1mabab\x1B[0m  |");
+        assert_eq!(format!("{:02x}", 10.blue().repeat(2)), "\x1B[34m0a0a\x1B[0m");
+        assert_eq!(format!("{:>6}", 5.red().repeat(2)), "    \x1B[31m55\x1B[0m");
+        assert_eq!(format!("{:*^7}", 5.red().repeat(2)), "**\x1B[31m55\x1B[0m***");
+        assert_eq!(format!("{:6}|", 5.red().repeat(2)), "\x1B[31m55\x1B[0m    |");
+        assert_eq!("x".red().repeat(0).to_string(), "\x1B[31m\x1B[0m");
+        assert_eq!("x".red().whenever(Condition::NEVER).repeat(3).to_string(), "xxx");
+    });
+}
+
+#[test]
+fn eq_and_hash() {
+    use std::collections::HashSet;
+
+    assert_eq!("a".red().bold(), "a".bold().red());
+    assert_eq!(1.into_painted().on_blue(), Painted::new(1).bg(Blue));
+    assert_ne!("a".red(), "a".blue());
+    assert_ne!("a".red(), "b".red());
+    assert_ne!("a".red(), "a".red().italic());
+    assert_ne!("a".new(), "a".red());
+
+    // Styles are compared structurally: quirks and conditions are ignored.
+    assert_eq!("a".red(), "a".red().wrap().linger());
+    assert_eq!("a".red(), "a".red().whenever(Condition::NEVER));
+
+    #[cfg(feature = "hyperlink")]
+    assert_ne!("a".red(), "a".paint(Red.link("https://docs.rs")));
+
+    let set: HashSet<_> = ["a".red(), "a".red().mask(), "a".blue(), "b".red()].into_iter().collect();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&"a".red().bright()));
+    assert!(!set.contains(&"a".green()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn parts() {
+    enabled(|| {
+        let painted = "hi".red().bold();
+        let (prefix, value, suffix) = painted.parts();
+        assert_eq!(prefix, painted.style.prefix());
+        assert_eq!(suffix, painted.style.suffix());
+        assert_eq!(prefix, "\x1B[1;31m");
+        assert_eq!(value, Some(&"hi"));
+        assert_eq!(suffix, "\x1B[0m");
+        assert_eq!(format!("{}{}{}", prefix, value.unwrap(), suffix), painted.to_string());
+
+        let plain = 5.new();
+        let (prefix, value, suffix) = plain.parts();
+        assert_eq!((&*prefix, value, &*suffix), ("", Some(&5), ""));
+
+        let never = "hi".red().whenever(Condition::NEVER);
+        let (prefix, value, suffix) = never.parts();
+        assert_eq!((&*prefix, value, &*suffix), ("", Some(&"hi"), ""));
+
+        // A masked value is only omitted when styling is disabled.
+        let masked = "hi".red().mask();
+        let (prefix, value, suffix) = masked.parts();
+        assert_eq!((&*prefix, value, &*suffix), ("\x1B[31m", Some(&"hi"), "\x1B[0m"));
+
+        let masked = masked.whenever(Condition::NEVER);
+        let (prefix, value, suffix) = masked.parts();
+        assert_eq!((&*prefix, value, &*suffix), ("", None, ""));
+    });
+
+    // With styling disabled, the parts match the unstyled rendering.
+    let _lock = LOCK.lock().expect("FAIL FAST - LOCK POISONED");
+    yansi::disable();
+    for painted in ["hi".red().bold(), "hi".red().mask(), "hi".new().wrap()] {
+        let (prefix, value, suffix) = painted.parts();
+        assert_eq!((&*prefix, &*suffix), ("", ""));
+
+        let rendered = value.map(|v| v.to_string()).unwrap_or_default();
+        assert_eq!(rendered, painted.to_string());
+    }
+
+    yansi::whenever(Condition::DEFAULT);
+}
+
+#[test]
+#[deny(unused_must_use)]
+fn must_use_chaining() {
+    // Chained builders, including deprecated ones, are used exactly once.
+    #[allow(deprecated)]
+    let painted = "x".red().bold().on_blue().clear().resetting();
+    let style = Style::new().red().bold().on_blue().whenever(Condition::ALWAYS);
+    assert_eq!(painted.style, style);
+    assert_eq!(Red.on_gray(3).bold(), Style::new().red().on_gray(3).bold());
+}
+
+#[test]
+fn fg_bg_into() {
+    assert_eq!("x".new().fg_into([30, 144, 255]), "x".rgb(30, 144, 255));
+    assert_eq!("x".new().fg_into((30, 144, 255)), "x".rgb(30, 144, 255));
+    assert_eq!("x".new().fg_into(Red), "x".red());
+    assert_eq!("x".new().bg_into([1, 2, 3]), "x".on_rgb(1, 2, 3));
+    assert_eq!("x".new().bg_into((1, 2, 3)).fg_into([4, 5, 6]), "x".on_rgb(1, 2, 3).rgb(4, 5, 6));
+    assert_eq!("x".bold().bg_into(Blue), "x".bold().on_blue());
+
+    let rgb: [u8; 3] = [255, 0, 170];
+    assert_eq!(Style::new().fg_into(rgb), Style::new().fg(Rgb(255, 0, 170)));
+    assert_eq!(Style::new().bg_into(rgb).bold(), Style::new().on_rgb(255, 0, 170).bold());
+
+    enabled(|| {
+        assert_eq!("x".new().fg_into([1, 2, 3]).to_string(), "\x1B[38;2;1;2;3mx\x1B[0m");
+        assert_eq!("x".new().bg_into((1, 2, 3)).to_string(), "\x1B[48;2;1;2;3mx\x1B[0m");
+    });
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn to_html() {
+    assert_eq!("hi".red().bold().to_html(), "<span style=\"color:red;font-weight:bold\">hi</span>");
+    assert_eq!("hi".rgb(1, 2, 3).on_black().to_html(),
+        "<span style=\"color:rgb(1, 2, 3);background:black\">hi</span>");
+
+    // HTML-special characters are escaped.
+    assert_eq!("<a href=\"x\">'&'</a>".italic().to_html(),
+        "<span style=\"font-style:italic\">&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;</span>");
+    assert_eq!("&&".new().to_html(), "&amp;&amp;");
+
+    // Unstyled values aren't wrapped, and escape sequences are removed.
+    assert_eq!("plain".new().to_html(), "plain");
+    assert_eq!("x".mask().to_html(), "x");
+    let nested = format!("a{}b", "<c>".green());
+    assert_eq!(nested.blue().to_html(), "<span style=\"color:blue\">a&lt;c&gt;b</span>");
+
+    // Conditions are not considered.
+    let never = "hi".red().whenever(Condition::NEVER);
+    assert_eq!(never.to_html(), "hi".red().to_html());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn by_ref() {
+    let owned = Painted::new(String::from("hello")).red().bold();
+    let borrowed = owned.by_ref();
+    assert_eq!(borrowed.value, &owned.value);
+    assert_eq!(borrowed.style, owned.style);
+
+    enabled(|| {
+        let first = owned.by_ref().to_string();
+        let second = owned.by_ref().to_string();
+        assert_eq!(first, "\x1B[1;31mhello\x1B[0m");
+        assert_eq!(first, second);
+        assert_eq!(first, owned.to_string());
+
+        assert_eq!(owned.by_ref().italic().to_string(), "\x1B[1;3;31mhello\x1B[0m");
+        assert_eq!(owned.style, Red.bold());
+    });
+}
+
+#[test]
+fn tap_style() {
+    let painted = "hi".bold().tap_style(|style| {
+        assert_eq!(style, Style::new().bold());
+        style.red().on_blue()
+    });
+
+    assert_eq!(painted.value, "hi");
+    assert_eq!(painted.style, Red.on_blue().bold());
+
+    let painted = "hi".red().tap_style(|_| Style::new().italic()).underline();
+    assert_eq!(painted.style, Style::new().italic().underline());
+
+    let colors = [("error", Red), ("warn", Yellow)];
+    let level = "warn";
+    let painted = "msg".bold().tap_style(|style| match colors.iter().find(|(l, _)| *l == level) {
+        Some(&(_, color)) => style.fg(color),
+        None => style,
+    });
+
+    assert_eq!(painted.style, Yellow.bold());
+}
+
+#[test]
+fn paint_some() {
+    use yansi::PaintOption;
+
+    let (some, none): (Option<usize>, Option<usize>) = (Some(10), None);
+    enabled(|| {
+        assert_eq!(some.paint_some(Green).to_string(), "\x1b[32m10\x1b[0m");
+        assert_eq!(format!("{:?}", some.paint_some(Green)), "\x1b[32m10\x1b[0m");
+        assert_eq!(format!("{:x}", some.paint_some(Green)), "\x1b[32ma\x1b[0m");
+        assert_eq!(some.paint_some(Green).otherwise(Red).to_string(), "\x1b[32m10\x1b[0m");
+
+        assert_eq!(none.paint_some(Green).to_string(), "None");
+        assert_eq!(format!("{:?}", none.paint_some(Green)), "None");
+        assert_eq!(format!("{:>6}", none.paint_some(Green)), "  None");
+        assert_eq!(none.paint_some(Green).otherwise(Red).to_string(), "\x1b[31mNone\x1b[0m");
+    });
+}
+
+#[test]
+fn paint_ok() {
+    use yansi::PaintResult;
+
+    let (ok, err): (Result<&str, &str>, Result<&str, &str>) = (Ok("yes"), Err("no"));
+    enabled(|| {
+        assert_eq!(ok.paint_ok(Green).to_string(), "\x1b[32myes\x1b[0m");
+        assert_eq!(format!("{:?}", ok.paint_ok(Green)), "\x1b[32m\"yes\"\x1b[0m");
+        assert_eq!(ok.paint_ok(Green).otherwise(Red).to_string(), "\x1b[32myes\x1b[0m");
+
+        assert_eq!(err.paint_ok(Green).to_string(), "no");
+        assert_eq!(format!("{:?}", err.paint_ok(Green)), "\"no\"");
+        assert_eq!(err.paint_ok(Green).otherwise(Red.bold()).to_string(), "\x1b[1;31mno\x1b[0m");
+    });
+
+    let painted = ok.paint_ok(Green).otherwise(Red);
+    assert_eq!(painted.value.unwrap().style, Green.foreground());
+    assert_eq!(err.paint_ok(Green).value.unwrap_err().style, Style::new());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn as_ref_style() {
+    fn emit(style: impl AsRef<Style>) -> String {
+        style.as_ref().prefix().into_owned()
+    }
+
+    static STYLE: Style = Red.bold();
+
+    let painted = "hi".paint(STYLE);
+    assert_eq!(emit(STYLE), emit(painted));
+    assert_eq!(emit(painted), "\x1b[1;31m");
+    assert_eq!(emit("hi".paint(STYLE).on_blue()), emit(STYLE.on_blue()));
+
+    let painted: Painted<String> = Painted::new("x".to_string()).green();
+    assert!(std::ptr::eq(painted.as_ref(), &painted.style));
+    assert_eq!(Style::from(painted.clone()), *painted.as_ref());
+}
+
+#[test]
+fn with() {
+    const OK: Painted<&str> = Painted::with("OK", Green.bold());
+    const DIM: Painted<&str> = Painted::with("-", Style::new().dim().whenever(Condition::NEVER));
+    static TABLE: [Painted<&str>; 3] = [OK, DIM, Painted::with("FAIL", Red.on_white())];
+
+    assert_eq!(OK, "OK".green().bold());
+    assert_eq!(Painted::with(1, Style::new()), Painted::new(1));
+    enabled(|| {
+        assert_eq!(OK.to_string(), "\x1b[1;32mOK\x1b[0m");
+        assert_eq!(DIM.to_string(), "-");
+        assert_eq!(TABLE[2].to_string(), "\x1b[47;31mFAIL\x1b[0m");
+        assert_eq!(format!("{:>4}", TABLE[0]), "  \x1b[1;32mOK\x1b[0m");
+    });
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn collect_string() {
+    let (a, b, c) = ("build".green().bold(), " ".new(), "3 warnings".yellow());
+    enabled(|| {
+        let collected: String = vec![a, b, c].into_iter().collect();
+        assert_eq!(collected, format!("{}{}{}", a, b, c));
+        assert_eq!(collected, "\x1b[1;32mbuild\x1b[0m \x1b[33m3 warnings\x1b[0m");
+
+        let mut line = String::from("$ ");
+        line.extend([a, b]);
+        line.extend(Some(c));
+        assert_eq!(line, format!("$ {}", collected));
+
+        let numbers: String = (1..4).map(|n| Painted::new(n).red().whenever(Condition::NEVER)).collect();
+        assert_eq!(numbers, "123");
+        assert_eq!(std::iter::empty::<Painted<&str>>().collect::<String>(), "");
+    });
+}
+
+/// `Paint` is deliberately not in scope: only `Paintable` is.
+mod paintable {
+    use yansi::{Paintable, Painted, Style, Color::*};
+
+    trait Team {
+        fn red(&self) -> &'static str;
+    }
+
+    impl Team for str {
+        fn red(&self) -> &'static str {
+            "team"
+        }
+    }
+
+    struct Sign;
+
+    impl Sign {
+        fn red(&self) -> u8 {
+            1
+        }
+    }
+
+    #[test]
+    fn no_collision() {
+        assert_eq!("x".red(), "team");
+        assert_eq!(Sign.red(), 1);
+
+        let painted: Painted<&str> = "x".styled(Red.bold()).italic();
+        assert_eq!(painted.style, Red.bold().italic());
+        assert_eq!(Sign.styled(Style::new()).style, Style::new());
+        assert_eq!(1.styled(Blue).style, Blue.foreground());
+    }
+}
+
+/// Everything is in scope, including both `Paint` and `Paintable`.
+mod glob {
+    use yansi::*;
+
+    #[test]
+    fn no_ambiguity() {
+        assert_eq!("x".paint(Color::Red).style, "x".styled(Color::Red).style);
+        assert_eq!("x".red().style, Color::Red.foreground());
+    }
+}
+
+/// Methods with common names aren't provided by the blanket `Paint`, so they
+/// don't collide with those of other traits.
+mod blanket {
+    use yansi::{Paint, Color::*};
+
+    trait Common {
+        fn gray(&self) -> &'static str { "gray" }
+        fn cube(&self) -> &'static str { "cube" }
+        fn dull(&self) -> &'static str { "dull" }
+        fn force(&self) -> &'static str { "force" }
+        fn owned(&self) -> &'static str { "owned" }
+        fn fg_hex(&self, _: &str) -> &'static str { "fg_hex" }
+        fn bg_hex(&self, _: &str) -> &'static str { "bg_hex" }
+        fn fg_into(&self, _: [u8; 3]) -> &'static str { "fg_into" }
+        fn bg_into(&self, _: [u8; 3]) -> &'static str { "bg_into" }
+        fn rgb_f32(&self, _: f32, _: f32, _: f32) -> &'static str { "rgb_f32" }
+        fn on_rgb_f32(&self, _: f32, _: f32, _: f32) -> &'static str { "on_rgb_f32" }
+    }
+
+    impl Common for str { }
+
+    #[test]
+    fn no_collision() {
+        assert_eq!("x".gray(), "gray");
+        assert_eq!("x".new().gray(5).style, Fixed(237).foreground());
+
+        assert_eq!("x".cube(), "cube");
+        assert_eq!("x".new().cube(5, 0, 0).style, Fixed(196).foreground());
+
+        assert_eq!("x".dull(), "dull");
+        assert_eq!("x".bright_red().dull().style, BrightRed.dull());
+
+        assert_eq!("x".force(), "force");
+        assert_eq!("x".new().force().style.quirks().count(), 1);
+
+        assert_eq!("x".owned(), "owned");
+        assert_eq!("x".into_painted().value, "x");
+
+        assert_eq!("x".fg_hex("#f00"), "fg_hex");
+        assert_eq!("x".bg_hex("#f00"), "bg_hex");
+        assert_eq!("x".new().fg_hex("#f00").bg_hex("#00f").style, Rgb(255, 0, 0).on_rgb(0, 0, 255));
+
+        assert_eq!("x".fg_into([1, 2, 3]), "fg_into");
+        assert_eq!("x".bg_into([1, 2, 3]), "bg_into");
+        assert_eq!("x".new().fg_into([1, 2, 3]).bg_into(Red).style, Rgb(1, 2, 3).on_red());
+
+        assert_eq!("x".rgb_f32(1.0, 0.0, 0.0), "rgb_f32");
+        assert_eq!("x".on_rgb_f32(1.0, 0.0, 0.0), "on_rgb_f32");
+        assert_eq!("x".new().rgb_f32(1.0, 0.0, 0.0).style, Rgb(255, 0, 0).foreground());
+    }
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "serde")]
+
+use yansi::{Attribute, Color::{self, *}, Paint, Quirk, Style};
+
+fn round_trip<T>(value: &T) -> T
+    where T: serde::Serialize + serde::de::DeserializeOwned
+{
+    let json = serde_json::to_string(value).expect("serialize");
+    serde_json::from_str(&json).expect("deserialize")
+}
+
+#[test]
+fn color_format() {
+    assert_eq!(serde_json::to_string(&Primary).unwrap(), r#""primary""#);
+    assert_eq!(serde_json::to_string(&Red).unwrap(), r#""red""#);
+    assert_eq!(serde_json::to_string(&BrightRed).unwrap(), r#""bright_red""#);
+    assert_eq!(serde_json::to_string(&Fixed(208)).unwrap(), "208");
+    assert_eq!(serde_json::to_string(&Rgb(30, 144, 255)).unwrap(), "[30,144,255]");
+
+    let color: Color = serde_json::from_str(r##""#1e90ff""##).unwrap();
+    assert_eq!(color, Rgb(30, 144, 255));
+
+    assert!(serde_json::from_str::<Color>(r#""Red""#).is_err());
+    assert!(serde_json::from_str::<Color>(r#""bright red""#).is_err());
+    assert!(serde_json::from_str::<Color>("256").is_err());
+    assert!(serde_json::from_str::<Color>("-1").is_err());
+    assert!(serde_json::from_str::<Color>("[1, 2]").is_err());
+    assert!(serde_json::from_str::<Color>("[1, 2, 3, 4]").is_err());
+    assert!(serde_json::from_str::<Color>("[1, 2, 256]").is_err());
+}
+
+#[test]
+fn color_round_trip() {
+    let colors = [
+        Primary, Fixed(0), Fixed(255), Rgb(0, 0, 0), Rgb(255, 128, 1),
+        Black, Red, Green, Yellow, Blue, Magenta, Cyan, White,
+        BrightBlack, BrightRed, BrightGreen, BrightYellow,
+        BrightBlue, BrightMagenta, BrightCyan, BrightWhite,
+    ];
+
+    for color in colors {
+        assert_eq!(round_trip(&color), color);
+    }
+}
+
+#[test]
+fn attribute_quirk_round_trip() {
+    use Attribute::*;
+
+    let attrs = [
+        Bold, Dim, Italic, Underline, Blink, RapidBlink, Invert, Conceal, Strike,
+        DoubleUnderline, Overline, CurlyUnderline, Framed, Encircled,
+    ];
+
+    for attr in attrs {
+        assert_eq!(round_trip(&attr), attr);
+    }
+
+    use Quirk::*;
+
+    for quirk in [Mask, Wrap, Linger, Resetting, Bright, OnBright, Dull, OnDull, ResetBefore, PerLine, Force] {
+        assert_eq!(round_trip(&quirk), quirk);
+    }
+
+    assert_eq!(serde_json::to_string(&RapidBlink).unwrap(), r#""rapid_blink""#);
+    assert_eq!(serde_json::to_string(&OnBright).unwrap(), r#""on_bright""#);
+    assert!(serde_json::from_str::<Attribute>(r#""sparkle""#).is_err());
+}
+
+#[test]
+fn style_round_trip() {
+    let styles = [
+        Style::new(),
+        Red.foreground(),
+        Red.on_blue(),
+        Style::new().on_fixed(100),
+        Rgb(1, 2, 3).bold().italic().strike(),
+        Fixed(1).on_rgb(4, 5, 6).underline().mask().wrap().linger(),
+        Green.bright().on_bright().resetting(),
+        Style::new().curly_underline().underline_rgb(255, 0, 0),
+    ];
+
+    for style in styles {
+        let rt = round_trip(&style);
+        assert_eq!(rt, style);
+        assert_eq!("x".paint(rt).to_string(), "x".paint(style).to_string());
+    }
+}
+
+#[test]
+fn style_from_json() {
+    let json = r##"{
+        "foreground": "bright_red",
+        "background": [30, 144, 255],
+        "attributes": ["bold", "underline"],
+        "quirks": ["mask"]
+    }"##;
+
+    let style: Style = serde_json::from_str(json).unwrap();
+    assert_eq!(style, BrightRed.on_rgb(30, 144, 255).bold().underline());
+    assert!(style.condition.is_none());
+
+    let style: Style = serde_json::from_str(r##"{ "background": "#f0a" }"##).unwrap();
+    assert_eq!(style, Style::new().on_rgb(0xff, 0x00, 0xaa));
+
+    let style: Style = serde_json::from_str(r#"{ "foreground": 3, "background": null }"#).unwrap();
+    assert_eq!(style, Fixed(3).foreground());
+
+    let json = r#"{ "attributes": ["curly_underline"], "underline_color": "red" }"#;
+    let style: Style = serde_json::from_str(json).unwrap();
+    assert_eq!(style, Style::new().curly_underline().underline_red());
+
+    let style: Style = serde_json::from_str("{}").unwrap();
+    assert_eq!(style, Style::new());
+
+    assert!(serde_json::from_str::<Style>(r#"{ "color": "red" }"#).is_err());
+    assert!(serde_json::from_str::<Style>(r#"{ "attributes": "bold" }"#).is_err());
+}
+
+#[test]
+#[cfg(feature = "hyperlink")]
+fn linked_style_loses_link() {
+    let linked = Red.bold().link("https://docs.rs");
+    let json = serde_json::to_string(&linked).unwrap();
+    assert!(!json.contains("docs.rs"));
+
+    let style = round_trip(&linked);
+    assert_eq!(style.link, None);
+    assert_ne!(style, linked);
+    assert_eq!(style, Red.bold());
+}
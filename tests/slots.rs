@@ -0,0 +1,63 @@
+#![cfg(feature = "alloc")]
+
+// Conditions share a fixed number of process-wide slots, so this test lives in
+// its own binary to exhaust them without affecting other tests.
+
+use std::sync::{Arc, atomic::AtomicBool};
+use yansi::{Condition, Style};
+
+static COND_A: Condition = Condition::from(|| true);
+static COND_B: Condition = Condition::from(|| false);
+
+static FLAG: AtomicBool = AtomicBool::new(true);
+static OTHER: AtomicBool = AtomicBool::new(true);
+
+#[test]
+fn boxed_exhausts_slots_without_panicking() {
+    let and = COND_A.and(COND_B);
+    let flag = Condition::from_atomic(&FLAG).unwrap();
+
+    let state = Arc::new(());
+    let mut conditions = vec![];
+    loop {
+        let captured = Arc::clone(&state);
+        match Condition::boxed(move || Arc::strong_count(&captured) > 0) {
+            Some(condition) => conditions.push(condition),
+            None => break,
+        }
+    }
+
+    // The rejected closure was dropped rather than leaked.
+    assert_eq!(conditions.len(), 62);
+    assert_eq!(Arc::strong_count(&state), 1 + conditions.len());
+    assert!(Condition::boxed(|| true).is_none());
+
+    // Existing conditions are unaffected.
+    assert!(conditions.iter().all(|condition| condition()));
+    assert_eq!(Condition::ALWAYS.and(Condition::ALWAYS), Condition::ALWAYS);
+
+    // New combinations fail, but existing and simplified ones don't.
+    assert_eq!(COND_A.try_or(COND_B), None);
+    assert_eq!(COND_B.try_and(COND_A), None);
+    assert_eq!(COND_A.try_not(), None);
+    assert_eq!(COND_A.try_and(COND_B), Some(and));
+    assert_eq!(COND_A.try_and(Condition::ALWAYS), Some(COND_A));
+    assert_eq!(COND_A.try_or(Condition::ALWAYS), Some(Condition::ALWAYS));
+    assert_eq!(Condition::NEVER.try_not(), Some(Condition::ALWAYS));
+    assert!(!and());
+
+    // Likewise for flags.
+    assert_eq!(Condition::from_atomic(&OTHER), None);
+    assert_eq!(Condition::from_atomic(&FLAG), Some(flag));
+    assert!(flag());
+
+    // Layering a style keeps its existing condition instead of panicking.
+    let style = Style::new().whenever(COND_A);
+    assert_eq!(style.and_whenever(COND_B).condition, Some(and));
+    assert_eq!(style.and_whenever(flag).condition, Some(COND_A));
+    assert_eq!(style.and_whenever(Condition::NEVER).condition, Some(Condition::NEVER));
+    assert_eq!(Style::new().and_whenever(COND_B).condition, Some(COND_B));
+
+    let result = std::panic::catch_unwind(|| COND_A | COND_B);
+    assert!(result.is_err());
+}
@@ -0,0 +1,48 @@
+// The default global condition can only be restored by a guard, so this test
+// lives in its own binary to observe it before anything else changes it.
+
+use yansi::{Condition, Stream};
+
+fn assert_default() {
+    #[cfg(feature = "detect-tty")] {
+        let os = Condition::os_support();
+        assert_eq!(yansi::is_enabled_for(Stream::Stdout), os && Condition::stdout_is_tty());
+        assert_eq!(yansi::is_enabled_for(Stream::Stderr), os && Condition::stderr_is_tty());
+    }
+
+    #[cfg(not(feature = "detect-tty"))] {
+        assert_eq!(yansi::is_enabled_for(Stream::Stdout), yansi::is_enabled());
+        assert_eq!(yansi::is_enabled_for(Stream::Stderr), yansi::is_enabled());
+    }
+}
+
+#[test]
+fn guards_restore_default_for_stream() {
+    assert_default();
+
+    {
+        let _guard = yansi::enable_guard();
+        assert!(yansi::is_enabled_for(Stream::Stdout));
+        assert!(yansi::is_enabled_for(Stream::Stderr));
+    }
+
+    assert_default();
+
+    {
+        let _guard = yansi::disable_guard();
+        assert!(!yansi::is_enabled_for(Stream::Stdout));
+        assert!(!yansi::is_enabled_for(Stream::Stderr));
+    }
+
+    assert_default();
+
+    // Guards restore the default condition itself, not just its value.
+    {
+        let _outer = yansi::whenever_guard(Condition::DEFAULT);
+        assert_eq!(yansi::is_enabled_for(Stream::Stderr), Condition::os_support());
+        drop(yansi::enable_guard());
+        assert_eq!(yansi::is_enabled_for(Stream::Stderr), Condition::os_support());
+    }
+
+    assert_default();
+}
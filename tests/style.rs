@@ -0,0 +1,590 @@
+use yansi::{Condition, Paint, Style, Color::*};
+
+#[test]
+fn combine() {
+    let base = Red.on_black().bold().underline().mask();
+    let over = Yellow.italic().underline().wrap();
+    let combined = base.combine(over);
+    assert_eq!(combined, Yellow.on_black().bold().italic().underline());
+    let expected = Yellow.on_black().bold().italic().underline().mask().wrap();
+    assert_eq!("x".paint(combined).to_string(), "x".paint(expected).to_string());
+
+    // `None` fields in `other` preserve `self`'s values.
+    let combined = base.combine(Style::new());
+    assert_eq!(combined, base);
+    assert_eq!(combined.foreground, Some(Red));
+    assert_eq!(combined.background, Some(Black));
+
+    let combined = Style::new().combine(base);
+    assert_eq!(combined, base);
+
+    let combined = Red.foreground().combine(Style::new().on_green());
+    assert_eq!(combined, Red.on_green());
+
+    let combined = Red.underline_blue().combine(Green.underline());
+    assert_eq!(combined, Green.underline().underline_blue());
+    let combined = Red.underline_blue().combine(Style::new().underline_red());
+    assert_eq!(combined, Red.underline_red());
+
+    // Conditions in `other` win when they're set.
+    let combined = base.whenever(Condition::NEVER).combine(over);
+    assert_eq!(combined.condition, Some(Condition::NEVER));
+
+    let combined = base.whenever(Condition::NEVER).combine(over.whenever(Condition::ALWAYS));
+    assert_eq!(combined.condition, Some(Condition::ALWAYS));
+}
+
+#[test]
+fn without() {
+    use yansi::Attribute::*;
+
+    let style = Red.bold().underline().italic();
+    assert_eq!(style.without(Underline), Red.bold().italic());
+    assert_eq!(style.without(Underline).without(Bold), Red.italic());
+    assert_eq!(style.without(Bold).without(Italic).without(Underline), Red.foreground());
+
+    // Removing an absent attribute is a no-op.
+    assert_eq!(style.without(Blink), style);
+    assert_eq!(style.without(Underline).without(Underline), Red.bold().italic());
+    assert_eq!(Style::new().without(Strike), Style::new());
+
+    // Everything else is preserved.
+    let style = Red.on_blue().bold().mask().whenever(Condition::NEVER).without(Bold);
+    assert_eq!(style, Red.on_blue());
+    assert_eq!(style.condition, Some(Condition::NEVER));
+    let expected = Red.on_blue().mask().whenever(Condition::NEVER);
+    assert_eq!("x".paint(style).to_string(), "x".paint(expected).to_string());
+}
+
+#[test]
+fn attributes_and_quirks() {
+    use yansi::{Attribute, Quirk};
+
+    let style = Red.strike().bold().dim().wrap().bright().mask();
+    let attrs: Vec<_> = style.attributes().collect();
+    let quirks: Vec<_> = style.quirks().collect();
+    assert_eq!(attrs, [Attribute::Bold, Attribute::Dim, Attribute::Strike]);
+    assert_eq!(quirks, [Quirk::Mask, Quirk::Wrap, Quirk::Bright]);
+
+    assert_eq!(Style::new().attributes().count(), 0);
+    assert_eq!(Style::new().quirks().count(), 0);
+    assert_eq!(Red.on_blue().attributes().count(), 0);
+
+    let all = Style::new().bold().dim().italic().underline().blink()
+        .rapid_blink().invert().conceal().strike();
+
+    assert_eq!(all.attributes().count(), 9);
+    assert!(all.attributes().eq(Style::new()
+        .strike().conceal().invert().rapid_blink().blink()
+        .underline().italic().dim().bold()
+        .attributes()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn to_css() {
+    assert_eq!(Style::new().to_css(), "");
+    assert_eq!(Style::new().mask().wrap().to_css(), "");
+    assert_eq!(Red.foreground().to_css(), "color:red");
+    assert_eq!(Style::new().on_rgb(1, 2, 3).to_css(), "background:rgb(1, 2, 3)");
+    assert_eq!(Fixed(21).on_primary().to_css(), "color:rgb(0, 0, 255);background:inherit");
+    assert_eq!(Style::new().bold().italic().to_css(), "font-weight:bold;font-style:italic");
+    assert_eq!(Style::new().dim().conceal().to_css(), "opacity:50%;visibility:hidden");
+    assert_eq!(Style::new().underline().to_css(), "text-decoration:underline");
+    assert_eq!(Style::new().blink().rapid_blink().strike().to_css(),
+        "text-decoration:blink line-through");
+
+    assert_eq!(Style::new().overline().strike().to_css(), "text-decoration:overline line-through");
+    assert_eq!(Style::new().double_underline().to_css(), "text-decoration:underline double");
+    assert_eq!(Style::new().underline().double_underline().overline().to_css(),
+        "text-decoration:underline overline double");
+
+    assert_eq!(Style::new().curly_underline().underline_rgb(255, 0, 0).to_css(),
+        "text-decoration:underline wavy;text-decoration-color:rgb(255, 0, 0)");
+    assert_eq!(Style::new().curly_underline().double_underline().to_css(),
+        "text-decoration:underline double");
+
+    assert_eq!(Red.on_blue().bright().to_css(), "color:hotpink;background:blue");
+    assert_eq!(Red.on_blue().on_bright().to_css(), "color:red;background:dodgerblue");
+    assert_eq!(BrightRed.on_bright_blue().dull().on_dull().to_css(), "color:red;background:blue");
+    assert_eq!(Red.on_blue().invert().to_css(), "color:blue;background:red");
+    assert_eq!(Red.invert().to_css(), "background:red");
+
+    assert_eq!(Green.on_bright_black().bold().underline().strike().to_css(),
+        "color:green;background:gray;font-weight:bold;\
+        text-decoration:underline line-through");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn write_prefix_suffix() {
+    use std::fmt::Write;
+
+    fn written(style: Style) -> String {
+        let mut string = String::new();
+        style.write_prefix(&mut string).unwrap();
+        string.write_str("hi").unwrap();
+        style.write_suffix(&mut string).unwrap();
+        string
+    }
+
+    fn unconditional(style: Style) -> String {
+        let mut string = String::new();
+        style.fmt_prefix(&mut string).unwrap();
+        string.write_str("hi").unwrap();
+        style.fmt_suffix(&mut string).unwrap();
+        string
+    }
+
+    yansi::with_enabled(true, || {
+        let style = Red.bold();
+        assert_eq!(written(style), "\x1B[1;31mhi\x1B[0m");
+        assert_eq!(written(style), unconditional(style));
+        assert_eq!(written(style.whenever(Condition::ALWAYS)), unconditional(style));
+        assert_eq!(written(style.linger()), "\x1B[1;31mhi");
+        assert_eq!(written(style.mask()), unconditional(style));
+
+        assert_eq!(written(style.whenever(Condition::NEVER)), "hi");
+        assert_eq!(unconditional(style.whenever(Condition::NEVER)), "\x1B[1;31mhi\x1B[0m");
+    });
+
+    yansi::with_enabled(false, || {
+        assert_eq!(written(Red.bold()), "hi");
+        assert_eq!(written(Red.bold().whenever(Condition::ALWAYS)), "hi");
+        assert_eq!(unconditional(Red.bold()), "\x1B[1;31mhi\x1B[0m");
+    });
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn compile() {
+    let styles = [
+        Style::new(),
+        Red.foreground(),
+        Red.on_blue().bold().underline_green(),
+        Red.bold().linger(),
+        Style::new().resetting(),
+        Red.mask(),
+        Red.wrap(),
+        Red.whenever(Condition::NEVER),
+        Red.mask().whenever(Condition::NEVER),
+    ];
+
+    let inner = "x".green().to_string();
+    for style in styles {
+        let compiled = style.compile();
+        assert_eq!(compiled.style(), style);
+        assert_eq!(compiled.prefix(), style.prefix());
+        assert_eq!(compiled.suffix(), style.suffix());
+
+        for enabled in [true, false] {
+            yansi::with_enabled(enabled, || {
+                assert_eq!(compiled.paint(1).to_string(), 1.paint(style).to_string());
+                assert_eq!(compiled.paint(&inner).to_string(), inner.paint(style).to_string());
+                assert_eq!(format!("{:?}", compiled.paint("a")), format!("{:?}", "a".paint(style)));
+                assert_eq!(format!("{:x}", compiled.paint(255)), format!("{:x}", 255.paint(style)));
+                assert_eq!(format!("{:>4}", compiled.paint(7)), format!("{:>4}", 7.paint(style)));
+                assert_eq!(format!("{:.1}", compiled.paint(1.25)), format!("{:.1}", 1.25.paint(style)));
+            });
+        }
+    }
+}
+
+#[test]
+fn readable_fg() {
+    // Light backgrounds yield a black foreground.
+    for bg in [Rgb(255, 255, 255), Rgb(255, 215, 0), Rgb(0, 255, 0), Rgb(128, 128, 128)] {
+        assert_eq!(Style::new().bg(bg).readable_fg().foreground, Some(Black), "{:?}", bg);
+    }
+
+    for bg in [BrightWhite, Yellow, Cyan, Fixed(250), Fixed(231)] {
+        assert_eq!(Style::new().bg(bg).readable_fg().foreground, Some(Black), "{:?}", bg);
+    }
+
+    // Dark backgrounds yield a white foreground.
+    for bg in [Rgb(0, 0, 0), Rgb(25, 25, 112), Rgb(139, 0, 0), Rgb(0, 0, 255)] {
+        assert_eq!(Style::new().bg(bg).readable_fg().foreground, Some(White), "{:?}", bg);
+    }
+
+    for bg in [Black, Blue, Red, Magenta, Fixed(16), Fixed(235)] {
+        assert_eq!(Style::new().bg(bg).readable_fg().foreground, Some(White), "{:?}", bg);
+    }
+
+    // Existing foregrounds are overridden; everything else is preserved.
+    let style = Red.on_rgb(250, 250, 250).bold().underline_blue().readable_fg();
+    assert_eq!(style, Black.on_rgb(250, 250, 250).bold().underline_blue());
+
+    // Without a known background, the style is unchanged.
+    assert_eq!(Style::new().readable_fg(), Style::new());
+    assert_eq!(Red.bold().readable_fg(), Red.bold());
+    assert_eq!(Red.on_primary().readable_fg(), Red.on_primary());
+}
+
+#[test]
+fn from_str() {
+    use yansi::Quirk;
+
+    let parse = |s: &str| s.parse::<Style>();
+
+    assert_eq!(parse("bold underline red on_blue"), Ok(Red.on_blue().bold().underline()));
+    assert_eq!(parse("  red\ton_blue\n bold "), Ok(Red.on_blue().bold()));
+    assert_eq!(parse("BOLD Red ON_BRIGHT_BLUE"), Ok(Red.on_bright_blue().bold()));
+    assert_eq!(parse("fixed:100 on_rgb:70,130,180"), Ok(Fixed(100).on_rgb(70, 130, 180)));
+    assert_eq!(parse("#1e90ff on_fixed(236)"), Ok(Rgb(30, 144, 255).on_fixed(236)));
+    assert_eq!(parse("dodgerblue on_primary"), Ok(Rgb(30, 144, 255).on_primary()));
+    assert_eq!(parse("rapid_blink curly_underline"), Ok(Style::new().rapid_blink().curly_underline()));
+    assert_eq!(parse("Framed encircled"), Ok(Style::new().framed().encircled()));
+    assert_eq!(parse(""), Ok(Style::new()));
+
+    // Later tokens override earlier ones.
+    assert_eq!(parse("red green on_red on_blue"), Ok(Green.on_blue()));
+
+    let style = parse("red bright on_blue on_bright mask wrap linger").unwrap();
+    assert_eq!(style, Red.on_blue());
+    let quirks = [Quirk::Mask, Quirk::Wrap, Quirk::Linger, Quirk::Bright, Quirk::OnBright];
+    assert!(style.quirks().eq(quirks));
+
+    let error = |s: &str| parse(s).unwrap_err().position();
+    assert_eq!(error("fancy"), 0);
+    assert_eq!(error("bold fancy red"), 5);
+    assert_eq!(error("bold  on_fancy"), 6);
+    assert_eq!(error("red on_"), 4);
+    assert_eq!(error("fixed:256"), 0);
+    assert_eq!(error("bold rgb:1,2"), 5);
+    assert_eq!(error("rgb:1,2,3,4"), 0);
+    assert_eq!(error("red on blue"), 4);
+    assert!(parse("bold fancy").unwrap_err().to_string().contains("byte 5"));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn ansi() {
+    let styles = [
+        Style::new(),
+        Red.foreground(),
+        Red.on_blue().bold().italic(),
+        Fixed(100).on_rgb(1, 2, 3).underline_green().curly_underline(),
+        Red.bright().on_blue().on_bright(),
+        Red.linger().mask().whenever(Condition::NEVER),
+    ];
+
+    for style in styles {
+        assert_eq!(style.ansi().to_string(), style.prefix());
+        assert_eq!(format!("{}x", style.ansi()), format!("{}x", style.prefix()));
+    }
+
+    assert_eq!(Style::new().ansi().to_string(), "");
+    assert_eq!(Red.on_blue().bold().ansi().to_string(), "\x1B[1;44;31m");
+}
+
+#[test]
+fn is_plain() {
+    assert!(Style::new().is_plain());
+    assert!(Style::default().is_plain());
+    assert!(Style::new().mask().wrap().linger().whenever(Condition::NEVER).is_plain());
+
+    assert!(!Red.foreground().is_plain());
+    assert!(!Style::new().on_primary().is_plain());
+    assert!(!Style::new().underline_blue().is_plain());
+    assert!(!Style::new().dim().is_plain());
+    assert!(Style::new().dim().without(yansi::Attribute::Dim).is_plain());
+    assert!(Red.on_blue().downgrade(yansi::ColorLevel::None).is_plain());
+
+    #[cfg(feature = "hyperlink")]
+    assert!(!Style::new().link("https://docs.rs").is_plain());
+}
+
+#[test]
+fn bitor() {
+    use yansi::Attribute::*;
+
+    assert_eq!(Bold | Italic, Style::new().bold().italic());
+    assert_eq!(Bold | Bold, Style::new().bold());
+    assert_eq!(Red | Bold, Red.bold());
+    assert_eq!(Underline | Blue, Blue.underline());
+    assert_eq!(Red | Bold | Underline, Red.bold().underline());
+    assert_eq!(Bold | Underline | Red, Red.bold().underline());
+    assert_eq!(Green.on_black() | Dim, Green.on_black().dim());
+    assert_eq!(Dim | Green.on_black(), Green.on_black().dim());
+    assert_eq!(Style::new() | Strike, Strike.style());
+    assert_eq!(Blue.on_red() | Bold | Green, Green.on_red().bold());
+
+    // Quirks and conditions are retained.
+    let style = Red.mask().whenever(Condition::NEVER) | Bold;
+    assert_eq!(style.condition, Some(Condition::NEVER));
+    assert!(style.quirks().eq([yansi::Quirk::Mask]));
+
+    let mut style = Red.foreground();
+    style |= Italic;
+    style |= Blink;
+    assert_eq!(style, Red.italic().blink());
+
+    let style: Style = Red | Bold | Underline;
+    assert_eq!(style.ansi().to_string(), "\x1B[1;4;31m");
+    assert_eq!((Bold | Italic).ansi().to_string(), "\x1B[1;3m");
+}
+
+#[test]
+fn fmt_transition() {
+    fn transition(from: Style, to: Style) -> String {
+        let mut string = String::new();
+        to.fmt_transition(&from, &mut string).unwrap();
+        string
+    }
+
+    // Add-only transitions write only what changed.
+    assert_eq!(transition(Style::new(), Red.bold()), "\x1B[1;31m");
+    assert_eq!(transition(Red.foreground(), Red.bold().italic()), "\x1B[1;3m");
+    assert_eq!(transition(Red.bold(), Blue.bold()), "\x1B[34m");
+    assert_eq!(transition(Red.bold(), Red.on_green().bold().underline()), "\x1B[4;42m");
+    assert_eq!(transition(Red.on_green(), Blue.on_yellow()), "\x1B[43;34m");
+    assert_eq!(transition(Red.underline(), Red.underline().underline_blue()), "\x1B[58;5;4m");
+    assert_eq!(transition(Red.foreground(), Red.bright()), "\x1B[91m");
+    assert_eq!(transition(BrightRed.foreground(), BrightRed.dull()), "\x1B[31m");
+    assert_eq!(transition(Fixed(1).foreground(), Rgb(1, 2, 3).foreground()), "\x1B[38;2;1;2;3m");
+
+    // Transitions that remove an attribute or color require a reset.
+    assert_eq!(transition(Red.bold(), Red.foreground()), "\x1B[0m\x1B[31m");
+    assert_eq!(transition(Red.bold(), Style::new()), "\x1B[0m");
+    assert_eq!(transition(Red.on_blue(), Red.foreground()), "\x1B[0m\x1B[31m");
+    assert_eq!(transition(Red.on_blue(), Style::new().on_blue()), "\x1B[0m\x1B[44m");
+    assert_eq!(transition(Red.underline_blue(), Red.italic()), "\x1B[0m\x1B[3;31m");
+    assert_eq!(transition(Red.bold().italic(), Green.bold()), "\x1B[0m\x1B[1;32m");
+    assert_eq!(transition(Red.underline(), Red.curly_underline()), "\x1B[0m\x1B[4:3;31m");
+
+    // Identical renderings need nothing.
+    assert_eq!(transition(Style::new(), Style::new()), "");
+    assert_eq!(transition(Red.bold(), Red.bold().mask().linger()), "");
+    assert_eq!(transition(Red.bold(), Red.bold().whenever(Condition::NEVER)), "");
+    assert_eq!(transition(BrightRed.foreground(), Red.bright()), "");
+
+    // Transitions pick up where a prefix leaves off.
+    let (from, to) = (Red.on_blue(), Green.on_blue().bold());
+    let mut string = from.ansi().to_string();
+    to.fmt_transition(&from, &mut string).unwrap();
+    assert_eq!(string, "\x1B[44;31m\x1B[1;32m");
+}
+
+#[test]
+fn diff() {
+    use yansi::Attribute::*;
+
+    // Foreground change.
+    let diff = Red.on_blue().diff(&Green.on_blue());
+    assert_eq!(diff.foreground, Some(Some(Green)));
+    assert_eq!(diff.background, None);
+    assert_eq!(diff.underline_color, None);
+    assert_eq!(diff.added_attributes().count(), 0);
+    assert_eq!(diff.removed_attributes().count(), 0);
+    assert!(!diff.is_empty());
+
+    // Foreground and underline color removal.
+    let diff = Red.underline_blue().diff(&Style::new());
+    assert_eq!(diff.foreground, Some(None));
+    assert_eq!(diff.underline_color, Some(None));
+
+    // Attribute addition.
+    let diff = Red.bold().diff(&Red.bold().italic().underline());
+    assert_eq!(diff.foreground, None);
+    assert!(diff.added_attributes().eq([Italic, Underline]));
+    assert_eq!(diff.removed_attributes().count(), 0);
+
+    // Attribute removal.
+    let diff = Red.bold().italic().diff(&Red.italic());
+    assert_eq!(diff.added_attributes().count(), 0);
+    assert!(diff.removed_attributes().eq([Bold]));
+
+    // Both, at once.
+    let diff = Red.bold().strike().diff(&Red.strike().dim());
+    assert!(diff.added_attributes().eq([Dim]));
+    assert!(diff.removed_attributes().eq([Bold]));
+
+    // Quirks and conditions are ignored, just as by `==`.
+    let (a, b) = (Red.bold(), Red.bold().mask().whenever(Condition::NEVER));
+    assert_eq!(a, b);
+    assert!(a.diff(&b).is_empty());
+    assert!(Style::new().diff(&Style::new()).is_empty());
+}
+
+#[test]
+fn add() {
+    let base = Red.on_black().bold().underline().mask();
+    let over = Yellow.italic().underline().wrap();
+    let expected = Yellow.on_black().bold().italic().underline().mask().wrap();
+    assert_eq!(base + over, base.combine(over));
+    assert_eq!(base + over, Yellow.on_black().bold().italic().underline());
+    assert_eq!("x".paint(base + over).to_string(), "x".paint(expected).to_string());
+
+    // `None` fields on the right preserve those on the left, and vice versa.
+    assert_eq!(base + Style::new(), base);
+    assert_eq!(Style::new() + base, base);
+    assert_eq!(Red.foreground() + Style::new().on_green(), Red.on_green());
+    assert_eq!(Red.underline_blue() + Style::new().underline_red(), Red.underline_red());
+
+    // Conditions on the right win when they're set.
+    let combined = base.whenever(Condition::NEVER) + over;
+    assert_eq!(combined.condition, Some(Condition::NEVER));
+    let combined = base.whenever(Condition::NEVER) + over.whenever(Condition::ALWAYS);
+    assert_eq!(combined.condition, Some(Condition::ALWAYS));
+
+    // Layering is left to right.
+    assert_eq!(Red.foreground() + Green.foreground() + Blue.bold(), Blue.bold());
+
+    let mut style = base;
+    style += over;
+    assert_eq!(style, base + over);
+    style += Style::new();
+    assert_eq!(style, base + over);
+}
+
+#[test]
+fn with_optional_colors() {
+    static BASE: Style = Red.on_blue().bold().underline().underline_green();
+    static NONE: Style = BASE.with_foreground(None).with_background(None);
+
+    assert_eq!(NONE, Style::new().bold().underline().underline_green());
+    assert_eq!(BASE.with_foreground(Some(Yellow)), Yellow.on_blue().bold().underline().underline_green());
+    assert_eq!(BASE.with_background(Some(Yellow)), Red.on_yellow().bold().underline().underline_green());
+    assert_eq!(BASE.with_underline_color(Some(Yellow)).underline_color, Some(Yellow));
+    assert_eq!(BASE.with_underline_color(None).underline_color, None);
+
+    // Equivalent to the builders for `Some`, a no-op when already unset.
+    for color in [Primary, Fixed(3), Rgb(1, 2, 3), BrightCyan] {
+        assert_eq!(Style::new().with_foreground(Some(color)), Style::new().fg(color));
+        assert_eq!(Style::new().with_background(Some(color)), Style::new().bg(color));
+        assert_eq!(Style::new().with_underline_color(Some(color)), Style::new().underline_color(color));
+    }
+
+    assert_eq!(Style::new().with_foreground(None).with_background(None), Style::new());
+
+    // Other properties are preserved.
+    let style = Red.mask().whenever(Condition::NEVER).with_foreground(None);
+    assert_eq!(style.condition, Some(Condition::NEVER));
+    assert!(style.quirks().eq([yansi::Quirk::Mask]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn paint() {
+    static ALERT: Style = Red.bold().on_white().underline_blue();
+
+    let painted = ALERT.paint("text");
+    assert_eq!(painted.value, "text");
+    assert_eq!(painted.style, ALERT);
+
+    for style in [Style::new(), ALERT, Red.wrap(), Red.whenever(Condition::NEVER)] {
+        for enabled in [true, false] {
+            yansi::with_enabled(enabled, || {
+                assert_eq!(style.paint("a").to_string(), "a".paint(style).to_string());
+                assert_eq!(format!("{:>4}", style.paint(7)), format!("{:>4}", 7.paint(style)));
+            });
+        }
+    }
+}
+
+#[test]
+fn sanitize() {
+    use yansi::{Attribute::*, Capabilities};
+
+    let style = Red.on_blue().bold().blink().rapid_blink().conceal().mask();
+    let sanitized = style.sanitize(Capabilities::COMMON);
+    assert_eq!(sanitized, Red.on_blue().bold());
+    assert!(sanitized.attributes().eq([Bold]));
+    assert!(sanitized.quirks().eq([yansi::Quirk::Mask]));
+
+    let common = Style::new().bold().dim().italic().underline().strike();
+    assert_eq!(common.sanitize(Capabilities::COMMON), common);
+    assert_eq!(common.sanitize(Capabilities::NONE), Style::new());
+    assert_eq!(style.sanitize(Capabilities::ALL), style);
+
+    let caps = Capabilities::COMMON.with(Blink).without(Bold);
+    assert!(caps.contains(Blink) && !caps.contains(Bold));
+    assert_eq!(style.sanitize(caps), Red.on_blue().blink());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn prefix_into() {
+    use yansi::Attribute::*;
+
+    let longest = [Bold, Dim, Italic, Underline, Blink, RapidBlink, Invert, Conceal, Strike,
+        DoubleUnderline, Overline, CurlyUnderline, Framed, Encircled]
+        .into_iter()
+        .fold(Rgb(255, 255, 255).on_rgb(255, 255, 255).underline_rgb(255, 255, 255), Style::attr)
+        .reset_before();
+
+    let styles = [
+        Style::new(),
+        Red.foreground(),
+        Red.on_blue().bold().underline_green(),
+        Fixed(200).on_rgb(1, 22, 133).italic().curly_underline(),
+        Red.bright().on_primary().reset_before(),
+        longest,
+    ];
+
+    let mut buf = [0; Style::MAX_PREFIX_LEN];
+    for style in styles {
+        assert_eq!(style.prefix_into(&mut buf), Some(&*style.prefix()));
+        assert_eq!(style.prefix_into(&mut buf).map(str::as_bytes), Some(style.prefix().as_bytes()));
+
+        let len = style.prefix().len();
+        assert_eq!(style.prefix_into(&mut vec![0; len]), Some(&*style.prefix()));
+        if len > 0 {
+            assert_eq!(style.prefix_into(&mut vec![0; len - 1]), None);
+        }
+    }
+
+    assert_eq!(longest.prefix().len(), Style::MAX_PREFIX_LEN);
+}
+
+#[test]
+fn and_whenever() {
+    use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+
+    static A: AtomicBool = AtomicBool::new(false);
+    static B: AtomicBool = AtomicBool::new(false);
+
+    const COND_A: Condition = Condition(|| A.load(SeqCst));
+    const COND_B: Condition = Condition(|| B.load(SeqCst));
+
+    // Without a prior condition, the new condition is set as-is.
+    let style = Red.bold().and_whenever(COND_B);
+    assert_eq!(style.condition, Some(COND_B));
+
+    // With one, both must hold.
+    let style = Red.bold().whenever(COND_A).and_whenever(COND_B);
+    assert_eq!(style.condition, Some(COND_A.and(COND_B)));
+    for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+        A.store(a, SeqCst);
+        B.store(b, SeqCst);
+        assert_eq!(style.enabled(), a && b);
+    }
+
+    // `whenever()` still replaces.
+    assert_eq!(style.whenever(COND_B).condition, Some(COND_B));
+    assert_eq!(style, Red.bold());
+
+    assert!(!Style::new().whenever(Condition::ALWAYS).and_whenever(Condition::NEVER).enabled());
+    assert!(!Style::new().whenever(Condition::NEVER).and_whenever(Condition::ALWAYS).enabled());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sample() {
+    let style = Red.on_black().bold();
+    let sample = style.sample();
+    assert!(sample.contains("\x1b[1;40;31m"));
+    assert!(sample.contains("Sample"));
+    assert!(sample.contains(&format!("{:?}", style)));
+    assert_eq!(sample, format!("\x1b[1;40;31mSample\x1b[0m {:?}", style));
+
+    // The sample is reset even if the style lingers.
+    let lingering = Red.linger();
+    assert_eq!(lingering.sample(), format!("\x1b[31mSample\x1b[0m {:?}", lingering));
+
+    // Styling is emitted even when disabled.
+    let never = Red.whenever(Condition::NEVER);
+    assert!(never.sample().starts_with("\x1b[31mSample\x1b[0m "));
+
+    assert_eq!(Style::new().sample(), format!("Sample {:?}", Style::new()));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use yansi::{Paint, StyledBuf, Style, Condition, Color::*};
+
+fn render(pushes: &[(&str, Style)]) -> String {
+    let mut buf = StyledBuf::new();
+    for (value, style) in pushes {
+        buf.push(value, *style);
+    }
+
+    buf.finish()
+}
+
+#[test]
+fn coalesces() {
+    yansi::with_enabled(true, || {
+        let keyword = Magenta.bold();
+
+        // Runs of the same style share a single prefix and a single reset.
+        let string = render(&[("a", keyword), ("b", keyword), ("c", keyword)]);
+        assert_eq!(string, "\x1B[1;35mabc\x1B[0m");
+        assert_eq!(string.matches("\x1B[").count(), 2);
+
+        // Styles that render the same are the same.
+        let string = render(&[("a", Red.bold()), ("b", Red.bold().linger()), ("c", BrightRed.dull().bold())]);
+        assert_eq!(string, "\x1B[1;31mabc\x1B[0m");
+
+        // No redundant reset + prefix pairs between same-styled pushes.
+        let pushes = [("x", Red.foreground()), ("y", Red.foreground()), ("z", Blue.foreground())];
+        let string = render(&pushes);
+        assert_eq!(string, "\x1B[31mxy\x1B[34mz\x1B[0m");
+        assert!(!string.contains("\x1B[0m\x1B[31m"));
+        let individually: String = pushes.iter().map(|(v, s)| v.paint(*s).to_string()).collect();
+        assert_eq!(individually.matches("\x1B[0m").count(), 3);
+        assert_eq!(yansi::strip(&string), yansi::strip(&individually));
+
+        // Additions are incremental; removals reset.
+        let string = render(&[("a", Red.foreground()), ("b", Red.bold()), ("c", Red.foreground())]);
+        assert_eq!(string, "\x1B[31ma\x1B[1mb\x1B[0m\x1B[31mc\x1B[0m");
+
+        // Plain pushes don't start styling, and styling ends before them.
+        let string = render(&[("a", Style::new()), ("b", Green.foreground()), ("c", Style::new())]);
+        assert_eq!(string, "a\x1B[32mb\x1B[0mc");
+        assert_eq!(render(&[("a", Style::new()), ("b", Style::new())]), "ab");
+        assert_eq!(render(&[]), "");
+
+        // Any `Display` value works.
+        let mut buf = StyledBuf::new();
+        buf.push(1, Red.foreground());
+        buf.push(format_args!("{}+{}", 2, 3), Red.foreground());
+        assert_eq!(buf.finish(), "\x1B[31m12+3\x1B[0m");
+    });
+}
+
+#[test]
+fn conditions_and_masking() {
+    let pushes = [
+        ("a", Red.foreground()),
+        ("b", Blue.whenever(Condition::NEVER)),
+        ("c", Green.mask()),
+        ("d", Green.mask().whenever(Condition::NEVER)),
+    ];
+
+    yansi::with_enabled(true, || {
+        assert_eq!(render(&pushes), "\x1B[31ma\x1B[0mb\x1B[32mc\x1B[0m");
+    });
+
+    yansi::with_enabled(false, || {
+        assert_eq!(render(&pushes), "ab");
+    });
+}